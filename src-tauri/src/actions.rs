@@ -1,15 +1,24 @@
 #[cfg(all(target_os = "macos", target_arch = "aarch64"))]
 use crate::apple_intelligence;
 use crate::audio_feedback::{play_feedback_sound, play_feedback_sound_blocking, SoundType};
+use crate::audio_toolkit::constants::WHISPER_SAMPLE_RATE;
+use crate::input;
 use crate::managers::audio::AudioRecordingManager;
-use crate::managers::history::HistoryManager;
+use crate::managers::benchmark::BenchmarkManager;
+use crate::managers::history::{DictationMetrics, EntryLink, HistoryManager};
+use crate::managers::meeting::{MeetingManager, MEETING_BINDING_ID};
+use crate::managers::secrets::SecretsManager;
 use crate::managers::transcription::TranscriptionManager;
+use crate::output;
+use crate::paste_target::{self, PasteTarget};
+use crate::picker;
+use crate::preview;
 use crate::settings::{get_settings, AppSettings, APPLE_INTELLIGENCE_PROVIDER_ID};
 use crate::shortcut;
 use crate::tray::{change_tray_icon, TrayIconState};
 use crate::utils::{self, show_recording_overlay, show_transcribing_overlay};
 use ferrous_opencc::{config::BuiltinConfig, OpenCC};
-use log::{debug, error};
+use log::{debug, error, warn};
 use once_cell::sync::Lazy;
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -26,7 +35,8 @@ pub trait ShortcutAction: Send + Sync {
 // Transcribe Action
 struct TranscribeAction;
 
-async fn maybe_post_process_transcription(
+pub(crate) async fn maybe_post_process_transcription(
+    app: &AppHandle,
     settings: &AppSettings,
     transcription: &str,
 ) -> Option<String> {
@@ -129,11 +139,7 @@ async fn maybe_post_process_transcription(
         }
     }
 
-    let api_key = settings
-        .post_process_api_keys
-        .get(&provider.id)
-        .cloned()
-        .unwrap_or_default();
+    let api_key = app.state::<Arc<SecretsManager>>().get_api_key(&provider.id);
 
     // Send the chat completion request
     match crate::llm_client::send_chat_completion(&provider, api_key, &model, processed_prompt)
@@ -206,15 +212,39 @@ async fn maybe_convert_chinese_variant(
     }
 }
 
+fn save_metrics(hm: &Arc<HistoryManager>, metrics: DictationMetrics) {
+    debug!(
+        "Dictation metrics: model {}, audio {}ms, speech {}ms, load {}ms, inference {}ms, RTF {:.3}",
+        metrics.model_id,
+        metrics.audio_duration_ms,
+        metrics.speech_duration_ms,
+        metrics.load_ms,
+        metrics.inference_ms,
+        metrics.real_time_factor
+    );
+    if let Err(e) = hm.save_metrics(&metrics) {
+        error!("Failed to save dictation metrics: {}", e);
+    }
+}
+
 impl ShortcutAction for TranscribeAction {
     fn start(&self, app: &AppHandle, binding_id: &str, _shortcut_str: &str) {
         let start_time = Instant::now();
         debug!("TranscribeAction::start called for binding: {}", binding_id);
 
+        // The benchmark swaps models in and out, so a dictation would get whichever is loaded
+        if app.state::<Arc<BenchmarkManager>>().is_running() {
+            warn!("Ignoring dictation while a model benchmark is running");
+            return;
+        }
+
         // Load model in the background
         let tm = app.state::<Arc<TranscriptionManager>>();
         tm.initiate_model_load();
 
+        // Text goes to the window focused now, even if focus moves meanwhile
+        app.state::<PasteTarget>().lock();
+
         let binding_id = binding_id.to_string();
         change_tray_icon(app, TrayIconState::Recording);
         show_recording_overlay(app);
@@ -283,6 +313,11 @@ impl ShortcutAction for TranscribeAction {
         let stop_time = Instant::now();
         debug!("TranscribeAction::stop called for binding: {}", binding_id);
 
+        // Nothing was recorded if the dictation was refused on start
+        if app.state::<Arc<BenchmarkManager>>().is_running() {
+            return;
+        }
+
         let ah = app.clone();
         let rm = Arc::clone(&app.state::<Arc<AudioRecordingManager>>());
         let tm = Arc::clone(&app.state::<Arc<TranscriptionManager>>());
@@ -316,18 +351,38 @@ impl ShortcutAction for TranscribeAction {
 
                 let transcription_time = Instant::now();
                 let samples_clone = samples.clone(); // Clone for history saving
+                let recording_stats = rm.last_recording_stats();
+                let transcribed_secs = samples.len() as f64 / WHISPER_SAMPLE_RATE as f64;
                 match tm.transcribe(samples) {
-                    Ok(transcription) => {
+                    Ok(result) => {
+                        let transcription = result.text;
                         debug!(
                             "Transcription completed in {:?}: '{}'",
                             transcription_time.elapsed(),
                             transcription
                         );
+
+                        let mut metrics = DictationMetrics {
+                            model_id: result.model_id.unwrap_or_default(),
+                            audio_duration_ms: recording_stats.duration.as_millis() as u64,
+                            speech_duration_ms: recording_stats.speech_duration.as_millis() as u64,
+                            load_ms: result.load_duration.as_millis() as u64,
+                            inference_ms: result.inference_duration.as_millis() as u64,
+                            real_time_factor: if transcribed_secs > 0.0 {
+                                result.inference_duration.as_secs_f64() / transcribed_secs
+                            } else {
+                                0.0
+                            },
+                            ..Default::default()
+                        };
+
                         if !transcription.is_empty() {
                             let settings = get_settings(&ah);
                             let mut final_text = transcription.clone();
                             let mut post_processed_text: Option<String> = None;
                             let mut post_process_prompt: Option<String> = None;
+                            let mut used_prompt_id: Option<String> = None;
+                            let post_process_time = Instant::now();
 
                             // First, check if Chinese variant conversion is needed
                             if let Some(converted_text) =
@@ -338,7 +393,8 @@ impl ShortcutAction for TranscribeAction {
                             }
                             // Then apply regular post-processing if enabled
                             else if let Some(processed_text) =
-                                maybe_post_process_transcription(&settings, &transcription).await
+                                maybe_post_process_transcription(&ah, &settings, &transcription)
+                                    .await
                             {
                                 final_text = processed_text.clone();
                                 post_processed_text = Some(processed_text);
@@ -351,15 +407,56 @@ impl ShortcutAction for TranscribeAction {
                                         .find(|p| &p.id == prompt_id)
                                     {
                                         post_process_prompt = Some(prompt.prompt.clone());
+                                        used_prompt_id = Some(prompt.id.clone());
                                     }
                                 }
                             }
 
+                            if post_processed_text.is_some() {
+                                metrics.post_process_ms =
+                                    Some(post_process_time.elapsed().as_millis() as u64);
+                            }
+
+                            let previewed = settings.paste_preview_enabled;
+                            if previewed {
+                                let Some(reviewed) = preview::review(
+                                    &ah,
+                                    &transcription,
+                                    final_text.clone(),
+                                    used_prompt_id.clone(),
+                                )
+                                .await
+                                else {
+                                    debug!("Dictation discarded in preview");
+                                    save_metrics(&hm, metrics);
+                                    change_tray_icon(&ah, TrayIconState::Idle);
+                                    return;
+                                };
+
+                                if reviewed.prompt_id != used_prompt_id {
+                                    post_process_prompt =
+                                        reviewed.prompt_id.as_ref().and_then(|id| {
+                                            settings
+                                                .post_process_prompts
+                                                .iter()
+                                                .find(|p| &p.id == id)
+                                                .map(|p| p.prompt.clone())
+                                        });
+                                    used_prompt_id = reviewed.prompt_id;
+                                }
+                                // Edits are kept in history as the pasted text
+                                final_text = reviewed.text;
+                                post_processed_text =
+                                    (final_text != transcription).then(|| final_text.clone());
+                            }
+
                             // Save to history with post-processed text and prompt
                             let hm_clone = Arc::clone(&hm);
                             let transcription_for_history = transcription.clone();
+                            let entry = Arc::new(EntryLink::default());
+                            let entry_clone = Arc::clone(&entry);
                             tauri::async_runtime::spawn(async move {
-                                if let Err(e) = hm_clone
+                                match hm_clone
                                     .save_transcription(
                                         samples_clone,
                                         transcription_for_history,
@@ -368,37 +465,73 @@ impl ShortcutAction for TranscribeAction {
                                     )
                                     .await
                                 {
-                                    error!("Failed to save transcription to history: {}", e);
+                                    // Undone before it was saved
+                                    Ok(id) if entry_clone.set_saved(id) => {
+                                        if let Err(e) = hm_clone.discard_entry(id).await {
+                                            error!("Failed to mark dictation as discarded: {}", e);
+                                        }
+                                    }
+                                    Ok(_) => {}
+                                    Err(e) => {
+                                        error!("Failed to save transcription to history: {}", e)
+                                    }
                                 }
                             });
 
+                            output::deliver(&ah, &final_text, used_prompt_id.as_deref());
+
                             // Hide the overlay first to ensure focus returns to the previous window
                             utils::hide_recording_overlay(&ah);
                             change_tray_icon(&ah, TrayIconState::Idle);
 
-                            let ah_clone = ah.clone();
-                            tauri::async_runtime::spawn(async move {
-                                // Give the OS a moment to switch focus back
-                                tokio::time::sleep(std::time::Duration::from_millis(100)).await;
-
-                                let final_text = final_text.clone();
-                                let paste_time = Instant::now();
-                                
-                                // Create a fresh clone for inside the run_on_main_thread closure
-                                let ah_inner = ah_clone.clone();
-                                ah_clone.run_on_main_thread(move || {
-                                    match utils::paste(final_text, ah_inner) {
-                                        Ok(()) => debug!(
-                                            "Text pasted successfully in {:?}",
-                                            paste_time.elapsed()
-                                        ),
-                                        Err(e) => error!("Failed to paste transcription: {}", e),
+                            if previewed {
+                                // The overlay had focus for editing, so the
+                                // original window gets it back first
+                                paste_target::paste_into_target(&ah, final_text, Some(entry));
+                                save_metrics(&hm, metrics);
+                            } else {
+                                let ah_clone = ah.clone();
+                                let hm_clone = Arc::clone(&hm);
+                                tauri::async_runtime::spawn(async move {
+                                    // Give the OS a moment to switch focus back
+                                    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+
+                                    let final_text = final_text.clone();
+                                    if !paste_target::prepare(&ah_clone, &final_text, &entry).await
+                                    {
+                                        save_metrics(&hm_clone, metrics);
+                                        return;
                                     }
-                                }).unwrap_or_else(|e| {
-                                    error!("Failed to run paste on main thread: {:?}", e);
+                                    let paste_time = Instant::now();
+
+                                    // Create a fresh clone for inside the run_on_main_thread closure
+                                    let ah_inner = ah_clone.clone();
+                                    ah_clone
+                                        .run_on_main_thread(move || {
+                                            match utils::paste(final_text, ah_inner) {
+                                                Ok(()) => {
+                                                    utils::link_last_insertion(entry);
+                                                    debug!(
+                                                        "Text pasted successfully in {:?}",
+                                                        paste_time.elapsed()
+                                                    );
+                                                    metrics.paste_ms = Some(
+                                                        paste_time.elapsed().as_millis() as u64,
+                                                    );
+                                                }
+                                                Err(e) => {
+                                                    error!("Failed to paste transcription: {}", e)
+                                                }
+                                            }
+                                            save_metrics(&hm_clone, metrics);
+                                        })
+                                        .unwrap_or_else(|e| {
+                                            error!("Failed to run paste on main thread: {:?}", e);
+                                        });
                                 });
-                            });
+                            }
                         } else {
+                            save_metrics(&hm, metrics);
                             utils::hide_recording_overlay(&ah);
                             change_tray_icon(&ah, TrayIconState::Idle);
                         }
@@ -423,6 +556,30 @@ impl ShortcutAction for TranscribeAction {
     }
 }
 
+// Meeting Action
+struct MeetingAction;
+
+impl ShortcutAction for MeetingAction {
+    fn start(&self, app: &AppHandle, _binding_id: &str, _shortcut_str: &str) {
+        let mm = app.state::<Arc<MeetingManager>>();
+        if mm.is_active() {
+            play_feedback_sound(app, SoundType::Stop);
+            if let Err(e) = mm.stop() {
+                error!("Failed to stop meeting: {}", e);
+            }
+        } else {
+            match mm.start() {
+                Ok(()) => play_feedback_sound(app, SoundType::Start),
+                Err(e) => error!("Failed to start meeting: {}", e),
+            }
+        }
+    }
+
+    fn stop(&self, _app: &AppHandle, _binding_id: &str, _shortcut_str: &str) {
+        // The meeting shortcut toggles on press
+    }
+}
+
 // Cancel Action
 struct CancelAction;
 
@@ -436,6 +593,125 @@ impl ShortcutAction for CancelAction {
     }
 }
 
+/// Binding that removes the last pasted dictation again.
+pub const UNDO_BINDING_ID: &str = "undo_last_dictation";
+
+// Undo Last Dictation Action
+struct UndoAction;
+
+impl ShortcutAction for UndoAction {
+    fn start(&self, app: &AppHandle, _binding_id: &str, _shortcut_str: &str) {
+        let ah = app.clone();
+        std::thread::spawn(move || {
+            // Backspace or Ctrl+Z sent with the shortcut's Ctrl+Alt still
+            // down would be a different shortcut altogether
+            if let Err(e) = input::wait_for_modifiers_released() {
+                warn!("Not undoing dictation: {}", e);
+                return;
+            }
+            let Some(insertion) = utils::take_last_insertion() else {
+                debug!("No dictation to undo");
+                return;
+            };
+
+            let ah_clone = ah.clone();
+            ah.run_on_main_thread(move || {
+                if let Err(e) = utils::undo_insertion(&ah_clone, &insertion) {
+                    error!("Failed to undo dictation: {}", e);
+                    return;
+                }
+
+                // Still being saved otherwise, and discarded once it is
+                let Some(id) = insertion.entry.and_then(|entry| entry.discard()) else {
+                    return;
+                };
+                let hm = Arc::clone(&ah_clone.state::<Arc<HistoryManager>>());
+                tauri::async_runtime::spawn(async move {
+                    if let Err(e) = hm.discard_entry(id).await {
+                        error!("Failed to mark dictation as discarded: {}", e);
+                    }
+                });
+            })
+            .unwrap_or_else(|e| {
+                error!("Failed to run undo on main thread: {:?}", e);
+            });
+        });
+    }
+
+    fn stop(&self, _app: &AppHandle, _binding_id: &str, _shortcut_str: &str) {
+        // Nothing to do on stop for undo
+    }
+}
+
+/// Binding that pastes the most recent dictation again.
+pub const REPASTE_BINDING_ID: &str = "repaste_last";
+
+// Re-paste Last Dictation Action
+struct RepasteAction;
+
+impl ShortcutAction for RepasteAction {
+    fn start(&self, app: &AppHandle, _binding_id: &str, _shortcut_str: &str) {
+        let ah = app.clone();
+        let hm = Arc::clone(&app.state::<Arc<HistoryManager>>());
+        tauri::async_runtime::spawn(async move {
+            // Ctrl+V sent with the shortcut's Ctrl+Alt still down would be
+            // Ctrl+Alt+V, which can be this very shortcut
+            let released = tauri::async_runtime::spawn_blocking(input::wait_for_modifiers_released)
+                .await
+                .map_err(|e| e.to_string())
+                .and_then(|result| result);
+            if let Err(e) = released {
+                warn!("Not re-pasting dictation: {}", e);
+                return;
+            }
+
+            let entry = match hm.get_latest_entry().await {
+                Ok(Some(entry)) => entry,
+                Ok(None) => {
+                    debug!("No dictation to re-paste");
+                    return;
+                }
+                Err(e) => {
+                    error!("Failed to load the last dictation: {}", e);
+                    return;
+                }
+            };
+
+            let ah_clone = ah.clone();
+            // Left unlinked, so undoing the re-paste only removes the text
+            // and keeps the entry it came from
+            ah.run_on_main_thread(move || {
+                if let Err(e) = utils::paste(entry.pasted_text(), ah_clone) {
+                    error!("Failed to re-paste dictation: {}", e);
+                }
+            })
+            .unwrap_or_else(|e| {
+                error!("Failed to run re-paste on main thread: {:?}", e);
+            });
+        });
+    }
+
+    fn stop(&self, _app: &AppHandle, _binding_id: &str, _shortcut_str: &str) {
+        // Nothing to do on stop for re-paste
+    }
+}
+
+/// Binding that opens the history picker.
+pub const HISTORY_PICKER_BINDING_ID: &str = "history_picker";
+
+// History Picker Action
+struct HistoryPickerAction;
+
+impl ShortcutAction for HistoryPickerAction {
+    fn start(&self, app: &AppHandle, _binding_id: &str, _shortcut_str: &str) {
+        picker::show_history_picker(app);
+    }
+
+    fn stop(&self, _app: &AppHandle, _binding_id: &str, _shortcut_str: &str) {
+        // The picker stays open until an entry is chosen or it's dismissed
+    }
+}
+
 // Test Action
 struct TestAction;
 
@@ -466,10 +742,26 @@ pub static ACTION_MAP: Lazy<HashMap<String, Arc<dyn ShortcutAction>>> = Lazy::ne
         "transcribe".to_string(),
         Arc::new(TranscribeAction) as Arc<dyn ShortcutAction>,
     );
+    map.insert(
+        MEETING_BINDING_ID.to_string(),
+        Arc::new(MeetingAction) as Arc<dyn ShortcutAction>,
+    );
     map.insert(
         "cancel".to_string(),
         Arc::new(CancelAction) as Arc<dyn ShortcutAction>,
     );
+    map.insert(
+        UNDO_BINDING_ID.to_string(),
+        Arc::new(UndoAction) as Arc<dyn ShortcutAction>,
+    );
+    map.insert(
+        REPASTE_BINDING_ID.to_string(),
+        Arc::new(RepasteAction) as Arc<dyn ShortcutAction>,
+    );
+    map.insert(
+        HISTORY_PICKER_BINDING_ID.to_string(),
+        Arc::new(HistoryPickerAction) as Arc<dyn ShortcutAction>,
+    );
     map.insert(
         "test".to_string(),
         Arc::new(TestAction) as Arc<dyn ShortcutAction>,
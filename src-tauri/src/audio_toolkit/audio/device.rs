@@ -50,3 +50,47 @@ pub fn list_output_devices() -> Result<Vec<CpalDeviceInfo>, Box<dyn std::error::
 
     Ok(out)
 }
+
+/// What an input device can be configured to, for presenting capture options.
+pub struct InputCapabilities {
+    pub channels: u16,
+    pub sample_rates: Vec<u32>,
+    pub buffer_size: Option<(u32, u32)>,
+}
+
+/// Rates offered to the user, filtered to those the device supports.
+const COMMON_SAMPLE_RATES: [u32; 9] =
+    [8000, 16000, 22050, 24000, 32000, 44100, 48000, 88200, 96000];
+
+pub fn input_capabilities(
+    device: &cpal::Device,
+) -> Result<InputCapabilities, Box<dyn std::error::Error>> {
+    let mut capabilities = InputCapabilities {
+        channels: 0,
+        sample_rates: Vec::new(),
+        buffer_size: None,
+    };
+
+    for range in device.supported_input_configs()? {
+        capabilities.channels = capabilities.channels.max(range.channels());
+
+        for rate in COMMON_SAMPLE_RATES {
+            if range.min_sample_rate().0 <= rate
+                && range.max_sample_rate().0 >= rate
+                && !capabilities.sample_rates.contains(&rate)
+            {
+                capabilities.sample_rates.push(rate);
+            }
+        }
+
+        if let cpal::SupportedBufferSize::Range { min, max } = *range.buffer_size() {
+            capabilities.buffer_size = Some(match capabilities.buffer_size {
+                Some((lo, hi)) => (lo.min(min), hi.max(max)),
+                None => (min, max),
+            });
+        }
+    }
+
+    capabilities.sample_rates.sort_unstable();
+    Ok(capabilities)
+}
@@ -0,0 +1,390 @@
+use rustfft::{num_complex::Complex32, Fft, FftPlanner};
+use std::collections::VecDeque;
+use std::f32::consts::PI;
+use std::sync::Arc;
+
+use crate::audio_toolkit::constants;
+
+/// Rumble, handling noise and mains hum sit below this; speech does not.
+const HIGH_PASS_HZ: f32 = 80.0;
+
+const DENOISE_WINDOW: usize = 512;
+const DENOISE_HOP: usize = DENOISE_WINDOW / 2;
+const DENOISE_BINS: usize = DENOISE_WINDOW / 2 + 1;
+/// Subtracting a little more than the estimated noise avoids "musical" noise.
+const OVER_SUBTRACTION: f32 = 2.0;
+/// Never attenuate a bin by more than 20 dB, which would sound hollow.
+const MIN_GAIN: f32 = 0.1;
+/// Bin powers are averaged over time before comparing them with the noise
+/// floor, so random fluctuations of the noise are not mistaken for speech.
+const POWER_SMOOTHING: f32 = 0.7;
+const NOISE_FALL_RATE: f32 = 0.1;
+const NOISE_RISE_RATE: f32 = 0.01;
+
+const AGC_TARGET_RMS: f32 = 0.1; // -20 dBFS
+/// Frames quieter than this are background, and must not be amplified.
+const AGC_GATE_RMS: f32 = 0.003; // -50 dBFS
+const AGC_MAX_GAIN: f32 = 10.0;
+const AGC_MIN_GAIN: f32 = 0.25;
+const AGC_ATTACK_RATE: f32 = 0.3;
+const AGC_RELEASE_RATE: f32 = 0.05;
+
+const CLIP_LEVEL: f32 = 0.999;
+/// A single full-scale sample can be a legitimate peak; a run of them is not.
+const CLIP_RUN: usize = 3;
+
+/// Which conditioning stages run between resampling and the VAD.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DspConfig {
+    pub high_pass: bool,
+    pub denoise: bool,
+    pub agc: bool,
+    pub clipping_detection: bool,
+    /// Keep unprocessed and processed copies of each recording for comparison.
+    pub ab_capture: bool,
+}
+
+impl DspConfig {
+    fn processes_audio(&self) -> bool {
+        self.high_pass || self.denoise || self.agc
+    }
+}
+
+/// Both versions of a recording, before VAD trimming, at the Whisper sample rate.
+#[derive(Clone, Debug, Default)]
+pub struct AbCapture {
+    pub raw: Vec<f32>,
+    pub processed: Vec<f32>,
+}
+
+/// Second-order Butterworth high-pass filter.
+struct HighPass {
+    b: [f32; 3],
+    a: [f32; 2],
+    x: [f32; 2],
+    y: [f32; 2],
+}
+
+impl HighPass {
+    fn new(cutoff_hz: f32, sample_rate: f32) -> Self {
+        let w0 = 2.0 * PI * cutoff_hz / sample_rate;
+        let alpha = w0.sin() / (2.0 * std::f32::consts::FRAC_1_SQRT_2);
+        let cos = w0.cos();
+        let a0 = 1.0 + alpha;
+
+        Self {
+            b: [
+                (1.0 + cos) / 2.0 / a0,
+                -(1.0 + cos) / a0,
+                (1.0 + cos) / 2.0 / a0,
+            ],
+            a: [-2.0 * cos / a0, (1.0 - alpha) / a0],
+            x: [0.0; 2],
+            y: [0.0; 2],
+        }
+    }
+
+    fn process(&mut self, samples: &mut [f32]) {
+        for sample in samples {
+            let x0 = *sample;
+            let y0 = self.b[0] * x0 + self.b[1] * self.x[0] + self.b[2] * self.x[1]
+                - self.a[0] * self.y[0]
+                - self.a[1] * self.y[1];
+            self.x = [x0, self.x[0]];
+            self.y = [y0, self.y[0]];
+            *sample = y0;
+        }
+    }
+
+    fn reset(&mut self) {
+        self.x = [0.0; 2];
+        self.y = [0.0; 2];
+    }
+}
+
+/// Spectral noise suppression: tracks the noise floor of every frequency bin
+/// and attenuates bins that do not rise above it. Output lags input by one hop
+/// (16 ms), and always has the same length as the input.
+struct Denoiser {
+    forward: Arc<dyn Fft<f32>>,
+    inverse: Arc<dyn Fft<f32>>,
+    window: Vec<f32>,
+    input: Vec<f32>,
+    output: VecDeque<f32>,
+    overlap: Vec<f32>,
+    power: Vec<f32>,
+    noise: Option<Vec<f32>>,
+    gains: Vec<f32>,
+    buffer: Vec<Complex32>,
+}
+
+impl Denoiser {
+    fn new() -> Self {
+        let mut planner = FftPlanner::<f32>::new();
+
+        // Square-root Hann applied on analysis and synthesis sums to unity
+        // at 50% overlap
+        let window = (0..DENOISE_WINDOW)
+            .map(|i| (0.5 - 0.5 * (2.0 * PI * i as f32 / DENOISE_WINDOW as f32).cos()).sqrt())
+            .collect();
+
+        let mut denoiser = Self {
+            forward: planner.plan_fft_forward(DENOISE_WINDOW),
+            inverse: planner.plan_fft_inverse(DENOISE_WINDOW),
+            window,
+            input: Vec::with_capacity(DENOISE_WINDOW),
+            output: VecDeque::new(),
+            overlap: vec![0.0; DENOISE_HOP],
+            power: vec![0.0; DENOISE_BINS],
+            noise: None,
+            gains: vec![1.0; DENOISE_BINS],
+            buffer: vec![Complex32::new(0.0, 0.0); DENOISE_WINDOW],
+        };
+        denoiser.reset();
+        denoiser
+    }
+
+    fn process(&mut self, samples: &mut [f32]) {
+        for &sample in samples.iter() {
+            self.input.push(sample);
+            if self.input.len() == DENOISE_WINDOW {
+                self.process_window();
+                self.input.drain(..DENOISE_HOP);
+            }
+        }
+        for sample in samples.iter_mut() {
+            *sample = self.output.pop_front().unwrap_or(0.0);
+        }
+    }
+
+    fn process_window(&mut self) {
+        for ((value, &sample), &weight) in self.buffer.iter_mut().zip(&self.input).zip(&self.window)
+        {
+            *value = Complex32::new(sample * weight, 0.0);
+        }
+        self.forward.process(&mut self.buffer);
+
+        let first_window = self.noise.is_none();
+        for (power, bin) in self.power.iter_mut().zip(&self.buffer[..DENOISE_BINS]) {
+            *power = if first_window {
+                bin.norm_sqr()
+            } else {
+                POWER_SMOOTHING * *power + (1.0 - POWER_SMOOTHING) * bin.norm_sqr()
+            };
+        }
+        let power = &self.power;
+        let noise = self.noise.get_or_insert_with(|| power.clone());
+
+        for k in 0..DENOISE_BINS {
+            let rate = if power[k] < noise[k] {
+                NOISE_FALL_RATE
+            } else {
+                NOISE_RISE_RATE
+            };
+            noise[k] += (power[k] - noise[k]) * rate;
+
+            // Wiener-style gain, smoothed over time
+            let snr = (power[k] / (OVER_SUBTRACTION * noise[k]).max(f32::EPSILON) - 1.0).max(0.0);
+            let gain = (snr / (snr + 1.0)).max(MIN_GAIN);
+            self.gains[k] = 0.5 * self.gains[k] + 0.5 * gain;
+
+            self.buffer[k] *= self.gains[k];
+            if k > 0 && k < DENOISE_WINDOW - k {
+                self.buffer[DENOISE_WINDOW - k] *= self.gains[k];
+            }
+        }
+
+        self.inverse.process(&mut self.buffer);
+        let scale = 1.0 / DENOISE_WINDOW as f32;
+        for i in 0..DENOISE_HOP {
+            let sample = self.buffer[i].re * scale * self.window[i];
+            self.output.push_back(self.overlap[i] + sample);
+            self.overlap[i] =
+                self.buffer[i + DENOISE_HOP].re * scale * self.window[i + DENOISE_HOP];
+        }
+    }
+
+    fn reset(&mut self) {
+        self.input.clear();
+        self.overlap.fill(0.0);
+        self.power.fill(0.0);
+        self.gains.fill(1.0);
+        self.noise = None;
+        // The latency is primed with silence
+        self.output.clear();
+        self.output.extend(std::iter::repeat_n(0.0, DENOISE_HOP));
+    }
+}
+
+/// Brings speech towards a constant level, so quiet microphones are not
+/// mistaken for silence and loud ones are not clipped further.
+struct Agc {
+    gain: f32,
+}
+
+impl Agc {
+    fn process(&mut self, samples: &mut [f32]) {
+        if samples.is_empty() {
+            return;
+        }
+
+        let rms = (samples.iter().map(|s| s * s).sum::<f32>() / samples.len() as f32).sqrt();
+        if rms > AGC_GATE_RMS {
+            let desired = (AGC_TARGET_RMS / rms).clamp(AGC_MIN_GAIN, AGC_MAX_GAIN);
+            // Turn down quickly to avoid overshoot, turn up slowly to avoid pumping
+            let rate = if desired < self.gain {
+                AGC_ATTACK_RATE
+            } else {
+                AGC_RELEASE_RATE
+            };
+            self.gain += (desired - self.gain) * rate;
+        }
+
+        for sample in samples {
+            *sample = (*sample * self.gain).clamp(-1.0, 1.0);
+        }
+    }
+}
+
+/// The optional conditioning stages, applied in order: high-pass, noise
+/// suppression, then gain control.
+pub struct DspChain {
+    config: DspConfig,
+    high_pass: HighPass,
+    denoiser: Denoiser,
+    agc: Agc,
+    scratch: Vec<f32>,
+}
+
+impl DspChain {
+    pub fn new(config: DspConfig) -> Self {
+        Self {
+            config,
+            high_pass: HighPass::new(HIGH_PASS_HZ, constants::WHISPER_SAMPLE_RATE as f32),
+            denoiser: Denoiser::new(),
+            agc: Agc { gain: 1.0 },
+            scratch: Vec::new(),
+        }
+    }
+
+    pub fn config(&self) -> DspConfig {
+        self.config
+    }
+
+    /// Stages that are switched on start from a clean state.
+    pub fn set_config(&mut self, config: DspConfig) {
+        if config.high_pass && !self.config.high_pass {
+            self.high_pass.reset();
+        }
+        if config.denoise && !self.config.denoise {
+            self.denoiser.reset();
+        }
+        if config.agc && !self.config.agc {
+            self.agc.gain = 1.0;
+        }
+        self.config = config;
+    }
+
+    /// Returns the conditioned frame, or the input untouched when every stage
+    /// is off.
+    pub fn process<'a>(&'a mut self, frame: &'a [f32]) -> &'a [f32] {
+        if !self.config.processes_audio() {
+            return frame;
+        }
+
+        self.scratch.clear();
+        self.scratch.extend_from_slice(frame);
+        if self.config.high_pass {
+            self.high_pass.process(&mut self.scratch);
+        }
+        if self.config.denoise {
+            self.denoiser.process(&mut self.scratch);
+        }
+        if self.config.agc {
+            self.agc.process(&mut self.scratch);
+        }
+        &self.scratch
+    }
+}
+
+/// Whether the input reached full scale for several consecutive samples,
+/// meaning the microphone gain is set too high.
+pub fn is_clipping(samples: &[f32]) -> bool {
+    let mut run = 0;
+    for sample in samples {
+        if sample.abs() >= CLIP_LEVEL {
+            run += 1;
+            if run >= CLIP_RUN {
+                return true;
+            }
+        } else {
+            run = 0;
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RATE: f32 = constants::WHISPER_SAMPLE_RATE as f32;
+
+    fn tone(hz: f32, amplitude: f32, len: usize) -> Vec<f32> {
+        (0..len)
+            .map(|i| amplitude * (2.0 * PI * hz * i as f32 / RATE).sin())
+            .collect()
+    }
+
+    fn rms(samples: &[f32]) -> f32 {
+        (samples.iter().map(|s| s * s).sum::<f32>() / samples.len() as f32).sqrt()
+    }
+
+    /// Runs audio through the chain in 30 ms frames.
+    fn run(chain: &mut DspChain, audio: &[f32]) -> Vec<f32> {
+        audio
+            .chunks(480)
+            .flat_map(|frame| chain.process(frame).to_vec())
+            .collect()
+    }
+
+    #[test]
+    fn test_stages_condition_audio() {
+        let config = DspConfig::default();
+
+        // High-pass removes rumble but keeps speech frequencies
+        let mut chain = DspChain::new(DspConfig {
+            high_pass: true,
+            ..config
+        });
+        assert!(rms(&run(&mut chain, &tone(20.0, 0.5, 16000))[8000..]) < 0.05);
+        assert!(rms(&run(&mut chain, &tone(500.0, 0.5, 16000))[8000..]) > 0.3);
+
+        // Steady noise is suppressed, and the output keeps the input's length
+        let mut chain = DspChain::new(DspConfig {
+            denoise: true,
+            ..config
+        });
+        let mut seed = 1u32;
+        let noise: Vec<f32> = (0..32000)
+            .map(|_| {
+                seed = seed.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+                (seed >> 8) as f32 / (1u32 << 24) as f32 * 0.1 - 0.05
+            })
+            .collect();
+        let denoised = run(&mut chain, &noise);
+        assert_eq!(denoised.len(), noise.len());
+        assert!(rms(&denoised[16000..]) < rms(&noise[16000..]) * 0.5);
+
+        // Gain control lifts a quiet voice towards the target level
+        let mut chain = DspChain::new(DspConfig {
+            agc: true,
+            ..config
+        });
+        let quiet = tone(300.0, 0.02, 32000);
+        assert!(rms(&run(&mut chain, &quiet)[24000..]) > AGC_TARGET_RMS * 0.8);
+
+        assert!(is_clipping(&[0.2, 1.0, 1.0, 1.0, 0.3]));
+        assert!(!is_clipping(&[0.2, 1.0, 0.9, 1.0, 0.3]));
+    }
+}
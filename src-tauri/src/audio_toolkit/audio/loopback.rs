@@ -0,0 +1,88 @@
+use std::sync::mpsc;
+
+/// Captures what the default output device is playing, as mono f32 samples.
+///
+/// On Linux this records the PulseAudio monitor of the default sink through
+/// `parec`, which PipeWire also provides via pipewire-pulse. Other platforms
+/// are not supported yet.
+pub struct LoopbackCapture {
+    #[cfg(target_os = "linux")]
+    child: std::process::Child,
+    #[cfg(target_os = "linux")]
+    reader: Option<std::thread::JoinHandle<()>>,
+}
+
+impl LoopbackCapture {
+    pub fn is_supported() -> bool {
+        cfg!(target_os = "linux")
+    }
+
+    #[cfg(target_os = "linux")]
+    pub fn start(
+        sample_rate: u32,
+        sample_tx: mpsc::Sender<Vec<f32>>,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        use std::io::Read;
+        use std::process::{Command, Stdio};
+
+        let mut child = Command::new("parec")
+            .args([
+                "--device=@DEFAULT_MONITOR@",
+                "--raw",
+                "--format=float32le",
+                "--channels=1",
+                "--latency-msec=20",
+            ])
+            .arg(format!("--rate={}", sample_rate))
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|e| format!("Failed to start parec (install pulseaudio-utils): {}", e))?;
+
+        let mut stdout = child.stdout.take().ok_or("parec has no output")?;
+
+        // 10 ms chunks keep latency close to that of the microphone stream
+        let chunk_bytes = (sample_rate as usize / 100).max(1) * 4;
+        let reader = std::thread::spawn(move || {
+            let mut bytes = vec![0u8; chunk_bytes];
+            loop {
+                if stdout.read_exact(&mut bytes).is_err() {
+                    log::debug!("System audio capture ended");
+                    return;
+                }
+                let samples = bytes
+                    .chunks_exact(4)
+                    .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+                    .collect();
+                if sample_tx.send(samples).is_err() {
+                    return;
+                }
+            }
+        });
+
+        Ok(Self {
+            child,
+            reader: Some(reader),
+        })
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    pub fn start(
+        _sample_rate: u32,
+        _sample_tx: mpsc::Sender<Vec<f32>>,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        Err("System audio capture is not supported on this platform yet".into())
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl Drop for LoopbackCapture {
+    fn drop(&mut self) {
+        // Killing parec closes its output, which ends the reader
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+        if let Some(reader) = self.reader.take() {
+            let _ = reader.join();
+        }
+    }
+}
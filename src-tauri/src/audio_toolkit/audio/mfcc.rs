@@ -0,0 +1,145 @@
+use rustfft::{num_complex::Complex32, Fft, FftPlanner};
+use std::sync::Arc;
+
+use crate::audio_toolkit::constants;
+
+pub(crate) const WINDOW_SAMPLES: usize = 400; // 25 ms
+const HOP_SAMPLES: usize = 160; // 10 ms
+const FFT_SIZE: usize = 512;
+const MEL_BANDS: usize = 26;
+pub(crate) const CEPSTRAL_COEFFS: usize = 13;
+const PRE_EMPHASIS: f32 = 0.97;
+
+pub(crate) type Features = Vec<[f32; CEPSTRAL_COEFFS]>;
+
+/// Computes mean-normalised MFCCs, which are robust enough to compare short
+/// recordings of the same phrase by the same speaker.
+pub(crate) struct MfccExtractor {
+    fft: Arc<dyn Fft<f32>>,
+    window: Vec<f32>,
+    mel_filters: Vec<Vec<(usize, f32)>>,
+    dct: Vec<[f32; MEL_BANDS]>,
+}
+
+impl MfccExtractor {
+    pub(crate) fn new() -> Self {
+        let fft = FftPlanner::<f32>::new().plan_fft_forward(FFT_SIZE);
+
+        // Hamming window
+        let window = (0..WINDOW_SAMPLES)
+            .map(|i| {
+                0.54 - 0.46
+                    * (2.0 * std::f32::consts::PI * i as f32 / (WINDOW_SAMPLES - 1) as f32).cos()
+            })
+            .collect();
+
+        // Triangular filters spaced evenly on the mel scale
+        let hz_to_mel = |hz: f32| 2595.0 * (1.0 + hz / 700.0).log10();
+        let mel_to_hz = |mel: f32| 700.0 * (10f32.powf(mel / 2595.0) - 1.0);
+        let max_mel = hz_to_mel(constants::WHISPER_SAMPLE_RATE as f32 / 2.0);
+        let bins: Vec<usize> = (0..MEL_BANDS + 2)
+            .map(|i| {
+                let hz = mel_to_hz(max_mel * i as f32 / (MEL_BANDS + 1) as f32);
+                ((FFT_SIZE + 1) as f32 * hz / constants::WHISPER_SAMPLE_RATE as f32) as usize
+            })
+            .collect();
+        let mel_filters = (0..MEL_BANDS)
+            .map(|band| {
+                let (start, center, end) = (bins[band], bins[band + 1], bins[band + 2]);
+                (start..end)
+                    .map(|bin| {
+                        let weight = if bin < center {
+                            (bin - start) as f32 / (center - start).max(1) as f32
+                        } else {
+                            (end - bin) as f32 / (end - center).max(1) as f32
+                        };
+                        (bin, weight)
+                    })
+                    .collect()
+            })
+            .collect();
+
+        // DCT-II basis
+        let dct = (0..CEPSTRAL_COEFFS)
+            .map(|k| {
+                let mut row = [0.0; MEL_BANDS];
+                for (n, value) in row.iter_mut().enumerate() {
+                    *value = (std::f32::consts::PI * k as f32 * (n as f32 + 0.5)
+                        / MEL_BANDS as f32)
+                        .cos();
+                }
+                row
+            })
+            .collect();
+
+        Self {
+            fft,
+            window,
+            mel_filters,
+            dct,
+        }
+    }
+
+    pub(crate) fn features(&self, samples: &[f32]) -> Features {
+        let mut features = self.cepstra(samples);
+
+        // Cepstral mean normalisation removes the microphone's colouring
+        if !features.is_empty() {
+            let mut mean = [0.0; CEPSTRAL_COEFFS];
+            for frame in &features {
+                for (m, c) in mean.iter_mut().zip(frame) {
+                    *m += c / features.len() as f32;
+                }
+            }
+            for frame in &mut features {
+                for (c, m) in frame.iter_mut().zip(&mean) {
+                    *c -= m;
+                }
+            }
+        }
+        features
+    }
+
+    /// Raw cepstra without mean normalisation, which would also remove most of
+    /// what distinguishes one voice from another.
+    pub(crate) fn cepstra(&self, samples: &[f32]) -> Features {
+        let mut emphasized = Vec::with_capacity(samples.len());
+        let mut previous = 0.0;
+        for &sample in samples {
+            emphasized.push(sample - PRE_EMPHASIS * previous);
+            previous = sample;
+        }
+
+        let mut features = Features::new();
+        let mut buffer = vec![Complex32::new(0.0, 0.0); FFT_SIZE];
+        let mut start = 0;
+        while start + WINDOW_SAMPLES <= emphasized.len() {
+            buffer.fill(Complex32::new(0.0, 0.0));
+            for (i, (&sample, &weight)) in emphasized[start..start + WINDOW_SAMPLES]
+                .iter()
+                .zip(&self.window)
+                .enumerate()
+            {
+                buffer[i].re = sample * weight;
+            }
+            self.fft.process(&mut buffer);
+
+            let mut log_mel = [0.0; MEL_BANDS];
+            for (value, filter) in log_mel.iter_mut().zip(&self.mel_filters) {
+                let energy: f32 = filter
+                    .iter()
+                    .map(|&(bin, weight)| buffer[bin].norm_sqr() * weight)
+                    .sum();
+                *value = energy.max(1e-10).ln();
+            }
+
+            let mut coeffs = [0.0; CEPSTRAL_COEFFS];
+            for (coeff, basis) in coeffs.iter_mut().zip(&self.dct) {
+                *coeff = basis.iter().zip(&log_mel).map(|(b, m)| b * m).sum();
+            }
+            features.push(coeffs);
+            start += HOP_SAMPLES;
+        }
+        features
+    }
+}
@@ -1,12 +1,23 @@
 // Re-export all audio components
 mod device;
+mod dsp;
+mod loopback;
+mod mfcc;
 mod recorder;
 mod resampler;
 mod utils;
 mod visualizer;
 
-pub use device::{list_input_devices, list_output_devices, CpalDeviceInfo};
-pub use recorder::AudioRecorder;
+pub use device::{
+    input_capabilities, list_input_devices, list_output_devices, CpalDeviceInfo, InputCapabilities,
+};
+pub use dsp::{AbCapture, DspConfig};
+pub use loopback::LoopbackCapture;
+pub(crate) use mfcc::{Features, MfccExtractor, CEPSTRAL_COEFFS, WINDOW_SAMPLES};
+pub use recorder::{
+    AudioChunk, AudioRecorder, CaptureConfig, CaptureSource, ChunkingConfig, HandsFreeConfig,
+    HandsFreeEvent,
+};
 pub use resampler::FrameResampler;
-pub use utils::save_wav_file;
+pub use utils::{concat_wav_files, read_wav_file, save_wav_file, write_wav_file};
 pub use visualizer::AudioVisualiser;
@@ -1,7 +1,8 @@
 use std::{
+    collections::VecDeque,
     io::Error,
     sync::{mpsc, Arc, Mutex},
-    time::Duration,
+    time::{Duration, Instant},
 };
 
 use cpal::{
@@ -10,15 +11,105 @@ use cpal::{
 };
 
 use crate::audio_toolkit::{
-    audio::{AudioVisualiser, FrameResampler},
+    audio::{
+        dsp::{self, AbCapture, DspChain, DspConfig},
+        AudioVisualiser, FrameResampler, LoopbackCapture,
+    },
     constants,
     vad::{self, VadFrame},
     VoiceActivityDetector,
 };
 
+/// Length of the frames fed to the VAD.
+const FRAME_DURATION: Duration = Duration::from_millis(30);
+
+/// Ignore speech onsets for a moment after a hands-free dictation ends, so the
+/// stop feedback sound does not immediately start the next one.
+const HANDS_FREE_REARM_DELAY: Duration = Duration::from_millis(1000);
+
+/// Report clipping at most this often, so a loud passage raises one warning.
+const CLIPPING_REPORT_INTERVAL: Duration = Duration::from_secs(5);
+
+/// How long the consumer waits for audio before serving commands anyway, so a
+/// stalled device cannot block `stop` or `close`.
+const SAMPLE_TIMEOUT: Duration = Duration::from_millis(100);
+
+/// System audio that the microphone has not caught up with after this long is
+/// dropped, so the two sources cannot drift apart.
+const MAX_SYSTEM_AUDIO_BACKLOG: Duration = Duration::from_millis(200);
+
+/// A stream that delivers nothing for this long is reported as failed. Some
+/// backends go silent instead of raising an error when a device is unplugged.
+const STREAM_STALL_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// When and for how long hands-free dictation keeps recording.
+#[derive(Clone, Copy, Debug)]
+pub struct HandsFreeConfig {
+    /// Trailing silence that ends a dictation.
+    pub silence: Duration,
+    /// Upper bound for a single dictation.
+    pub max_duration: Duration,
+}
+
+/// How audio is read from the input device. Unset fields use the device's
+/// own defaults.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CaptureConfig {
+    /// Weight of each input channel in the mono signal. Empty, or a length
+    /// that does not match the device, averages all channels.
+    pub channel_mix: Vec<f32>,
+    pub sample_rate: Option<u32>,
+    /// Frames per hardware buffer, clamped to what the device supports.
+    pub buffer_size: Option<u32>,
+}
+
+/// What is recorded: the microphone, what the computer plays, or both.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CaptureSource {
+    #[default]
+    Microphone,
+    SystemAudio,
+    /// Microphone and system audio summed, e.g. for both sides of a call.
+    Mixed,
+}
+
+/// Where a long recording is split into separately delivered chunks.
+#[derive(Clone, Copy, Debug)]
+pub struct ChunkingConfig {
+    /// Trailing silence after speech that closes a chunk.
+    pub silence: Duration,
+    /// Upper bound for a single chunk, reached only by uninterrupted speech.
+    pub max_duration: Duration,
+}
+
+/// A piece of a chunked recording.
+#[derive(Clone, Debug)]
+pub struct AudioChunk {
+    /// Offset of the first sample from the start of the recording.
+    pub start: Duration,
+    pub samples: Vec<f32>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HandsFreeEvent {
+    /// Speech was detected while idle and recording has already begun.
+    SpeechStarted,
+    /// The configured trailing silence elapsed.
+    SpeechEnded,
+    /// The dictation reached its maximum length.
+    MaxDurationReached,
+}
+
 enum Cmd {
     Start,
     Stop(mpsc::Sender<Vec<f32>>),
+    HandsFree(Option<HandsFreeConfig>),
+    WakeWord(Option<Box<dyn VoiceActivityDetector>>),
+    Trim(bool),
+    Preview(bool),
+    Dsp(DspConfig),
+    PreRoll(Duration),
+    Chunking(Option<ChunkingConfig>),
     Shutdown,
 }
 
@@ -28,6 +119,20 @@ pub struct AudioRecorder {
     worker_handle: Option<std::thread::JoinHandle<()>>,
     vad: Option<Arc<Mutex<Box<dyn vad::VoiceActivityDetector>>>>,
     level_cb: Option<Arc<dyn Fn(Vec<f32>) + Send + Sync + 'static>>,
+    hands_free_cb: Option<Arc<dyn Fn(HandsFreeEvent) + Send + Sync + 'static>>,
+    hands_free: Option<HandsFreeConfig>,
+    wake_word: Option<Box<dyn VoiceActivityDetector>>,
+    vad_cb: Option<Arc<dyn Fn(bool) + Send + Sync + 'static>>,
+    trim: bool,
+    dsp: DspConfig,
+    clipping_cb: Option<Arc<dyn Fn() + Send + Sync + 'static>>,
+    error_cb: Option<Arc<dyn Fn(String) + Send + Sync + 'static>>,
+    ab_capture: Arc<Mutex<Option<AbCapture>>>,
+    pre_roll: Duration,
+    capture: CaptureConfig,
+    source: CaptureSource,
+    chunking: Option<ChunkingConfig>,
+    chunk_cb: Option<Arc<dyn Fn(AudioChunk) + Send + Sync + 'static>>,
 }
 
 impl AudioRecorder {
@@ -38,6 +143,20 @@ impl AudioRecorder {
             worker_handle: None,
             vad: None,
             level_cb: None,
+            hands_free_cb: None,
+            hands_free: None,
+            wake_word: None,
+            vad_cb: None,
+            trim: true,
+            dsp: DspConfig::default(),
+            clipping_cb: None,
+            error_cb: None,
+            ab_capture: Arc::new(Mutex::new(None)),
+            pre_roll: Duration::ZERO,
+            capture: CaptureConfig::default(),
+            source: CaptureSource::default(),
+            chunking: None,
+            chunk_cb: None,
         })
     }
 
@@ -54,6 +173,153 @@ impl AudioRecorder {
         self
     }
 
+    /// Receives the VAD's decision for every frame while previewing.
+    pub fn with_vad_callback<F>(mut self, cb: F) -> Self
+    where
+        F: Fn(bool) + Send + Sync + 'static,
+    {
+        self.vad_cb = Some(Arc::new(cb));
+        self
+    }
+
+    /// Swaps the VAD in place, so parameter changes apply to the open stream.
+    pub fn replace_vad(&self, vad: Box<dyn VoiceActivityDetector>) {
+        if let Some(current) = &self.vad {
+            *current.lock().unwrap() = vad;
+        }
+    }
+
+    /// When disabled, the VAD still runs but recordings keep every frame.
+    pub fn set_trimming(&mut self, trim: bool) -> Result<(), Box<dyn std::error::Error>> {
+        self.trim = trim;
+        if let Some(tx) = &self.cmd_tx {
+            tx.send(Cmd::Trim(trim))?;
+        }
+        Ok(())
+    }
+
+    /// Classifies incoming audio without recording it, reporting each frame
+    /// through the VAD callback. Hands-free dictation is paused meanwhile.
+    pub fn set_preview(&self, enabled: bool) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(tx) = &self.cmd_tx {
+            tx.send(Cmd::Preview(enabled))?;
+        }
+        Ok(())
+    }
+
+    /// Configures the conditioning applied to audio before the VAD sees it.
+    pub fn set_dsp(&mut self, config: DspConfig) -> Result<(), Box<dyn std::error::Error>> {
+        self.dsp = config;
+        if let Some(tx) = &self.cmd_tx {
+            tx.send(Cmd::Dsp(config))?;
+        }
+        Ok(())
+    }
+
+    /// How much audio from before `start` is included in a recording.
+    pub fn set_pre_roll(&mut self, duration: Duration) -> Result<(), Box<dyn std::error::Error>> {
+        self.pre_roll = duration;
+        if let Some(tx) = &self.cmd_tx {
+            tx.send(Cmd::PreRoll(duration))?;
+        }
+        Ok(())
+    }
+
+    /// Splits recordings into chunks delivered to the chunk callback as they
+    /// complete, instead of keeping all audio until `stop`. `stop` then
+    /// delivers the last chunk the same way and returns no samples.
+    pub fn set_chunking(
+        &mut self,
+        config: Option<ChunkingConfig>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        self.chunking = config;
+        if let Some(tx) = &self.cmd_tx {
+            tx.send(Cmd::Chunking(config))?;
+        }
+        Ok(())
+    }
+
+    /// Called from the audio thread with each chunk of a chunked recording.
+    pub fn with_chunk_callback<F>(mut self, cb: F) -> Self
+    where
+        F: Fn(AudioChunk) + Send + Sync + 'static,
+    {
+        self.chunk_cb = Some(Arc::new(cb));
+        self
+    }
+
+    /// Channel mix, sample rate and buffer size used the next time the stream
+    /// is opened.
+    pub fn set_capture_config(&mut self, config: CaptureConfig) {
+        self.capture = config;
+    }
+
+    /// Where audio comes from the next time the stream is opened.
+    pub fn set_capture_source(&mut self, source: CaptureSource) {
+        self.source = source;
+    }
+
+    /// Called when the input clips during a recording, if clipping detection
+    /// is enabled.
+    pub fn with_clipping_callback<F>(mut self, cb: F) -> Self
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        self.clipping_cb = Some(Arc::new(cb));
+        self
+    }
+
+    /// Called when the open stream fails or stops delivering audio, e.g. because
+    /// the device was unplugged. The recorder must be closed and reopened to
+    /// recover.
+    pub fn with_error_callback<F>(mut self, cb: F) -> Self
+    where
+        F: Fn(String) + Send + Sync + 'static,
+    {
+        self.error_cb = Some(Arc::new(cb));
+        self
+    }
+
+    /// Raw and processed audio of the last recording, if A/B capture was on.
+    pub fn take_ab_capture(&self) -> Option<AbCapture> {
+        self.ab_capture.lock().unwrap().take()
+    }
+
+    /// Called from the audio thread, so the callback must not block on the
+    /// recorder (e.g. by calling `stop`) before returning.
+    pub fn with_hands_free_callback<F>(mut self, cb: F) -> Self
+    where
+        F: Fn(HandsFreeEvent) + Send + Sync + 'static,
+    {
+        self.hands_free_cb = Some(Arc::new(cb));
+        self
+    }
+
+    /// Enables or disables starting recordings on speech onset. Requires a VAD.
+    pub fn set_hands_free(
+        &mut self,
+        config: Option<HandsFreeConfig>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        self.hands_free = config;
+        if let Some(tx) = &self.cmd_tx {
+            tx.send(Cmd::HandsFree(config))?;
+        }
+        Ok(())
+    }
+
+    /// In hands-free mode, only start recording after this detector reports
+    /// speech. The stream must be reconfigured each time it is opened.
+    pub fn set_wake_word(
+        &mut self,
+        detector: Option<Box<dyn VoiceActivityDetector>>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        match &self.cmd_tx {
+            Some(tx) => tx.send(Cmd::WakeWord(detector))?,
+            None => self.wake_word = detector,
+        }
+        Ok(())
+    }
+
     pub fn open(&mut self, device: Option<Device>) -> Result<(), Box<dyn std::error::Error>> {
         if self.worker_handle.is_some() {
             return Ok(()); // already open
@@ -63,65 +329,75 @@ impl AudioRecorder {
         let (cmd_tx, cmd_rx) = mpsc::channel::<Cmd>();
 
         let host = crate::audio_toolkit::get_cpal_host();
-        let device = match device {
-            Some(dev) => dev,
-            None => host
-                .default_input_device()
-                .ok_or_else(|| Error::new(std::io::ErrorKind::NotFound, "No input device found"))?,
+        let device = match (device, self.source) {
+            // Recording only system audio works without any microphone
+            (_, CaptureSource::SystemAudio) => None,
+            (Some(dev), _) => Some(dev),
+            (None, _) => Some(host.default_input_device().ok_or_else(|| {
+                Error::new(std::io::ErrorKind::NotFound, "No input device found")
+            })?),
         };
 
         let thread_device = device.clone();
-        let vad = self.vad.clone();
-        // Move the optional level callback into the worker thread
-        let level_cb = self.level_cb.clone();
+        let capture = self.capture.clone();
+        let source = self.source;
+        // Move the VAD, callbacks and current configuration into the worker thread
+        let mut setup = ConsumerSetup {
+            vad: self.vad.clone(),
+            level_cb: self.level_cb.clone(),
+            hands_free: self.hands_free,
+            hands_free_cb: self.hands_free_cb.clone(),
+            wake_word: self.wake_word.take(),
+            vad_cb: self.vad_cb.clone(),
+            trim: self.trim,
+            dsp: self.dsp,
+            clipping_cb: self.clipping_cb.clone(),
+            error_cb: self.error_cb.clone(),
+            ab_capture: self.ab_capture.clone(),
+            pre_roll: self.pre_roll,
+            system_audio: None,
+            chunking: self.chunking,
+            chunk_cb: self.chunk_cb.clone(),
+        };
+
+        let error_cb = self.error_cb.clone();
+        let (init_tx, init_rx) = mpsc::channel::<Result<(), String>>();
 
         let worker = std::thread::spawn(move || {
-            let config = AudioRecorder::get_preferred_config(&thread_device)
-                .expect("failed to fetch preferred config");
-
-            let sample_rate = config.sample_rate().0;
-            let channels = config.channels() as usize;
-
-            log::info!(
-                "Using device: {:?}\nSample rate: {}\nChannels: {}\nFormat: {:?}",
-                thread_device.name(),
-                sample_rate,
-                channels,
-                config.sample_format()
+            let started = AudioRecorder::start_capture(
+                thread_device.as_ref(),
+                source,
+                &capture,
+                sample_tx,
+                error_cb,
             );
-
-            let stream = match config.sample_format() {
-                cpal::SampleFormat::U8 => {
-                    AudioRecorder::build_stream::<u8>(&thread_device, &config, sample_tx, channels)
-                        .unwrap()
-                }
-                cpal::SampleFormat::I8 => {
-                    AudioRecorder::build_stream::<i8>(&thread_device, &config, sample_tx, channels)
-                        .unwrap()
-                }
-                cpal::SampleFormat::I16 => {
-                    AudioRecorder::build_stream::<i16>(&thread_device, &config, sample_tx, channels)
-                        .unwrap()
+            let mut active = match started {
+                Ok(active) => {
+                    let _ = init_tx.send(Ok(()));
+                    active
                 }
-                cpal::SampleFormat::I32 => {
-                    AudioRecorder::build_stream::<i32>(&thread_device, &config, sample_tx, channels)
-                        .unwrap()
+                Err(e) => {
+                    let _ = init_tx.send(Err(e.to_string()));
+                    return;
                 }
-                cpal::SampleFormat::F32 => {
-                    AudioRecorder::build_stream::<f32>(&thread_device, &config, sample_tx, channels)
-                        .unwrap()
-                }
-                _ => panic!("unsupported sample format"),
             };
-
-            stream.play().expect("failed to start stream");
+            setup.system_audio = active.system_audio.take();
 
             // keep the stream alive while we process samples
-            run_consumer(sample_rate, vad, sample_rx, cmd_rx, level_cb);
+            run_consumer(active.sample_rate, setup, sample_rx, cmd_rx);
             // stream is dropped here, after run_consumer returns
         });
 
-        self.device = Some(device);
+        // Report a device that cannot be opened instead of leaving a dead worker
+        let init = init_rx
+            .recv()
+            .unwrap_or_else(|_| Err("audio worker exited during setup".to_string()));
+        if let Err(e) = init {
+            let _ = worker.join();
+            return Err(Error::other(format!("Failed to open input stream: {}", e)).into());
+        }
+
+        self.device = device;
         self.cmd_tx = Some(cmd_tx);
         self.worker_handle = Some(worker);
 
@@ -154,17 +430,112 @@ impl AudioRecorder {
         Ok(())
     }
 
+    /// Starts the configured sources. Samples to process arrive through
+    /// `sample_tx`; in mixed mode system audio is delivered separately.
+    fn start_capture(
+        device: Option<&cpal::Device>,
+        source: CaptureSource,
+        capture: &CaptureConfig,
+        sample_tx: mpsc::Sender<Vec<f32>>,
+        error_cb: Option<Arc<dyn Fn(String) + Send + Sync + 'static>>,
+    ) -> Result<ActiveCapture, Box<dyn std::error::Error>> {
+        let require_device = || device.ok_or("No input device selected");
+
+        match source {
+            CaptureSource::Microphone => {
+                let (stream, sample_rate) =
+                    Self::start_stream(require_device()?, capture, sample_tx, error_cb)?;
+                Ok(ActiveCapture {
+                    _stream: Some(stream),
+                    _loopback: None,
+                    sample_rate,
+                    system_audio: None,
+                })
+            }
+            CaptureSource::SystemAudio => {
+                let sample_rate = constants::WHISPER_SAMPLE_RATE;
+                log::info!("Capturing system audio at {} Hz", sample_rate);
+                Ok(ActiveCapture {
+                    _stream: None,
+                    _loopback: Some(LoopbackCapture::start(sample_rate, sample_tx)?),
+                    sample_rate,
+                    system_audio: None,
+                })
+            }
+            CaptureSource::Mixed => {
+                let (stream, sample_rate) =
+                    Self::start_stream(require_device()?, capture, sample_tx, error_cb)?;
+                // Captured at the microphone's rate so the two can be summed
+                let (system_tx, system_rx) = mpsc::channel();
+                log::info!("Mixing in system audio at {} Hz", sample_rate);
+                Ok(ActiveCapture {
+                    _stream: Some(stream),
+                    _loopback: Some(LoopbackCapture::start(sample_rate, system_tx)?),
+                    sample_rate,
+                    system_audio: Some(system_rx),
+                })
+            }
+        }
+    }
+
+    /// Opens and starts the input stream, returning it with its sample rate.
+    fn start_stream(
+        device: &cpal::Device,
+        capture: &CaptureConfig,
+        sample_tx: mpsc::Sender<Vec<f32>>,
+        error_cb: Option<Arc<dyn Fn(String) + Send + Sync + 'static>>,
+    ) -> Result<(cpal::Stream, u32), Box<dyn std::error::Error>> {
+        let supported = AudioRecorder::get_preferred_config(device, capture)?;
+
+        let sample_rate = supported.sample_rate().0;
+        let mix = channel_weights(&capture.channel_mix, supported.channels() as usize);
+        let config = stream_config(&supported, capture.buffer_size);
+
+        log::info!(
+            "Using device: {:?}\nSample rate: {}\nChannels: {}\nFormat: {:?}\nBuffer: {:?}",
+            device.name(),
+            sample_rate,
+            mix.len(),
+            supported.sample_format(),
+            config.buffer_size
+        );
+
+        let stream = match supported.sample_format() {
+            cpal::SampleFormat::U8 => {
+                AudioRecorder::build_stream::<u8>(device, &config, sample_tx, mix, error_cb)?
+            }
+            cpal::SampleFormat::I8 => {
+                AudioRecorder::build_stream::<i8>(device, &config, sample_tx, mix, error_cb)?
+            }
+            cpal::SampleFormat::I16 => {
+                AudioRecorder::build_stream::<i16>(device, &config, sample_tx, mix, error_cb)?
+            }
+            cpal::SampleFormat::I32 => {
+                AudioRecorder::build_stream::<i32>(device, &config, sample_tx, mix, error_cb)?
+            }
+            cpal::SampleFormat::F32 => {
+                AudioRecorder::build_stream::<f32>(device, &config, sample_tx, mix, error_cb)?
+            }
+            format => return Err(format!("Unsupported sample format {:?}", format).into()),
+        };
+
+        stream.play()?;
+        Ok((stream, sample_rate))
+    }
+
     fn build_stream<T>(
         device: &cpal::Device,
-        config: &cpal::SupportedStreamConfig,
+        config: &cpal::StreamConfig,
         sample_tx: mpsc::Sender<Vec<f32>>,
-        channels: usize,
+        mix: Vec<f32>,
+        error_cb: Option<Arc<dyn Fn(String) + Send + Sync + 'static>>,
     ) -> Result<cpal::Stream, cpal::BuildStreamError>
     where
         T: Sample + SizedSample + Send + 'static,
         f32: cpal::FromSample<T>,
     {
         let mut output_buffer = Vec::new();
+        let channels = mix.len();
 
         let stream_cb = move |data: &[T], _: &cpal::InputCallbackInfo| {
             output_buffer.clear();
@@ -173,16 +544,16 @@ impl AudioRecorder {
                 // Direct conversion without intermediate Vec
                 output_buffer.extend(data.iter().map(|&sample| sample.to_sample::<f32>()));
             } else {
-                // Convert to mono directly
+                // Mix down to mono directly
                 let frame_count = data.len() / channels;
                 output_buffer.reserve(frame_count);
 
                 for frame in data.chunks_exact(channels) {
                     let mono_sample = frame
                         .iter()
-                        .map(|&sample| sample.to_sample::<f32>())
-                        .sum::<f32>()
-                        / channels as f32;
+                        .zip(&mix)
+                        .map(|(&sample, &weight)| sample.to_sample::<f32>() * weight)
+                        .sum::<f32>();
                     output_buffer.push(mono_sample);
                 }
             }
@@ -193,67 +564,385 @@ impl AudioRecorder {
         };
 
         device.build_input_stream(
-            &config.clone().into(),
+            config,
             stream_cb,
-            |err| log::error!("Stream error: {}", err),
+            move |err| {
+                log::error!("Stream error: {}", err);
+                if let Some(cb) = &error_cb {
+                    cb(err.to_string());
+                }
+            },
             None,
         )
     }
 
     fn get_preferred_config(
         device: &cpal::Device,
+        capture: &CaptureConfig,
     ) -> Result<cpal::SupportedStreamConfig, Box<dyn std::error::Error>> {
-        let supported_configs = device.supported_input_configs()?;
-        let mut best_config: Option<cpal::SupportedStreamConfigRange> = None;
+        // A user-chosen rate the device cannot do falls back to 16kHz
+        let mut rates = vec![constants::WHISPER_SAMPLE_RATE];
+        if let Some(rate) = capture
+            .sample_rate
+            .filter(|&r| r != constants::WHISPER_SAMPLE_RATE)
+        {
+            rates.insert(0, rate);
+        }
+
+        for rate in rates {
+            if let Some(config) = Self::best_config_for_rate(device, capture, rate)? {
+                return Ok(config.with_sample_rate(cpal::SampleRate(rate)));
+            }
+        }
+
+        // If no config supports either rate, fall back to default
+        Ok(device.default_input_config()?)
+    }
+
+    fn best_config_for_rate(
+        device: &cpal::Device,
+        capture: &CaptureConfig,
+        rate: u32,
+    ) -> Result<Option<cpal::SupportedStreamConfigRange>, Box<dyn std::error::Error>> {
+        // Prefer the channel count the mix was made for, then F32 > I16 > I32 > others
+        let score = |config: &cpal::SupportedStreamConfigRange| {
+            let channels_match = capture.channel_mix.len() == config.channels() as usize;
+            let format = match config.sample_format() {
+                cpal::SampleFormat::F32 => 4,
+                cpal::SampleFormat::I16 => 3,
+                cpal::SampleFormat::I32 => 2,
+                _ => 1,
+            };
+            (channels_match, format)
+        };
 
-        // Try to find a config that supports 16kHz, prioritizing better formats
-        for config_range in supported_configs {
-            if config_range.min_sample_rate().0 <= constants::WHISPER_SAMPLE_RATE
-                && config_range.max_sample_rate().0 >= constants::WHISPER_SAMPLE_RATE
+        let mut best_config: Option<cpal::SupportedStreamConfigRange> = None;
+        for config_range in device.supported_input_configs()? {
+            if config_range.min_sample_rate().0 <= rate && config_range.max_sample_rate().0 >= rate
             {
                 match best_config {
-                    None => best_config = Some(config_range),
-                    Some(ref current) => {
-                        // Prioritize F32 > I16 > I32 > others
-                        let score = |fmt: cpal::SampleFormat| match fmt {
-                            cpal::SampleFormat::F32 => 4,
-                            cpal::SampleFormat::I16 => 3,
-                            cpal::SampleFormat::I32 => 2,
-                            _ => 1,
-                        };
-
-                        if score(config_range.sample_format()) > score(current.sample_format()) {
-                            best_config = Some(config_range);
-                        }
-                    }
+                    Some(ref current) if score(&config_range) <= score(current) => {}
+                    _ => best_config = Some(config_range),
                 }
             }
         }
+        Ok(best_config)
+    }
+}
+
+/// Sources kept alive by the worker while it processes their samples.
+struct ActiveCapture {
+    _stream: Option<cpal::Stream>,
+    _loopback: Option<LoopbackCapture>,
+    sample_rate: u32,
+    system_audio: Option<mpsc::Receiver<Vec<f32>>>,
+}
+
+/// Adds buffered system audio onto a chunk of microphone samples. The
+/// microphone sets the pace; missing system audio is treated as silence.
+fn mix_system_audio(
+    samples: &mut [f32],
+    system_rx: &mpsc::Receiver<Vec<f32>>,
+    backlog: &mut VecDeque<f32>,
+    max_backlog: usize,
+) {
+    while let Ok(chunk) = system_rx.try_recv() {
+        backlog.extend(chunk);
+    }
+    let excess = backlog.len().saturating_sub(max_backlog);
+    backlog.drain(..excess);
+
+    let mixed = samples.len().min(backlog.len());
+    for (sample, system) in samples.iter_mut().zip(backlog.drain(..mixed)) {
+        *sample = (*sample + system).clamp(-1.0, 1.0);
+    }
+}
+
+/// Per-channel weights for mixing `channels` channels down to mono.
+fn channel_weights(mix: &[f32], channels: usize) -> Vec<f32> {
+    if mix.len() == channels && mix.iter().any(|&weight| weight > 0.0) {
+        return mix.to_vec();
+    }
+    if !mix.is_empty() {
+        log::warn!(
+            "Channel mix has {} weights but the device has {} channels, averaging instead",
+            mix.len(),
+            channels
+        );
+    }
+    vec![1.0 / channels as f32; channels]
+}
+
+fn stream_config(
+    config: &cpal::SupportedStreamConfig,
+    buffer_size: Option<u32>,
+) -> cpal::StreamConfig {
+    let mut stream_config: cpal::StreamConfig = config.clone().into();
+    if let Some(frames) = buffer_size {
+        match config.buffer_size() {
+            cpal::SupportedBufferSize::Range { min, max } => {
+                stream_config.buffer_size = cpal::BufferSize::Fixed(frames.clamp(*min, *max));
+            }
+            cpal::SupportedBufferSize::Unknown => {
+                log::debug!("Device does not report buffer sizes, using its default");
+            }
+        }
+    }
+    stream_config
+}
+
+/// Per-frame bookkeeping for hands-free dictation.
+struct HandsFreeState {
+    config: HandsFreeConfig,
+    /// Whether the current recording was started by speech onset. Recordings
+    /// started by a shortcut are never ended automatically.
+    active: bool,
+    frames: u32,
+    silent_frames: u32,
+    ended: bool,
+    rearm_frames: u32,
+}
 
-        if let Some(config) = best_config {
-            return Ok(config.with_sample_rate(cpal::SampleRate(constants::WHISPER_SAMPLE_RATE)));
+impl HandsFreeState {
+    fn new(config: HandsFreeConfig) -> Self {
+        Self {
+            config,
+            active: false,
+            frames: 0,
+            silent_frames: 0,
+            ended: false,
+            rearm_frames: 0,
         }
+    }
 
-        // If no config supports 16kHz, fall back to default
-        Ok(device.default_input_config()?)
+    fn begin(&mut self) {
+        self.active = true;
+        self.frames = 0;
+        self.silent_frames = 0;
+        self.ended = false;
+    }
+
+    fn finish(&mut self) {
+        if self.active {
+            self.rearm_frames = frames_for(HANDS_FREE_REARM_DELAY);
+        }
+        self.active = false;
     }
+
+    /// Returns false while still ignoring onsets after the last dictation.
+    fn is_armed(&mut self) -> bool {
+        if self.rearm_frames > 0 {
+            self.rearm_frames -= 1;
+            return false;
+        }
+        true
+    }
+
+    /// Tracks one recorded frame and reports when the dictation should end.
+    fn track(&mut self, is_speech: bool) -> Option<HandsFreeEvent> {
+        if !self.active || self.ended {
+            return None;
+        }
+
+        self.frames += 1;
+        self.silent_frames = if is_speech { 0 } else { self.silent_frames + 1 };
+
+        let event = if self.silent_frames >= frames_for(self.config.silence) {
+            Some(HandsFreeEvent::SpeechEnded)
+        } else if self.frames >= frames_for(self.config.max_duration) {
+            Some(HandsFreeEvent::MaxDurationReached)
+        } else {
+            None
+        };
+        self.ended = event.is_some();
+        event
+    }
+}
+
+fn frames_for(duration: Duration) -> u32 {
+    (duration.as_millis() / FRAME_DURATION.as_millis()).max(1) as u32
+}
+
+/// The most recent frames captured while not recording, so a recording can
+/// begin slightly before it was requested.
+struct PreRoll {
+    frames: VecDeque<Vec<f32>>,
+    capacity: usize,
+}
+
+impl PreRoll {
+    fn new(duration: Duration) -> Self {
+        let mut pre_roll = Self {
+            frames: VecDeque::new(),
+            capacity: 0,
+        };
+        pre_roll.set_duration(duration);
+        pre_roll
+    }
+
+    fn set_duration(&mut self, duration: Duration) {
+        self.capacity = (duration.as_millis() / FRAME_DURATION.as_millis()) as usize;
+        while self.frames.len() > self.capacity {
+            self.frames.pop_front();
+        }
+    }
+
+    fn push(&mut self, frame: &[f32]) {
+        if self.capacity == 0 {
+            return;
+        }
+        // Reuse the oldest frame's allocation once full
+        let mut buf = if self.frames.len() >= self.capacity {
+            self.frames.pop_front().unwrap_or_default()
+        } else {
+            Vec::with_capacity(frame.len())
+        };
+        buf.clear();
+        buf.extend_from_slice(frame);
+        self.frames.push_back(buf);
+    }
+
+    fn clear(&mut self) {
+        self.frames.clear();
+    }
+}
+
+/// Cuts a chunked recording at pauses in speech.
+struct Chunker {
+    config: ChunkingConfig,
+    /// Frames recorded since the recording started.
+    elapsed_frames: u32,
+    /// Frame at which the audio of the pending chunk starts.
+    start_frame: Option<u32>,
+    silent_frames: u32,
+    has_speech: bool,
+}
+
+impl Chunker {
+    fn new(config: ChunkingConfig) -> Self {
+        Self {
+            config,
+            elapsed_frames: 0,
+            start_frame: None,
+            silent_frames: 0,
+            has_speech: false,
+        }
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new(self.config);
+    }
+
+    /// Accounts for one recorded frame, whose audio has already been added to
+    /// `samples`, and returns a chunk once a pause or the length limit is hit.
+    fn track(&mut self, is_speech: bool, samples: &mut Vec<f32>) -> Option<AudioChunk> {
+        self.elapsed_frames += 1;
+        if samples.is_empty() {
+            return None;
+        }
+
+        // The VAD prefill means a new chunk can begin a few frames back
+        let frame_samples =
+            constants::WHISPER_SAMPLE_RATE as usize * FRAME_DURATION.as_millis() as usize / 1000;
+        let buffered = (samples.len() / frame_samples) as u32;
+        let start = *self
+            .start_frame
+            .get_or_insert(self.elapsed_frames.saturating_sub(buffered));
+
+        self.has_speech |= is_speech;
+        self.silent_frames = if is_speech { 0 } else { self.silent_frames + 1 };
+
+        let paused = self.has_speech && self.silent_frames >= frames_for(self.config.silence);
+        let full = self.elapsed_frames - start >= frames_for(self.config.max_duration);
+        if paused || full {
+            self.take(samples)
+        } else {
+            None
+        }
+    }
+
+    /// Ends the pending chunk. Audio without any speech is dropped.
+    fn take(&mut self, samples: &mut Vec<f32>) -> Option<AudioChunk> {
+        let start = self.start_frame.take().unwrap_or(self.elapsed_frames);
+        let has_speech = std::mem::take(&mut self.has_speech);
+        self.silent_frames = 0;
+        let samples = std::mem::take(samples);
+
+        (has_speech && !samples.is_empty()).then(|| AudioChunk {
+            start: FRAME_DURATION * start,
+            samples,
+        })
+    }
+}
+
+/// Everything the consumer thread takes over from the recorder when opened.
+struct ConsumerSetup {
+    vad: Option<Arc<Mutex<Box<dyn vad::VoiceActivityDetector>>>>,
+    level_cb: Option<Arc<dyn Fn(Vec<f32>) + Send + Sync + 'static>>,
+    hands_free: Option<HandsFreeConfig>,
+    hands_free_cb: Option<Arc<dyn Fn(HandsFreeEvent) + Send + Sync + 'static>>,
+    wake_word: Option<Box<dyn VoiceActivityDetector>>,
+    vad_cb: Option<Arc<dyn Fn(bool) + Send + Sync + 'static>>,
+    trim: bool,
+    dsp: DspConfig,
+    clipping_cb: Option<Arc<dyn Fn() + Send + Sync + 'static>>,
+    error_cb: Option<Arc<dyn Fn(String) + Send + Sync + 'static>>,
+    ab_capture: Arc<Mutex<Option<AbCapture>>>,
+    pre_roll: Duration,
+    system_audio: Option<mpsc::Receiver<Vec<f32>>>,
+    chunking: Option<ChunkingConfig>,
+    chunk_cb: Option<Arc<dyn Fn(AudioChunk) + Send + Sync + 'static>>,
 }
 
 fn run_consumer(
     in_sample_rate: u32,
-    vad: Option<Arc<Mutex<Box<dyn vad::VoiceActivityDetector>>>>,
+    setup: ConsumerSetup,
     sample_rx: mpsc::Receiver<Vec<f32>>,
     cmd_rx: mpsc::Receiver<Cmd>,
-    level_cb: Option<Arc<dyn Fn(Vec<f32>) + Send + Sync + 'static>>,
 ) {
+    let ConsumerSetup {
+        vad,
+        level_cb,
+        hands_free,
+        hands_free_cb,
+        mut wake_word,
+        vad_cb,
+        mut trim,
+        dsp,
+        clipping_cb,
+        error_cb,
+        ab_capture,
+        pre_roll,
+        system_audio,
+        chunking,
+        chunk_cb,
+    } = setup;
+
     let mut frame_resampler = FrameResampler::new(
         in_sample_rate as usize,
         constants::WHISPER_SAMPLE_RATE as usize,
-        Duration::from_millis(30),
+        FRAME_DURATION,
     );
 
     let mut processed_samples = Vec::<f32>::new();
     let mut recording = false;
+    let mut preview = false;
+
+    let mut dsp = DspChain::new(dsp);
+    let mut ab = AbCapture::default();
+    let mut last_clipping_report: Option<Instant> = None;
+    let mut pre_roll = PreRoll::new(pre_roll);
+    let mut chunker = chunking.map(Chunker::new);
+
+    // Listening for speech onset only makes sense with a VAD
+    let mut hands_free = hands_free
+        .filter(|_| vad.is_some())
+        .map(HandsFreeState::new);
+    let emit = |event: HandsFreeEvent| {
+        if let Some(cb) = &hands_free_cb {
+            cb(event);
+        }
+    };
 
     // ---------- spectrum visualisation setup ---------------------------- //
     const BUCKETS: usize = 16;
@@ -266,33 +955,70 @@ fn run_consumer(
         4000.0, // vocal_max_hz
     );
 
+    /// Returns whether the VAD classified the frame as speech.
     fn handle_frame(
         samples: &[f32],
         recording: bool,
+        trim: bool,
         vad: &Option<Arc<Mutex<Box<dyn vad::VoiceActivityDetector>>>>,
         out_buf: &mut Vec<f32>,
-    ) {
+    ) -> bool {
         if !recording {
-            return;
+            return false;
         }
 
         if let Some(vad_arc) = vad {
             let mut det = vad_arc.lock().unwrap();
             match det.push_frame(samples).unwrap_or(VadFrame::Speech(samples)) {
-                VadFrame::Speech(buf) => out_buf.extend_from_slice(buf),
-                VadFrame::Noise => {}
+                VadFrame::Speech(buf) => {
+                    out_buf.extend_from_slice(if trim { buf } else { samples });
+                    true
+                }
+                VadFrame::Noise => {
+                    if !trim {
+                        out_buf.extend_from_slice(samples);
+                    }
+                    false
+                }
             }
         } else {
             out_buf.extend_from_slice(samples);
+            true
         }
     }
 
+    let mut last_samples = Instant::now();
+    let mut stalled = false;
+
+    let mut system_backlog = VecDeque::new();
+    let max_system_backlog =
+        (in_sample_rate as u128 * MAX_SYSTEM_AUDIO_BACKLOG.as_millis() / 1000) as usize;
+
     loop {
-        let raw = match sample_rx.recv() {
-            Ok(s) => s,
-            Err(_) => break, // stream closed
+        let mut raw = match sample_rx.recv_timeout(SAMPLE_TIMEOUT) {
+            Ok(s) => {
+                last_samples = Instant::now();
+                stalled = false;
+                s
+            }
+            // Keep serving commands while the device delivers nothing
+            Err(mpsc::RecvTimeoutError::Timeout) => {
+                if !stalled && last_samples.elapsed() >= STREAM_STALL_TIMEOUT {
+                    stalled = true;
+                    log::warn!("Input stream stalled");
+                    if let Some(cb) = &error_cb {
+                        cb("no audio received from the device".to_string());
+                    }
+                }
+                Vec::new()
+            }
+            Err(mpsc::RecvTimeoutError::Disconnected) => break, // stream closed
         };
 
+        if let Some(system_rx) = &system_audio {
+            mix_system_audio(&mut raw, system_rx, &mut system_backlog, max_system_backlog);
+        }
+
         // ---------- spectrum processing ---------------------------------- //
         if let Some(buckets) = visualizer.feed(&raw) {
             if let Some(cb) = &level_cb {
@@ -300,34 +1026,238 @@ fn run_consumer(
             }
         }
 
+        if recording && dsp.config().clipping_detection && dsp::is_clipping(&raw) {
+            let due = last_clipping_report.is_none_or(|t| t.elapsed() >= CLIPPING_REPORT_INTERVAL);
+            if due {
+                last_clipping_report = Some(Instant::now());
+                if let Some(cb) = &clipping_cb {
+                    cb();
+                }
+            }
+        }
+
         // ---------- existing pipeline ------------------------------------ //
-        frame_resampler.push(&raw, &mut |frame: &[f32]| {
-            handle_frame(frame, recording, &vad, &mut processed_samples)
+        frame_resampler.push(&raw, &mut |raw_frame: &[f32]| {
+            let ab_capture = recording && dsp.config().ab_capture;
+            let frame = dsp.process(raw_frame);
+            if ab_capture {
+                ab.raw.extend_from_slice(raw_frame);
+                ab.processed.extend_from_slice(frame);
+            }
+            if !recording {
+                pre_roll.push(frame);
+            }
+
+            match hands_free.as_mut() {
+                // Chunked recordings run until stopped, whatever the hands-free
+                // state would decide
+                _ if recording && chunker.is_some() => {
+                    let is_speech = handle_frame(frame, true, trim, &vad, &mut processed_samples);
+                    let chunk = chunker
+                        .as_mut()
+                        .and_then(|c| c.track(is_speech, &mut processed_samples));
+                    if let (Some(chunk), Some(cb)) = (chunk, &chunk_cb) {
+                        cb(chunk);
+                    }
+                }
+                _ if preview && !recording => {
+                    let mut discarded = Vec::new();
+                    let is_speech = handle_frame(frame, true, true, &vad, &mut discarded);
+                    if let Some(cb) = &vad_cb {
+                        cb(is_speech);
+                    }
+                }
+                Some(state) if !recording => {
+                    if !state.is_armed() {
+                        return;
+                    }
+                    let onset = match wake_word.as_mut() {
+                        // The wake phrase itself is not part of the dictation
+                        Some(detector) => detector.is_voice(frame).unwrap_or(false),
+                        // The VAD returns the prefill along with the onset frame,
+                        // so the start of the utterance is kept
+                        None => handle_frame(frame, true, true, &vad, &mut processed_samples),
+                    };
+                    if onset {
+                        if wake_word.is_some() {
+                            if let Some(v) = &vad {
+                                v.lock().unwrap().reset();
+                            }
+                        }
+                        // The VAD's prefill already covers the start of speech
+                        pre_roll.clear();
+                        recording = true;
+                        state.begin();
+                        emit(HandsFreeEvent::SpeechStarted);
+                    }
+                }
+                Some(state) => {
+                    let is_speech = handle_frame(frame, true, trim, &vad, &mut processed_samples);
+                    if let Some(event) = state.track(is_speech) {
+                        emit(event);
+                    }
+                }
+                None => {
+                    handle_frame(frame, recording, trim, &vad, &mut processed_samples);
+                }
+            }
         });
 
         // non-blocking check for a command
         while let Ok(cmd) = cmd_rx.try_recv() {
             match cmd {
                 Cmd::Start => {
+                    // A hands-free onset has already started capturing
+                    if recording {
+                        continue;
+                    }
                     processed_samples.clear();
+                    ab = AbCapture::default();
                     recording = true;
+                    if let Some(chunker) = chunker.as_mut() {
+                        chunker.reset();
+                    }
                     visualizer.reset(); // Reset visualization buffer
                     if let Some(v) = &vad {
                         v.lock().unwrap().reset();
                     }
+
+                    // Audio from just before the request goes through the VAD
+                    // like the rest, so the first syllable is not lost
+                    for frame in pre_roll.frames.drain(..) {
+                        handle_frame(&frame, true, trim, &vad, &mut processed_samples);
+                    }
                 }
                 Cmd::Stop(reply_tx) => {
                     recording = false;
+                    if let Some(state) = hands_free.as_mut() {
+                        state.finish();
+                    }
 
                     frame_resampler.finish(&mut |frame: &[f32]| {
                         // we still want to process the last few frames
-                        handle_frame(frame, true, &vad, &mut processed_samples)
+                        handle_frame(dsp.process(frame), true, trim, &vad, &mut processed_samples)
                     });
 
+                    // Stored before replying, so it is available once `stop` returns
+                    let capture = std::mem::take(&mut ab);
+                    *ab_capture.lock().unwrap() =
+                        (dsp.config().ab_capture && !capture.raw.is_empty()).then_some(capture);
+
+                    // A chunked recording hands over what is left as its last chunk
+                    let last_chunk = chunker
+                        .as_mut()
+                        .and_then(|c| c.take(&mut processed_samples));
+                    if let (Some(chunk), Some(cb)) = (last_chunk, &chunk_cb) {
+                        cb(chunk);
+                    }
+
                     let _ = reply_tx.send(std::mem::take(&mut processed_samples));
+
+                    // Start listening for the next onset from a clean state
+                    if let Some(v) = &vad {
+                        v.lock().unwrap().reset();
+                    }
+                    if let Some(detector) = wake_word.as_mut() {
+                        detector.reset();
+                    }
+                }
+                Cmd::HandsFree(config) => {
+                    match (hands_free.as_mut(), config.filter(|_| vad.is_some())) {
+                        (Some(state), Some(config)) => state.config = config,
+                        (Some(state), None) => {
+                            // Let a running hands-free dictation finish normally
+                            if state.active && !state.ended {
+                                emit(HandsFreeEvent::SpeechEnded);
+                            }
+                            hands_free = None;
+                        }
+                        (None, config) => hands_free = config.map(HandsFreeState::new),
+                    }
+                }
+                Cmd::WakeWord(detector) => wake_word = detector,
+                Cmd::Trim(enabled) => trim = enabled,
+                Cmd::Dsp(config) => dsp.set_config(config),
+                Cmd::PreRoll(duration) => pre_roll.set_duration(duration),
+                Cmd::Chunking(config) => chunker = config.map(Chunker::new),
+                Cmd::Preview(enabled) => {
+                    preview = enabled;
+                    if let Some(v) = &vad {
+                        v.lock().unwrap().reset();
+                    }
                 }
                 Cmd::Shutdown => return,
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn drained(pre_roll: &mut PreRoll) -> Vec<f32> {
+        pre_roll.frames.drain(..).map(|frame| frame[0]).collect()
+    }
+
+    #[test]
+    fn pre_roll_keeps_the_newest_frames_in_order() {
+        // 90 ms holds three 30 ms frames
+        let mut pre_roll = PreRoll::new(Duration::from_millis(90));
+        for i in 0..5 {
+            pre_roll.push(&[i as f32; 4]);
+        }
+        assert_eq!(drained(&mut pre_roll), [2.0, 3.0, 4.0]);
+
+        // Draining empties it, and it fills up again from scratch
+        pre_roll.push(&[7.0; 4]);
+        assert_eq!(drained(&mut pre_roll), [7.0]);
+    }
+
+    #[test]
+    fn pre_roll_shrinks_to_a_shorter_duration() {
+        let mut pre_roll = PreRoll::new(Duration::from_millis(120));
+        for i in 0..4 {
+            pre_roll.push(&[i as f32]);
+        }
+        pre_roll.set_duration(Duration::from_millis(60));
+        assert_eq!(drained(&mut pre_roll), [2.0, 3.0]);
+    }
+
+    #[test]
+    fn system_audio_is_added_and_clamped() {
+        let (system_tx, system_rx) = mpsc::channel();
+        let mut backlog = VecDeque::new();
+        system_tx.send(vec![0.25, 0.75, -0.75, 0.5, 0.1]).unwrap();
+
+        let mut samples = [0.5, 0.5, -0.5, 0.0];
+        mix_system_audio(&mut samples, &system_rx, &mut backlog, 16);
+        assert_eq!(samples, [0.75, 1.0, -1.0, 0.5]);
+        // What the microphone didn't use yet waits for the next chunk
+        assert_eq!(backlog, [0.1]);
+
+        let mut samples = [0.2, 0.2];
+        mix_system_audio(&mut samples, &system_rx, &mut backlog, 16);
+        assert_eq!(samples, [0.3, 0.2]);
+        assert!(backlog.is_empty());
+    }
+
+    #[test]
+    fn system_audio_backlog_drops_the_oldest_samples() {
+        let (system_tx, system_rx) = mpsc::channel();
+        let mut backlog = VecDeque::new();
+        system_tx.send(vec![0.1, 0.2, 0.3, 0.4]).unwrap();
+
+        let mut samples = [0.0];
+        mix_system_audio(&mut samples, &system_rx, &mut backlog, 2);
+        assert_eq!(samples, [0.3]);
+        assert_eq!(backlog, [0.4]);
+    }
+
+    #[test]
+    fn zero_pre_roll_keeps_nothing() {
+        let mut pre_roll = PreRoll::new(Duration::ZERO);
+        pre_roll.push(&[1.0]);
+        assert!(pre_roll.frames.is_empty());
+    }
+}
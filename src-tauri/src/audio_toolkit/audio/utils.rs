@@ -1,18 +1,29 @@
 use anyhow::Result;
-use hound::{WavSpec, WavWriter};
+use hound::{SampleFormat, WavReader, WavSpec, WavWriter};
 use log::debug;
 use std::path::Path;
+use std::time::Duration;
 
-/// Save audio samples as a WAV file
-pub async fn save_wav_file<P: AsRef<Path>>(file_path: P, samples: &[f32]) -> Result<()> {
-    let spec = WavSpec {
+use super::FrameResampler;
+use crate::audio_toolkit::constants::WHISPER_SAMPLE_RATE;
+
+fn wav_spec() -> WavSpec {
+    WavSpec {
         channels: 1,
-        sample_rate: 16000,
+        sample_rate: WHISPER_SAMPLE_RATE,
         bits_per_sample: 16,
-        sample_format: hound::SampleFormat::Int,
-    };
+        sample_format: SampleFormat::Int,
+    }
+}
 
-    let mut writer = WavWriter::create(file_path.as_ref(), spec)?;
+/// Save audio samples as a WAV file
+pub async fn save_wav_file<P: AsRef<Path>>(file_path: P, samples: &[f32]) -> Result<()> {
+    write_wav_file(file_path, samples)
+}
+
+/// Blocking version of [`save_wav_file`] for use outside of async code
+pub fn write_wav_file<P: AsRef<Path>>(file_path: P, samples: &[f32]) -> Result<()> {
+    let mut writer = WavWriter::create(file_path.as_ref(), wav_spec())?;
 
     // Convert f32 samples to i16 for WAV
     for sample in samples {
@@ -24,3 +35,77 @@ pub async fn save_wav_file<P: AsRef<Path>>(file_path: P, samples: &[f32]) -> Res
     debug!("Saved WAV file: {:?}", file_path.as_ref());
     Ok(())
 }
+
+/// Join WAV files into one, each starting at its offset from the beginning of
+/// the output. Gaps are filled with silence, and a part that would overlap
+/// the previous one follows it directly instead. Parts are read one at a
+/// time, so long recordings are never held in memory.
+pub fn concat_wav_files<P: AsRef<Path>, Q: AsRef<Path>>(
+    output_path: Q,
+    parts: &[(Duration, P)],
+) -> Result<()> {
+    let mut writer = WavWriter::create(output_path.as_ref(), wav_spec())?;
+    let mut written: u64 = 0;
+
+    for (offset, path) in parts {
+        let samples = read_wav_file(path)?;
+        let start = (offset.as_secs_f64() * WHISPER_SAMPLE_RATE as f64) as u64;
+        while written < start {
+            writer.write_sample(0i16)?;
+            written += 1;
+        }
+        for sample in &samples {
+            writer.write_sample((sample * i16::MAX as f32) as i16)?;
+        }
+        written += samples.len() as u64;
+    }
+
+    writer.finalize()?;
+    debug!(
+        "Joined {} WAV files into {:?}",
+        parts.len(),
+        output_path.as_ref()
+    );
+    Ok(())
+}
+
+/// Read a WAV file as mono samples at the Whisper sample rate, downmixing and
+/// resampling as needed
+pub fn read_wav_file<P: AsRef<Path>>(file_path: P) -> Result<Vec<f32>> {
+    let mut reader = WavReader::open(file_path.as_ref())?;
+    let spec = reader.spec();
+
+    let interleaved: Vec<f32> = match spec.sample_format {
+        SampleFormat::Float => reader.samples::<f32>().collect::<Result<_, _>>()?,
+        SampleFormat::Int => {
+            let scale = (1i64 << (spec.bits_per_sample - 1)) as f32;
+            reader
+                .samples::<i32>()
+                .map(|s| s.map(|s| s as f32 / scale))
+                .collect::<Result<_, _>>()?
+        }
+    };
+
+    let channels = spec.channels.max(1) as usize;
+    let mono: Vec<f32> = interleaved
+        .chunks(channels)
+        .map(|frame| frame.iter().sum::<f32>() / channels as f32)
+        .collect();
+
+    let mut resampler = FrameResampler::new(
+        spec.sample_rate as usize,
+        WHISPER_SAMPLE_RATE as usize,
+        Duration::from_millis(30),
+    );
+    let mut samples = Vec::with_capacity(mono.len());
+    resampler.push(&mono, |frame| samples.extend_from_slice(frame));
+    resampler.finish(|frame| samples.extend_from_slice(frame));
+
+    debug!(
+        "Read WAV file {:?}: {} Hz, {} channel(s)",
+        file_path.as_ref(),
+        spec.sample_rate,
+        spec.channels
+    );
+    Ok(samples)
+}
@@ -0,0 +1,248 @@
+use crate::audio_toolkit::audio::{MfccExtractor, CEPSTRAL_COEFFS};
+use crate::audio_toolkit::constants;
+
+/// Segments shorter than this don't carry enough speech for a voice print.
+const MIN_EMBEDDING_SAMPLES: usize = constants::WHISPER_SAMPLE_RATE as usize / 2;
+
+/// Root-mean-square difference per dimension above which two voice prints
+/// are taken to belong to different speakers.
+const SPEAKER_DISTANCE: f32 = 1.2;
+
+const REFINEMENT_PASSES: usize = 2;
+
+/// Summarises how a stretch of speech sounds, independently of what is said:
+/// the mean and spread of each cepstral coefficient over its louder frames.
+/// The energy coefficient is left out so loudness doesn't split a speaker.
+pub struct SpeakerEmbedder {
+    extractor: MfccExtractor,
+}
+
+impl SpeakerEmbedder {
+    pub fn new() -> Self {
+        Self {
+            extractor: MfccExtractor::new(),
+        }
+    }
+
+    /// `samples` are at the Whisper sample rate. Returns `None` for segments
+    /// too short to tell speakers apart.
+    pub fn embed(&self, samples: &[f32]) -> Option<Vec<f32>> {
+        if samples.len() < MIN_EMBEDDING_SAMPLES {
+            return None;
+        }
+        let cepstra = self.extractor.cepstra(samples);
+
+        // Quieter frames are mostly pauses and breath, which all sound alike
+        let mut energies: Vec<f32> = cepstra.iter().map(|frame| frame[0]).collect();
+        energies.sort_by(f32::total_cmp);
+        let median = *energies.get(energies.len() / 2)?;
+        let voiced: Vec<_> = cepstra.iter().filter(|frame| frame[0] >= median).collect();
+
+        let count = voiced.len() as f32;
+        let mut mean = [0.0; CEPSTRAL_COEFFS];
+        for frame in &voiced {
+            for (m, c) in mean.iter_mut().zip(frame.iter()) {
+                *m += c / count;
+            }
+        }
+        let mut spread = [0.0; CEPSTRAL_COEFFS];
+        for frame in &voiced {
+            for ((s, c), m) in spread.iter_mut().zip(frame.iter()).zip(&mean) {
+                *s += (c - m) * (c - m) / count;
+            }
+        }
+
+        Some(
+            mean[1..]
+                .iter()
+                .copied()
+                .chain(spread[1..].iter().map(|s| s.sqrt()))
+                .collect(),
+        )
+    }
+}
+
+impl Default for SpeakerEmbedder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn distance(a: &[f32], b: &[f32]) -> f32 {
+    let sum: f32 = a.iter().zip(b).map(|(x, y)| (x - y) * (x - y)).sum();
+    (sum / a.len().max(1) as f32).sqrt()
+}
+
+fn centroids(embeddings: &[&Vec<f32>], labels: &[usize], count: usize) -> Vec<Vec<f32>> {
+    let dims = embeddings.first().map_or(0, |e| e.len());
+    let mut sums = vec![vec![0.0; dims]; count];
+    let mut sizes = vec![0usize; count];
+    for (embedding, &label) in embeddings.iter().zip(labels) {
+        for (s, v) in sums[label].iter_mut().zip(embedding.iter()) {
+            *s += v;
+        }
+        sizes[label] += 1;
+    }
+    for (sum, size) in sums.iter_mut().zip(sizes) {
+        for s in sum.iter_mut() {
+            *s /= size.max(1) as f32;
+        }
+    }
+    sums
+}
+
+/// Groups voice prints by speaker. Speakers are numbered from 0 in order of
+/// first appearance; segments without a voice print are attributed to
+/// whoever spoke before them.
+pub fn cluster_speakers(embeddings: &[Option<Vec<f32>>]) -> Vec<Option<u32>> {
+    let present: Vec<&Vec<f32>> = embeddings.iter().flatten().collect();
+
+    // Online pass: join the closest speaker so far or start a new one
+    let mut labels = Vec::with_capacity(present.len());
+    let mut means: Vec<Vec<f32>> = Vec::new();
+    let mut sizes: Vec<usize> = Vec::new();
+    for embedding in &present {
+        let closest = means
+            .iter()
+            .enumerate()
+            .map(|(i, mean)| (i, distance(embedding, mean)))
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .filter(|&(_, d)| d <= SPEAKER_DISTANCE);
+        match closest {
+            Some((i, _)) => {
+                sizes[i] += 1;
+                let weight = 1.0 / sizes[i] as f32;
+                for (m, v) in means[i].iter_mut().zip(embedding.iter()) {
+                    *m += (v - *m) * weight;
+                }
+                labels.push(i);
+            }
+            None => {
+                means.push((*embedding).clone());
+                sizes.push(1);
+                labels.push(means.len() - 1);
+            }
+        }
+    }
+
+    // Early segments were assigned against rough centroids; settle them
+    for _ in 0..REFINEMENT_PASSES {
+        for (label, embedding) in labels.iter_mut().zip(&present) {
+            *label = means
+                .iter()
+                .enumerate()
+                .map(|(i, mean)| (i, distance(embedding, mean)))
+                .min_by(|a, b| a.1.total_cmp(&b.1))
+                .map_or(0, |(i, _)| i);
+        }
+        means = centroids(&present, &labels, means.len());
+    }
+
+    let mut order: Vec<usize> = Vec::new();
+    let mut present_labels = labels.into_iter();
+    let mut previous = None;
+    embeddings
+        .iter()
+        .map(|embedding| {
+            if embedding.is_some() {
+                let label = present_labels.next()?;
+                let speaker = match order.iter().position(|&l| l == label) {
+                    Some(speaker) => speaker,
+                    None => {
+                        order.push(label);
+                        order.len() - 1
+                    }
+                };
+                previous = Some(speaker as u32);
+            }
+            previous
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RATE: f32 = constants::WHISPER_SAMPLE_RATE as f32;
+
+    /// A crude voice: harmonics of `pitch` shaped by two formants, with a
+    /// little pitch wobble so no two utterances are identical.
+    fn voice(pitch: f32, formants: [f32; 2], seed: u32, seconds: f32) -> Vec<f32> {
+        let mut state = seed.wrapping_mul(2654435761).max(1);
+        let mut noise = move || {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            state as f32 / u32::MAX as f32 - 0.5
+        };
+        let drift = 1.0 + noise() * 0.06;
+        let gain = |freq: f32| {
+            formants
+                .iter()
+                .map(|f| 1.0 / (1.0 + ((freq - f) / 150.0).powi(2)))
+                .sum::<f32>()
+        };
+
+        let mut phases = [0.0f32; 30];
+        (0..(seconds * RATE) as usize)
+            .map(|i| {
+                let t = i as f32 / RATE;
+                let f0 = pitch * drift * (1.0 + 0.03 * (t * 5.0).sin());
+                let mut sample = 0.0;
+                for (h, phase) in phases.iter_mut().enumerate() {
+                    let freq = f0 * (h + 1) as f32;
+                    if freq >= RATE / 2.0 {
+                        break;
+                    }
+                    *phase += 2.0 * std::f32::consts::PI * freq / RATE;
+                    sample += gain(freq) * phase.sin();
+                }
+                0.1 * sample + 0.001 * noise()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn alternating_speakers_get_alternating_labels() {
+        let embedder = SpeakerEmbedder::new();
+        let embeddings: Vec<_> = (0..4)
+            .map(|i| {
+                let samples = if i % 2 == 0 {
+                    voice(110.0, [500.0, 1500.0], i, 1.5)
+                } else {
+                    voice(220.0, [800.0, 2300.0], i, 1.5)
+                };
+                embedder.embed(&samples)
+            })
+            .collect();
+
+        assert_eq!(
+            cluster_speakers(&embeddings),
+            vec![Some(0), Some(1), Some(0), Some(1)]
+        );
+    }
+
+    #[test]
+    fn one_speaker_stays_one_speaker() {
+        let embedder = SpeakerEmbedder::new();
+        let embeddings: Vec<_> = (0..5)
+            .map(|i| embedder.embed(&voice(140.0, [600.0, 1800.0], i, 1.0 + i as f32 * 0.3)))
+            .collect();
+
+        assert!(cluster_speakers(&embeddings).iter().all(|s| *s == Some(0)));
+    }
+
+    #[test]
+    fn short_segments_inherit_the_previous_speaker() {
+        let embedder = SpeakerEmbedder::new();
+        let short = voice(220.0, [800.0, 2300.0], 9, 0.2);
+        assert!(embedder.embed(&short).is_none());
+
+        let embeddings = vec![None, Some(vec![0.0; 4]), None, Some(vec![5.0; 4]), None];
+        assert_eq!(
+            cluster_speakers(&embeddings),
+            vec![None, Some(0), Some(0), Some(1), Some(1)]
+        );
+    }
+}
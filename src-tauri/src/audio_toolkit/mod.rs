@@ -1,12 +1,17 @@
 pub mod audio;
 pub mod constants;
+pub mod diarization;
 pub mod text;
 pub mod utils;
 pub mod vad;
 
 pub use audio::{
-    list_input_devices, list_output_devices, save_wav_file, AudioRecorder, CpalDeviceInfo,
+    concat_wav_files, input_capabilities, list_input_devices, list_output_devices, read_wav_file,
+    save_wav_file, write_wav_file, AbCapture, AudioChunk, AudioRecorder, CaptureConfig,
+    CaptureSource, ChunkingConfig, CpalDeviceInfo, DspConfig, HandsFreeConfig, HandsFreeEvent,
+    InputCapabilities, LoopbackCapture,
 };
-pub use text::apply_custom_words;
+pub use diarization::{cluster_speakers, SpeakerEmbedder};
+pub use text::{apply_custom_words, word_errors};
 pub use utils::get_cpal_host;
 pub use vad::{SileroVad, VoiceActivityDetector};
@@ -132,6 +132,44 @@ fn extract_punctuation(word: &str) -> (&str, &str) {
     (prefix, suffix)
 }
 
+/// Counts the word-level edits (substitutions, insertions and deletions) needed
+/// to turn `hypothesis` into `reference`, ignoring case and punctuation.
+///
+/// # Returns
+/// `(edits, reference_words)`. Summing both over a set of clips and dividing
+/// gives the word error rate for the whole set.
+pub fn word_errors(reference: &str, hypothesis: &str) -> (usize, usize) {
+    let reference = normalize_words(reference);
+    let hypothesis = normalize_words(hypothesis);
+
+    // Classic single-row Levenshtein over words
+    let mut row: Vec<usize> = (0..=hypothesis.len()).collect();
+    for (i, ref_word) in reference.iter().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, hyp_word) in hypothesis.iter().enumerate() {
+            let substitution = diagonal + usize::from(ref_word != hyp_word);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+
+    (row[hypothesis.len()], reference.len())
+}
+
+/// Lowercases and splits text into words, dropping punctuation
+fn normalize_words(text: &str) -> Vec<String> {
+    text.split_whitespace()
+        .map(|word| {
+            word.chars()
+                .filter(|c| c.is_alphanumeric() || *c == '\'')
+                .flat_map(char::to_lowercase)
+                .collect::<String>()
+        })
+        .filter(|word| !word.is_empty())
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -173,4 +211,17 @@ mod tests {
         let result = apply_custom_words(text, &custom_words, 0.5);
         assert_eq!(result, "hello world");
     }
+
+    #[test]
+    fn test_word_errors() {
+        assert_eq!(word_errors("Hello, world!", "hello world"), (0, 2));
+        // One substitution and one deletion
+        assert_eq!(
+            word_errors("the quick brown fox", "the quack brown"),
+            (2, 4)
+        );
+        // Insertions count against the reference length
+        assert_eq!(word_errors("hi", "hi there you"), (2, 1));
+        assert_eq!(word_errors("", "anything"), (1, 0));
+    }
 }
@@ -0,0 +1,92 @@
+use anyhow::Result;
+
+use super::{VadFrame, VoiceActivityDetector};
+
+const INITIAL_NOISE_FLOOR_DB: f32 = -60.0;
+
+/// Frames quieter than this are never speech, however quiet the room is.
+const MIN_SPEECH_DB: f32 = -50.0;
+
+/// How quickly the noise floor follows quieter and louder surroundings. It
+/// creeps up very slowly during speech so a constant hum is eventually learned.
+const FLOOR_FALL_RATE: f32 = 0.2;
+const FLOOR_RISE_RATE: f32 = 0.02;
+const FLOOR_RISE_RATE_IN_SPEECH: f32 = 0.002;
+
+/// Classifies frames by loudness relative to a tracked noise floor. Far cheaper
+/// than Silero, at the cost of mistaking loud non-speech sounds for speech.
+pub struct EnergyVad {
+    margin_db: f32,
+    noise_floor_db: f32,
+}
+
+impl EnergyVad {
+    /// `threshold` uses the same 0–1 range as the Silero VAD; higher values
+    /// require speech to stand further above the background noise.
+    pub fn new(threshold: f32) -> Result<Self> {
+        if !(0.0..=1.0).contains(&threshold) {
+            anyhow::bail!("threshold must be between 0.0 and 1.0");
+        }
+
+        Ok(Self {
+            margin_db: 6.0 + 24.0 * threshold,
+            noise_floor_db: INITIAL_NOISE_FLOOR_DB,
+        })
+    }
+}
+
+impl VoiceActivityDetector for EnergyVad {
+    fn push_frame<'a>(&'a mut self, frame: &'a [f32]) -> Result<VadFrame<'a>> {
+        if frame.is_empty() {
+            return Ok(VadFrame::Noise);
+        }
+
+        let rms = (frame.iter().map(|s| s * s).sum::<f32>() / frame.len() as f32).sqrt();
+        let level_db = 20.0 * rms.max(1e-6).log10();
+
+        let is_speech = level_db > MIN_SPEECH_DB && level_db > self.noise_floor_db + self.margin_db;
+
+        let rate = if level_db < self.noise_floor_db {
+            FLOOR_FALL_RATE
+        } else if is_speech {
+            FLOOR_RISE_RATE_IN_SPEECH
+        } else {
+            FLOOR_RISE_RATE
+        };
+        self.noise_floor_db += (level_db - self.noise_floor_db) * rate;
+
+        if is_speech {
+            Ok(VadFrame::Speech(frame))
+        } else {
+            Ok(VadFrame::Noise)
+        }
+    }
+
+    // The noise floor describes the room rather than a recording, so it is
+    // deliberately kept across resets.
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tone(amplitude: f32) -> Vec<f32> {
+        (0..480)
+            .map(|i| amplitude * (i as f32 * 0.2).sin())
+            .collect()
+    }
+
+    #[test]
+    fn test_speech_stands_out_from_adapted_noise_floor() {
+        let mut vad = EnergyVad::new(0.3).unwrap();
+
+        // A steady hum is learned as background
+        let hum = tone(0.01);
+        let hum_results: Vec<bool> = (0..200).map(|_| vad.is_voice(&hum).unwrap()).collect();
+        assert!(!hum_results[150..].iter().any(|&speech| speech));
+
+        assert!(vad.is_voice(&tone(0.3)).unwrap());
+        assert!(!vad.is_voice(&vec![0.0; 480]).unwrap());
+        assert!(EnergyVad::new(1.5).is_err());
+    }
+}
@@ -25,8 +25,12 @@ pub trait VoiceActivityDetector: Send + Sync {
     fn reset(&mut self) {}
 }
 
+mod energy;
 mod silero;
 mod smoothed;
+mod wake_word;
 
+pub use energy::EnergyVad;
 pub use silero::SileroVad;
 pub use smoothed::SmoothedVad;
+pub use wake_word::{WakeWordDetector, MIN_WAKE_WORD_SAMPLES};
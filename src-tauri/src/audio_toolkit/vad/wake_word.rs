@@ -0,0 +1,198 @@
+use anyhow::Result;
+
+use super::{VadFrame, VoiceActivityDetector};
+use crate::audio_toolkit::audio::{Features, MfccExtractor, CEPSTRAL_COEFFS, WINDOW_SAMPLES};
+use crate::audio_toolkit::constants;
+
+/// Utterances longer than this are ordinary speech, not the wake phrase.
+const MAX_PHRASE_SAMPLES: usize = constants::WHISPER_SAMPLE_RATE as usize * 3;
+
+/// How much further than the user's samples are from each other a phrase may
+/// be and still count as a match.
+const THRESHOLD_MARGIN: f32 = 1.3;
+
+pub const MIN_WAKE_WORD_SAMPLES: usize = 2;
+
+/// Dynamic time warping distance, normalised by path length so phrases of
+/// different lengths are comparable.
+fn dtw_distance(a: &Features, b: &Features) -> f32 {
+    if a.is_empty() || b.is_empty() {
+        return f32::INFINITY;
+    }
+
+    let distance = |x: &[f32; CEPSTRAL_COEFFS], y: &[f32; CEPSTRAL_COEFFS]| {
+        x.iter()
+            .zip(y)
+            .map(|(p, q)| (p - q) * (p - q))
+            .sum::<f32>()
+            .sqrt()
+    };
+
+    let mut previous = vec![f32::INFINITY; b.len() + 1];
+    let mut current = vec![f32::INFINITY; b.len() + 1];
+    previous[0] = 0.0;
+    for x in a {
+        current[0] = f32::INFINITY;
+        for (j, y) in b.iter().enumerate() {
+            let best = previous[j].min(previous[j + 1]).min(current[j]);
+            current[j + 1] = distance(x, y) + best;
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()] / (a.len() + b.len()) as f32
+}
+
+/// Reports speech only once the user has said their wake phrase, by comparing
+/// each utterance the inner VAD detects against recorded samples of it. The
+/// phrase itself is swallowed; the trigger is an empty speech frame.
+pub struct WakeWordDetector {
+    inner_vad: Box<dyn VoiceActivityDetector>,
+    extractor: MfccExtractor,
+    templates: Vec<Features>,
+    threshold: f32,
+    phrase: Vec<f32>,
+    skip_utterance: bool,
+}
+
+impl WakeWordDetector {
+    /// `samples` are recordings of the wake phrase at the Whisper sample rate.
+    pub fn new(inner_vad: Box<dyn VoiceActivityDetector>, samples: &[Vec<f32>]) -> Result<Self> {
+        if samples.len() < MIN_WAKE_WORD_SAMPLES {
+            anyhow::bail!(
+                "at least {MIN_WAKE_WORD_SAMPLES} wake word samples are needed, got {}",
+                samples.len()
+            );
+        }
+
+        let extractor = MfccExtractor::new();
+        let templates: Vec<Features> = samples.iter().map(|s| extractor.features(s)).collect();
+        if templates.iter().any(|t| t.is_empty()) {
+            anyhow::bail!("wake word samples must be at least {WINDOW_SAMPLES} samples long");
+        }
+
+        // Calibrate against how much the user's own samples differ
+        let mut total = 0.0;
+        let mut pairs = 0;
+        for (i, a) in templates.iter().enumerate() {
+            for b in &templates[i + 1..] {
+                total += dtw_distance(a, b);
+                pairs += 1;
+            }
+        }
+        let threshold = total / pairs as f32 * THRESHOLD_MARGIN;
+
+        Ok(Self {
+            inner_vad,
+            extractor,
+            templates,
+            threshold,
+            phrase: Vec::new(),
+            skip_utterance: false,
+        })
+    }
+
+    fn is_wake_phrase(&self, samples: &[f32]) -> bool {
+        let features = self.extractor.features(samples);
+        let best = self
+            .templates
+            .iter()
+            .map(|template| dtw_distance(&features, template))
+            .fold(f32::INFINITY, f32::min);
+        log::debug!(
+            "Wake word distance {:.2} (threshold {:.2})",
+            best,
+            self.threshold
+        );
+        best <= self.threshold
+    }
+}
+
+impl VoiceActivityDetector for WakeWordDetector {
+    fn push_frame<'a>(&'a mut self, frame: &'a [f32]) -> Result<VadFrame<'a>> {
+        match self.inner_vad.push_frame(frame)? {
+            VadFrame::Speech(buf) => {
+                if !self.skip_utterance {
+                    self.phrase.extend_from_slice(buf);
+                    if self.phrase.len() > MAX_PHRASE_SAMPLES {
+                        self.phrase.clear();
+                        self.skip_utterance = true;
+                    }
+                }
+                Ok(VadFrame::Noise)
+            }
+            VadFrame::Noise => {
+                self.skip_utterance = false;
+                if self.phrase.is_empty() {
+                    return Ok(VadFrame::Noise);
+                }
+
+                let phrase = std::mem::take(&mut self.phrase);
+                if self.is_wake_phrase(&phrase) {
+                    Ok(VadFrame::Speech(&[]))
+                } else {
+                    Ok(VadFrame::Noise)
+                }
+            }
+        }
+    }
+
+    fn reset(&mut self) {
+        self.inner_vad.reset();
+        self.phrase.clear();
+        self.skip_utterance = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RATE: f32 = constants::WHISPER_SAMPLE_RATE as f32;
+
+    /// Treats every non-silent frame as speech.
+    struct EnergyVad;
+
+    impl VoiceActivityDetector for EnergyVad {
+        fn push_frame<'a>(&'a mut self, frame: &'a [f32]) -> Result<VadFrame<'a>> {
+            if frame.iter().any(|s| s.abs() > 0.01) {
+                Ok(VadFrame::Speech(frame))
+            } else {
+                Ok(VadFrame::Noise)
+            }
+        }
+    }
+
+    fn sweep(from_hz: f32, to_hz: f32, secs: f32) -> Vec<f32> {
+        let len = (RATE * secs) as usize;
+        let mut phase = 0.0f32;
+        (0..len)
+            .map(|i| {
+                let hz = from_hz + (to_hz - from_hz) * i as f32 / len as f32;
+                phase += 2.0 * std::f32::consts::PI * hz / RATE;
+                0.5 * phase.sin()
+            })
+            .collect()
+    }
+
+    fn detect(detector: &mut WakeWordDetector, audio: &[f32]) -> bool {
+        let silence = vec![0.0; audio.len()];
+        audio
+            .chunks(480)
+            .chain(silence.chunks(480))
+            .any(|frame| detector.push_frame(frame).unwrap().is_speech())
+    }
+
+    #[test]
+    fn test_matches_recorded_phrase_only() {
+        let samples = vec![
+            sweep(300.0, 2000.0, 0.6),
+            sweep(320.0, 2100.0, 0.65),
+            sweep(280.0, 1900.0, 0.55),
+        ];
+        let mut detector = WakeWordDetector::new(Box::new(EnergyVad), &samples).unwrap();
+
+        assert!(detect(&mut detector, &sweep(310.0, 2050.0, 0.7)));
+        detector.reset();
+        assert!(!detect(&mut detector, &sweep(3000.0, 400.0, 0.6)));
+    }
+}
@@ -0,0 +1,151 @@
+//! Finds the text caret of the focused application through the platform's
+//! accessibility APIs, so the overlay can sit next to where text will land.
+//! Windows reports the caret of classic Win32 controls and macOS of any app
+//! that supports accessibility. Linux isn't supported yet, and many browsers
+//! and Electron apps don't expose a caret either; callers fall back to a fixed
+//! position then.
+
+use tauri::Position;
+
+/// The bottom-left corner of the caret: in physical pixels on Windows, and in
+/// points (Tauri's logical coordinates) on macOS.
+pub fn caret_position() -> Option<Position> {
+    platform::caret_position()
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use tauri::{PhysicalPosition, Position};
+    use windows::Win32::Foundation::POINT;
+    use windows::Win32::Graphics::Gdi::ClientToScreen;
+    use windows::Win32::UI::WindowsAndMessaging::{GetGUIThreadInfo, GUITHREADINFO};
+
+    pub fn caret_position() -> Option<Position> {
+        let mut info = GUITHREADINFO {
+            cbSize: std::mem::size_of::<GUITHREADINFO>() as u32,
+            ..Default::default()
+        };
+        // Thread 0 is the thread of the foreground window
+        unsafe { GetGUIThreadInfo(0, &mut info) }.ok()?;
+        if info.hwndCaret.is_invalid() {
+            return None;
+        }
+
+        let mut point = POINT {
+            x: info.rcCaret.left,
+            y: info.rcCaret.bottom,
+        };
+        if !unsafe { ClientToScreen(info.hwndCaret, &mut point) }.as_bool() {
+            return None;
+        }
+        Some(Position::Physical(PhysicalPosition {
+            x: point.x,
+            y: point.y,
+        }))
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use core_foundation::base::{CFRelease, CFTypeRef, TCFType};
+    use core_foundation::string::{CFString, CFStringRef};
+    use std::ffi::c_void;
+    use std::ptr;
+    use tauri::{LogicalPosition, Position};
+
+    type AXError = i32;
+    const AX_ERROR_SUCCESS: AXError = 0;
+    const AX_VALUE_CG_RECT_TYPE: u32 = 3;
+
+    #[repr(C)]
+    #[derive(Default)]
+    struct CGRect {
+        x: f64,
+        y: f64,
+        width: f64,
+        height: f64,
+    }
+
+    #[link(name = "ApplicationServices", kind = "framework")]
+    extern "C" {
+        fn AXUIElementCreateSystemWide() -> CFTypeRef;
+        fn AXUIElementCopyAttributeValue(
+            element: CFTypeRef,
+            attribute: CFStringRef,
+            value: *mut CFTypeRef,
+        ) -> AXError;
+        fn AXUIElementCopyParameterizedAttributeValue(
+            element: CFTypeRef,
+            attribute: CFStringRef,
+            parameter: CFTypeRef,
+            value: *mut CFTypeRef,
+        ) -> AXError;
+        fn AXValueGetValue(value: CFTypeRef, value_type: u32, value_ptr: *mut c_void) -> u8;
+    }
+
+    /// A Core Foundation object we own and release when dropped.
+    struct Owned(CFTypeRef);
+
+    impl Drop for Owned {
+        fn drop(&mut self) {
+            unsafe { CFRelease(self.0) }
+        }
+    }
+
+    fn owned(err: AXError, value: CFTypeRef) -> Option<Owned> {
+        (err == AX_ERROR_SUCCESS && !value.is_null()).then_some(Owned(value))
+    }
+
+    fn copy_attribute(element: &Owned, name: &'static str) -> Option<Owned> {
+        let attribute = CFString::from_static_string(name);
+        let mut value: CFTypeRef = ptr::null();
+        let err = unsafe {
+            AXUIElementCopyAttributeValue(element.0, attribute.as_concrete_TypeRef(), &mut value)
+        };
+        owned(err, value)
+    }
+
+    pub fn caret_position() -> Option<Position> {
+        let system = owned(AX_ERROR_SUCCESS, unsafe { AXUIElementCreateSystemWide() })?;
+        let focused = copy_attribute(&system, "AXFocusedUIElement")?;
+        let range = copy_attribute(&focused, "AXSelectedTextRange")?;
+
+        let attribute = CFString::from_static_string("AXBoundsForRange");
+        let mut value: CFTypeRef = ptr::null();
+        let err = unsafe {
+            AXUIElementCopyParameterizedAttributeValue(
+                focused.0,
+                attribute.as_concrete_TypeRef(),
+                range.0,
+                &mut value,
+            )
+        };
+        let bounds = owned(err, value)?;
+
+        let mut rect = CGRect::default();
+        let ok = unsafe {
+            AXValueGetValue(
+                bounds.0,
+                AX_VALUE_CG_RECT_TYPE,
+                &mut rect as *mut CGRect as *mut c_void,
+            )
+        };
+        // Apps that don't really support the attribute answer with an empty rect
+        if ok == 0 || (rect.x == 0.0 && rect.y == 0.0 && rect.height == 0.0) {
+            return None;
+        }
+        Some(Position::Logical(LogicalPosition {
+            x: rect.x,
+            y: rect.y + rect.height,
+        }))
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod platform {
+    use tauri::Position;
+
+    pub fn caret_position() -> Option<Position> {
+        None
+    }
+}
@@ -1,33 +1,146 @@
 use crate::input::{self, EnigoState};
-use crate::settings::{get_settings, ClipboardHandling, PasteMethod};
+use crate::managers::history::EntryLink;
+use crate::settings::{get_settings, AppSettings, ClipboardHandling, PasteMethod};
+use arboard::{Clipboard, ImageData};
 use enigo::Enigo;
-use log::info;
+use log::{debug, info, warn};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use tauri::{AppHandle, Manager};
 use tauri_plugin_clipboard_manager::ClipboardExt;
 
+use crate::selection::TemporarySelection;
 #[cfg(target_os = "linux")]
 use crate::utils::is_wayland;
 #[cfg(target_os = "linux")]
+use crate::wayland_input;
+#[cfg(target_os = "linux")]
+use crate::x11_input;
+#[cfg(target_os = "linux")]
+use once_cell::sync::Lazy;
+#[cfg(target_os = "linux")]
 use std::process::Command;
 
-/// Pastes text using the clipboard: saves current content, writes text, sends paste keystroke, restores clipboard.
+/// How long the target application gets to fetch the text once the paste
+/// keystroke has been sent.
+const PASTE_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// When there is no way to see the paste happen, how long to give the target
+/// application before putting the previous clipboard back.
+const PASTE_SETTLE: Duration = Duration::from_millis(300);
+
+/// The last text pasted into another application, kept so it can be undone.
+static LAST_INSERTION: Mutex<Option<Insertion>> = Mutex::new(None);
+
+/// What a paste put into the focused window.
+#[derive(Clone, Debug)]
+pub struct Insertion {
+    /// The history entry the text came from, once the caller links it.
+    pub entry: Option<Arc<EntryLink>>,
+    /// How many characters were typed or pasted.
+    pub chars: usize,
+    pub method: PasteMethod,
+}
+
+/// Whatever was on the clipboard before we pasted, in every format we know how
+/// to put back.
+struct ClipboardSnapshot {
+    text: Option<String>,
+    html: Option<String>,
+    image: Option<ImageData<'static>>,
+    files: Vec<PathBuf>,
+}
+
+impl ClipboardSnapshot {
+    fn capture(clipboard: &mut Clipboard) -> Self {
+        Self {
+            text: clipboard.get().text().ok(),
+            html: clipboard.get().html().ok(),
+            image: clipboard.get().image().ok(),
+            files: clipboard.get().file_list().unwrap_or_default(),
+        }
+    }
+
+    /// Puts back the richest format captured, with plain text alongside HTML.
+    fn restore(self, clipboard: &mut Clipboard) -> Result<(), String> {
+        let result = if !self.files.is_empty() {
+            clipboard.set().file_list(&self.files)
+        } else if let Some(image) = self.image {
+            clipboard.set().image(image)
+        } else if let Some(html) = self.html {
+            clipboard.set().html(html, self.text)
+        } else if let Some(text) = self.text {
+            clipboard.set().text(text)
+        } else {
+            clipboard.clear()
+        };
+        result.map_err(|e| format!("Failed to restore clipboard: {}", e))
+    }
+}
+
+/// Pastes text using the clipboard: saves current content, writes text, sends
+/// paste keystroke, restores clipboard unless something else replaced it.
 fn paste_via_clipboard(
     enigo: &mut Enigo,
     text: &str,
-    app_handle: &AppHandle,
     paste_method: &PasteMethod,
 ) -> Result<(), String> {
-    let clipboard = app_handle.clipboard();
-    let clipboard_content = clipboard.read_text().unwrap_or_default();
+    let mut clipboard = Clipboard::new().map_err(|e| format!("Failed to open clipboard: {}", e))?;
+    let snapshot = ClipboardSnapshot::capture(&mut clipboard);
+
+    // Serving the text ourselves shows when the target application fetched it
+    match TemporarySelection::offer(text) {
+        Ok(selection) => {
+            // Clipboard managers fetch a new entry right away; let them finish
+            // so only the target application's fetch counts as the paste
+            std::thread::sleep(Duration::from_millis(50));
+            selection.arm();
+            send_paste_key_combo(enigo, paste_method)?;
+            if !selection.wait_until_pasted(PASTE_TIMEOUT) {
+                warn!("No application fetched the text within {:?}", PASTE_TIMEOUT);
+            }
+            if selection.was_replaced() {
+                info!("Clipboard changed during paste, not restoring it");
+                return Ok(());
+            }
+            return snapshot.restore(&mut clipboard);
+        }
+        Err(e) => debug!("Falling back to a timed clipboard restore: {}", e),
+    }
 
-    // Write text to clipboard first
-    clipboard
-        .write_text(text)
+    set_text_excluded_from_history(&mut clipboard, text)
         .map_err(|e| format!("Failed to write to clipboard: {}", e))?;
 
-    std::thread::sleep(std::time::Duration::from_millis(50));
+    std::thread::sleep(Duration::from_millis(50));
+
+    send_paste_key_combo(enigo, paste_method)?;
+
+    std::thread::sleep(PASTE_SETTLE);
+
+    if clipboard.get().text().ok().as_deref() != Some(text) {
+        info!("Clipboard changed during paste, not restoring it");
+        return Ok(());
+    }
+    snapshot.restore(&mut clipboard)
+}
 
-    // Send paste key combo
+/// Marks the entry so clipboard history and managers don't record it.
+fn set_text_excluded_from_history(
+    clipboard: &mut Clipboard,
+    text: &str,
+) -> Result<(), arboard::Error> {
+    #[cfg(target_os = "macos")]
+    use arboard::SetExtApple;
+    #[cfg(target_os = "linux")]
+    use arboard::SetExtLinux;
+    #[cfg(target_os = "windows")]
+    use arboard::SetExtWindows;
+
+    clipboard.set().exclude_from_history().text(text)
+}
+
+fn send_paste_key_combo(enigo: &mut Enigo, paste_method: &PasteMethod) -> Result<(), String> {
     #[cfg(target_os = "linux")]
     let key_combo_sent = try_send_key_combo_linux(paste_method)?;
 
@@ -44,13 +157,6 @@ fn paste_via_clipboard(
         }
     }
 
-    std::thread::sleep(std::time::Duration::from_millis(50));
-
-    // Restore original clipboard content
-    clipboard
-        .write_text(&clipboard_content)
-        .map_err(|e| format!("Failed to restore clipboard: {}", e))?;
-
     Ok(())
 }
 
@@ -59,7 +165,12 @@ fn paste_via_clipboard(
 #[cfg(target_os = "linux")]
 fn try_send_key_combo_linux(paste_method: &PasteMethod) -> Result<bool, String> {
     if is_wayland() {
-        // Wayland: prefer wtype, then dotool
+        match wayland_input::send_key_combo(paste_method) {
+            Ok(()) => return Ok(true),
+            Err(e) => debug!("Native Wayland key combo unavailable: {}", e),
+        }
+
+        // Wayland fallback: prefer wtype, then dotool
         if is_wtype_available() {
             info!("Using wtype for key combo");
             send_key_combo_via_wtype(paste_method)?;
@@ -85,9 +196,14 @@ fn try_send_key_combo_linux(paste_method: &PasteMethod) -> Result<bool, String>
 /// Attempts to type text directly using Linux-native tools.
 /// Returns `Ok(true)` if a native tool handled it, `Ok(false)` to fall back to enigo.
 #[cfg(target_os = "linux")]
-fn try_direct_typing_linux(text: &str) -> Result<bool, String> {
+fn try_direct_typing_linux(text: &str, settings: &AppSettings) -> Result<bool, String> {
     if is_wayland() {
-        // Wayland: prefer wtype, then dotool
+        match wayland_input::type_text(text) {
+            Ok(()) => return Ok(true),
+            Err(e) => debug!("Native Wayland text input unavailable: {}", e),
+        }
+
+        // Wayland fallback: prefer wtype, then dotool
         if is_wtype_available() {
             info!("Using wtype for direct text input");
             type_text_via_wtype(text)?;
@@ -99,7 +215,13 @@ fn try_direct_typing_linux(text: &str) -> Result<bool, String> {
             return Ok(true);
         }
     } else {
-        // X11: prefer xdotool
+        let delay = Duration::from_millis(settings.typing_delay_ms as u64);
+        match x11_input::type_text(text, delay, settings.typing_chunk_size as usize) {
+            Ok(()) => return Ok(true),
+            Err(e) => debug!("XTEST text input unavailable: {}", e),
+        }
+
+        // X11 fallback: xdotool
         if is_xdotool_available() {
             info!("Using xdotool for direct text input");
             type_text_via_xdotool(text)?;
@@ -110,33 +232,34 @@ fn try_direct_typing_linux(text: &str) -> Result<bool, String> {
     Ok(false)
 }
 
-/// Check if wtype is available (Wayland text input tool)
+/// Check once whether a command-line tool is installed
 #[cfg(target_os = "linux")]
-fn is_wtype_available() -> bool {
+fn is_tool_installed(tool: &str) -> bool {
     Command::new("which")
-        .arg("wtype")
+        .arg(tool)
         .output()
         .map(|output| output.status.success())
         .unwrap_or(false)
 }
 
+/// Check if wtype is available (Wayland text input tool)
+#[cfg(target_os = "linux")]
+fn is_wtype_available() -> bool {
+    static AVAILABLE: Lazy<bool> = Lazy::new(|| is_tool_installed("wtype"));
+    *AVAILABLE
+}
+
 /// Check if dotool is available (another Wayland text input tool)
 #[cfg(target_os = "linux")]
 fn is_dotool_available() -> bool {
-    Command::new("which")
-        .arg("dotool")
-        .output()
-        .map(|output| output.status.success())
-        .unwrap_or(false)
+    static AVAILABLE: Lazy<bool> = Lazy::new(|| is_tool_installed("dotool"));
+    *AVAILABLE
 }
 
 #[cfg(target_os = "linux")]
 fn is_xdotool_available() -> bool {
-    Command::new("which")
-        .arg("xdotool")
-        .output()
-        .map(|output| output.status.success())
-        .unwrap_or(false)
+    static AVAILABLE: Lazy<bool> = Lazy::new(|| is_tool_installed("xdotool"));
+    *AVAILABLE
 }
 
 /// Type text directly via wtype on Wayland.
@@ -276,10 +399,11 @@ fn send_key_combo_via_xdotool(paste_method: &PasteMethod) -> Result<(), String>
 }
 
 /// Types text directly by simulating individual key presses.
-fn paste_direct(enigo: &mut Enigo, text: &str) -> Result<(), String> {
+#[cfg_attr(not(target_os = "linux"), allow(unused_variables))]
+fn paste_direct(enigo: &mut Enigo, text: &str, settings: &AppSettings) -> Result<(), String> {
     #[cfg(target_os = "linux")]
     {
-        if try_direct_typing_linux(text)? {
+        if try_direct_typing_linux(text, settings)? {
             return Ok(());
         }
         info!("Falling back to enigo for direct text input");
@@ -316,13 +440,19 @@ pub fn paste(text: String, app_handle: AppHandle) -> Result<(), String> {
             info!("PasteMethod::None selected - skipping paste action");
         }
         PasteMethod::Direct => {
-            paste_direct(&mut enigo, &text)?;
+            paste_direct(&mut enigo, &text, &settings)?;
         }
         PasteMethod::CtrlV | PasteMethod::CtrlShiftV | PasteMethod::ShiftInsert => {
-            paste_via_clipboard(&mut enigo, &text, &app_handle, &paste_method)?
+            paste_via_clipboard(&mut enigo, &text, &paste_method)?
         }
     }
 
+    *LAST_INSERTION.lock().unwrap() = (paste_method != PasteMethod::None).then(|| Insertion {
+        entry: None,
+        chars: typed_char_count(&text),
+        method: paste_method,
+    });
+
     // After pasting, optionally copy to clipboard based on settings
     if settings.clipboard_handling == ClipboardHandling::CopyToClipboard {
         let clipboard = app_handle.clipboard();
@@ -333,3 +463,82 @@ pub fn paste(text: String, app_handle: AppHandle) -> Result<(), String> {
 
     Ok(())
 }
+
+/// How many characters typing `text` produces. The typing backends skip
+/// control characters other than newline and tab, and undo must send exactly
+/// one backspace per character that was typed.
+fn typed_char_count(text: &str) -> usize {
+    text.chars()
+        .filter(|&c| matches!(c, '\n' | '\t') || !c.is_control())
+        .count()
+}
+
+/// Links the text `paste` just inserted to its history entry, so undoing it
+/// also discards the entry.
+pub fn link_last_insertion(entry: Arc<EntryLink>) {
+    if let Some(insertion) = LAST_INSERTION.lock().unwrap().as_mut() {
+        insertion.entry = Some(entry);
+    }
+}
+
+/// Takes the last insertion, so it's only ever undone once.
+pub fn take_last_insertion() -> Option<Insertion> {
+    LAST_INSERTION.lock().unwrap().take()
+}
+
+/// Removes `insertion` from the focused window, with backspaces when it was
+/// typed and the application's own undo when it was pasted.
+pub fn undo_insertion(app_handle: &AppHandle, insertion: &Insertion) -> Result<(), String> {
+    let enigo_state = app_handle
+        .try_state::<EnigoState>()
+        .ok_or("Enigo state not initialized")?;
+    let mut enigo = enigo_state
+        .0
+        .lock()
+        .map_err(|e| format!("Failed to lock Enigo: {}", e))?;
+
+    info!(
+        "Undoing {} characters inserted with {:?}",
+        insertion.chars, insertion.method
+    );
+    match insertion.method {
+        PasteMethod::None => Ok(()),
+        PasteMethod::Direct => {
+            #[cfg(target_os = "linux")]
+            if is_wayland() {
+                match wayland_input::send_backspaces(insertion.chars) {
+                    Ok(()) => return Ok(()),
+                    Err(e) => debug!("Native Wayland backspaces unavailable: {}", e),
+                }
+            }
+            input::send_backspaces(&mut enigo, insertion.chars)
+        }
+        PasteMethod::CtrlV | PasteMethod::CtrlShiftV | PasteMethod::ShiftInsert => {
+            #[cfg(target_os = "linux")]
+            if is_wayland() {
+                match wayland_input::send_undo() {
+                    Ok(()) => return Ok(()),
+                    Err(e) => debug!("Native Wayland undo unavailable: {}", e),
+                }
+            }
+            input::send_undo(&mut enigo)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn undo_counts_only_typed_characters() {
+        let text = "one\r\ntwo\t\u{7f}é";
+        assert_eq!(typed_char_count(text), 9);
+
+        #[cfg(target_os = "linux")]
+        assert_eq!(
+            typed_char_count(text),
+            text.chars().filter_map(x11_input::keysym_for).count()
+        );
+    }
+}
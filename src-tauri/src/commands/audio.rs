@@ -1,12 +1,21 @@
 use crate::audio_feedback;
-use crate::audio_toolkit::audio::{list_input_devices, list_output_devices};
-use crate::managers::audio::{AudioRecordingManager, MicrophoneMode};
-use crate::settings::{get_settings, write_settings};
+use crate::audio_toolkit::audio::{input_capabilities, list_input_devices, list_output_devices};
+use crate::audio_toolkit::{save_wav_file, LoopbackCapture};
+use crate::managers::audio::{
+    dsp_comparison_dir, wake_word_dir, wake_word_sample_paths, AudioRecordingManager,
+    MicrophoneMode,
+};
+use crate::settings::{
+    get_settings, write_settings, AppSettings, CaptureSource, MicrophoneCaptureSettings, VadEngine,
+    DEFAULT_MICROPHONE_KEY, MAX_PRE_ROLL_MS, MAX_VAD_FRAMES,
+};
+use crate::tray;
 use log::warn;
 use serde::{Deserialize, Serialize};
 use specta::Type;
 use std::sync::Arc;
 use tauri::{AppHandle, Manager};
+use tauri_plugin_opener::OpenerExt;
 
 #[derive(Serialize, Type)]
 pub struct CustomSounds {
@@ -39,26 +48,288 @@ pub struct AudioDevice {
     pub is_default: bool,
 }
 
+#[derive(Serialize, Debug, Clone, Type)]
+pub struct MicrophoneCapabilities {
+    pub channels: u16,
+    pub sample_rates: Vec<u32>,
+    pub min_buffer_size: Option<u32>,
+    pub max_buffer_size: Option<u32>,
+}
+
 #[tauri::command]
 #[specta::specta]
 pub fn update_microphone_mode(app: AppHandle, always_on: bool) -> Result<(), String> {
     // Update settings
     let mut settings = get_settings(&app);
     settings.always_on_microphone = always_on;
-    write_settings(&app, settings);
+    write_settings(&app, settings.clone());
+    tray::refresh_tray_menu(&app);
 
     // Update the audio manager mode
     let rm = app.state::<Arc<AudioRecordingManager>>();
-    let new_mode = if always_on {
-        MicrophoneMode::AlwaysOn
-    } else {
-        MicrophoneMode::OnDemand
-    };
-
-    rm.update_mode(new_mode)
+    rm.update_mode(MicrophoneMode::from_settings(&settings))
         .map_err(|e| format!("Failed to update microphone mode: {}", e))
 }
 
+fn update_hands_free(app: &AppHandle, update: impl FnOnce(&mut AppSettings)) -> Result<(), String> {
+    let mut settings = get_settings(app);
+    update(&mut settings);
+    write_settings(app, settings.clone());
+
+    let rm = app.state::<Arc<AudioRecordingManager>>();
+    rm.update_hands_free(&settings)
+        .map_err(|e| format!("Failed to update hands-free mode: {}", e))
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_hands_free_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    update_hands_free(&app, |settings| settings.hands_free_enabled = enabled)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_hands_free_silence_setting(app: AppHandle, silence_ms: u32) -> Result<(), String> {
+    if silence_ms == 0 {
+        return Err("Silence duration must be positive".to_string());
+    }
+    update_hands_free(&app, |settings| settings.hands_free_silence_ms = silence_ms)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_hands_free_max_duration_setting(
+    app: AppHandle,
+    max_duration_secs: u32,
+) -> Result<(), String> {
+    if max_duration_secs == 0 {
+        return Err("Maximum duration must be positive".to_string());
+    }
+    update_hands_free(&app, |settings| {
+        settings.hands_free_max_duration_secs = max_duration_secs
+    })
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_wake_word_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let rm = app.state::<Arc<AudioRecordingManager>>();
+    if enabled && !rm.has_enough_wake_word_samples() {
+        return Err("Record more wake word samples before enabling the wake word".to_string());
+    }
+
+    let mut settings = get_settings(&app);
+    settings.wake_word_enabled = enabled;
+    write_settings(&app, settings.clone());
+
+    rm.update_wake_word(&settings)
+        .map_err(|e| format!("Failed to update wake word: {}", e))
+}
+
+/// Records a two second sample of the wake phrase and returns the new sample count.
+#[tauri::command]
+#[specta::specta]
+pub async fn record_wake_word_sample(app: AppHandle) -> Result<u32, String> {
+    let rm = app.state::<Arc<AudioRecordingManager>>().inner().clone();
+    let samples = tauri::async_runtime::spawn_blocking({
+        let rm = rm.clone();
+        move || rm.record_wake_word_sample()
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| e.to_string())?;
+
+    let path = wake_word_dir(&app)
+        .map_err(|e| e.to_string())?
+        .join(format!(
+            "sample-{}.wav",
+            chrono::Utc::now().timestamp_millis()
+        ));
+    save_wav_file(&path, &samples)
+        .await
+        .map_err(|e| format!("Failed to save wake word sample: {}", e))?;
+
+    rm.update_wake_word(&get_settings(&app))
+        .map_err(|e| format!("Failed to update wake word: {}", e))?;
+    Ok(rm.wake_word_sample_count() as u32)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_wake_word_sample_count(app: AppHandle) -> Result<u32, String> {
+    Ok(wake_word_sample_paths(&app).len() as u32)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn clear_wake_word_samples(app: AppHandle) -> Result<(), String> {
+    for path in wake_word_sample_paths(&app) {
+        std::fs::remove_file(&path)
+            .map_err(|e| format!("Failed to delete {}: {}", path.display(), e))?;
+    }
+
+    // Without samples there is nothing to listen for
+    let mut settings = get_settings(&app);
+    settings.wake_word_enabled = false;
+    write_settings(&app, settings.clone());
+
+    app.state::<Arc<AudioRecordingManager>>()
+        .update_wake_word(&settings)
+        .map_err(|e| format!("Failed to update wake word: {}", e))
+}
+
+fn update_vad(app: &AppHandle, update: impl FnOnce(&mut AppSettings)) -> Result<(), String> {
+    let mut settings = get_settings(app);
+    update(&mut settings);
+    write_settings(app, settings.clone());
+
+    let rm = app.state::<Arc<AudioRecordingManager>>();
+    rm.update_vad(&settings)
+        .map_err(|e| format!("Failed to update voice activity detection: {}", e))
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_vad_engine_setting(app: AppHandle, engine: VadEngine) -> Result<(), String> {
+    update_vad(&app, |settings| settings.vad_engine = engine)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_vad_threshold_setting(app: AppHandle, threshold: f32) -> Result<(), String> {
+    if !(0.0..=1.0).contains(&threshold) {
+        return Err("VAD threshold must be between 0 and 1".to_string());
+    }
+    update_vad(&app, |settings| settings.vad_threshold = threshold)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_vad_prefill_setting(app: AppHandle, frames: u32) -> Result<(), String> {
+    if frames > MAX_VAD_FRAMES {
+        return Err(format!(
+            "VAD prefill must be at most {} frames",
+            MAX_VAD_FRAMES
+        ));
+    }
+    update_vad(&app, |settings| settings.vad_prefill_frames = frames)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_vad_hangover_setting(app: AppHandle, frames: u32) -> Result<(), String> {
+    if frames > MAX_VAD_FRAMES {
+        return Err(format!(
+            "VAD hangover must be at most {} frames",
+            MAX_VAD_FRAMES
+        ));
+    }
+    update_vad(&app, |settings| settings.vad_hangover_frames = frames)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_vad_onset_setting(app: AppHandle, frames: u32) -> Result<(), String> {
+    if frames == 0 {
+        return Err("VAD onset must be at least one frame".to_string());
+    }
+    update_vad(&app, |settings| settings.vad_onset_frames = frames)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_vad_trim_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    update_vad(&app, |settings| settings.vad_trim_enabled = enabled)
+}
+
+/// Emits `vad-preview` events with the VAD's verdict for every microphone frame
+/// until `stop_vad_preview` is called.
+#[tauri::command]
+#[specta::specta]
+pub fn start_vad_preview(app: AppHandle) -> Result<(), String> {
+    app.state::<Arc<AudioRecordingManager>>()
+        .set_vad_preview(true)
+        .map_err(|e| format!("Failed to start VAD preview: {}", e))
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn stop_vad_preview(app: AppHandle) -> Result<(), String> {
+    app.state::<Arc<AudioRecordingManager>>()
+        .set_vad_preview(false)
+        .map_err(|e| format!("Failed to stop VAD preview: {}", e))
+}
+
+fn update_dsp(app: &AppHandle, update: impl FnOnce(&mut AppSettings)) -> Result<(), String> {
+    let mut settings = get_settings(app);
+    update(&mut settings);
+    write_settings(app, settings.clone());
+
+    let rm = app.state::<Arc<AudioRecordingManager>>();
+    rm.update_dsp(&settings)
+        .map_err(|e| format!("Failed to update audio processing: {}", e))
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_dsp_high_pass_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    update_dsp(&app, |settings| settings.dsp_high_pass_enabled = enabled)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_dsp_denoise_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    update_dsp(&app, |settings| settings.dsp_denoise_enabled = enabled)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_dsp_agc_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    update_dsp(&app, |settings| settings.dsp_agc_enabled = enabled)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_dsp_clipping_detection_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    update_dsp(&app, |settings| {
+        settings.dsp_clipping_detection_enabled = enabled
+    })
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_dsp_ab_capture_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    update_dsp(&app, |settings| settings.dsp_ab_capture_enabled = enabled)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_pre_roll_setting(app: AppHandle, pre_roll_ms: u32) -> Result<(), String> {
+    if pre_roll_ms > MAX_PRE_ROLL_MS {
+        return Err(format!("Pre-roll must be at most {} ms", MAX_PRE_ROLL_MS));
+    }
+
+    let mut settings = get_settings(&app);
+    settings.pre_roll_ms = pre_roll_ms;
+    write_settings(&app, settings.clone());
+
+    app.state::<Arc<AudioRecordingManager>>()
+        .update_pre_roll(&settings)
+        .map_err(|e| format!("Failed to update pre-roll: {}", e))
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn open_dsp_comparison_folder(app: AppHandle) -> Result<(), String> {
+    let dir = dsp_comparison_dir(&app)
+        .map_err(|e| format!("Failed to create comparison folder: {}", e))?;
+
+    let path = dir.to_string_lossy().as_ref().to_string();
+    app.opener()
+        .open_path(path, None::<String>)
+        .map_err(|e| format!("Failed to open comparison folder: {}", e))
+}
+
 #[tauri::command]
 #[specta::specta]
 pub fn get_microphone_mode(app: AppHandle) -> Result<bool, String> {
@@ -115,6 +386,92 @@ pub fn get_selected_microphone(app: AppHandle) -> Result<String, String> {
         .unwrap_or_else(|| "default".to_string()))
 }
 
+#[tauri::command]
+#[specta::specta]
+pub fn get_microphone_capabilities(device_name: String) -> Result<MicrophoneCapabilities, String> {
+    let device = if device_name == DEFAULT_MICROPHONE_KEY {
+        crate::audio_toolkit::get_cpal_host().default_input_device()
+    } else {
+        list_input_devices()
+            .map_err(|e| format!("Failed to list audio devices: {}", e))?
+            .into_iter()
+            .find(|d| d.name == device_name)
+            .map(|d| d.device)
+    }
+    .ok_or_else(|| format!("Microphone '{}' not found", device_name))?;
+
+    let capabilities =
+        input_capabilities(&device).map_err(|e| format!("Failed to query microphone: {}", e))?;
+
+    Ok(MicrophoneCapabilities {
+        channels: capabilities.channels,
+        sample_rates: capabilities.sample_rates,
+        min_buffer_size: capabilities.buffer_size.map(|(min, _)| min),
+        max_buffer_size: capabilities.buffer_size.map(|(_, max)| max),
+    })
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_microphone_capture_setting(
+    app: AppHandle,
+    device_name: String,
+    capture: MicrophoneCaptureSettings,
+) -> Result<(), String> {
+    capture.validate()?;
+
+    let mut settings = get_settings(&app);
+    if capture == MicrophoneCaptureSettings::default() {
+        settings.microphone_capture.remove(&device_name);
+    } else {
+        settings.microphone_capture.insert(device_name, capture);
+    }
+    write_settings(&app, settings);
+
+    // Channel layout, rate and buffer size are fixed when the stream opens
+    let rm = app.state::<Arc<AudioRecordingManager>>();
+    rm.update_selected_device()
+        .map_err(|e| format!("Failed to restart microphone: {}", e))?;
+
+    Ok(())
+}
+
+/// Microphones to try, in order, when the selected one is not connected.
+#[tauri::command]
+#[specta::specta]
+pub fn change_fallback_microphones_setting(
+    app: AppHandle,
+    microphones: Vec<String>,
+) -> Result<(), String> {
+    let mut settings = get_settings(&app);
+    settings.fallback_microphones = microphones;
+    write_settings(&app, settings);
+
+    let rm = app.state::<Arc<AudioRecordingManager>>();
+    rm.update_selected_device()
+        .map_err(|e| format!("Failed to update selected device: {}", e))?;
+
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_capture_source_setting(app: AppHandle, source: CaptureSource) -> Result<(), String> {
+    if source != CaptureSource::Microphone && !LoopbackCapture::is_supported() {
+        return Err("System audio capture is not supported on this platform yet".to_string());
+    }
+
+    let mut settings = get_settings(&app);
+    settings.capture_source = source;
+    write_settings(&app, settings);
+
+    let rm = app.state::<Arc<AudioRecordingManager>>();
+    rm.update_selected_device()
+        .map_err(|e| format!("Failed to switch capture source: {}", e))?;
+
+    Ok(())
+}
+
 #[tauri::command]
 #[specta::specta]
 pub fn get_available_output_devices() -> Result<Vec<AudioDevice>, String> {
@@ -1,6 +1,10 @@
-use crate::managers::history::{HistoryEntry, HistoryManager};
+use crate::managers::history::{HistoryEntry, HistoryManager, MetricsSummary};
+use crate::paste_target;
+use crate::picker;
+use crate::transcript::TranscriptFormat;
 use std::sync::Arc;
 use tauri::{AppHandle, State};
+use tauri_plugin_opener::OpenerExt;
 
 #[tauri::command]
 #[specta::specta]
@@ -99,3 +103,83 @@ pub async fn update_recording_retention_period(
 
     Ok(())
 }
+
+/// Averages per-dictation metrics by model and day, optionally limited to the
+/// last `days` days.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_transcription_metrics(
+    _app: AppHandle,
+    history_manager: State<'_, Arc<HistoryManager>>,
+    days: Option<u32>,
+) -> Result<Vec<MetricsSummary>, String> {
+    history_manager
+        .get_metrics_summary(days)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Names a speaker of a meeting transcript. An empty name restores the
+/// default "Speaker N" label.
+#[tauri::command]
+#[specta::specta]
+pub async fn rename_history_speaker(
+    _app: AppHandle,
+    history_manager: State<'_, Arc<HistoryManager>>,
+    id: i64,
+    speaker: u32,
+    name: String,
+) -> Result<(), String> {
+    history_manager
+        .rename_speaker(id, speaker, name)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Writes the transcript next to the recording and reveals it in the file
+/// manager. Returns the path of the exported file.
+#[tauri::command]
+#[specta::specta]
+pub async fn export_history_entry(
+    app: AppHandle,
+    history_manager: State<'_, Arc<HistoryManager>>,
+    id: i64,
+    format: TranscriptFormat,
+) -> Result<String, String> {
+    let path = history_manager
+        .export_entry(id, format)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    app.opener()
+        .reveal_item_in_dir(&path)
+        .map_err(|e| format!("Failed to reveal exported transcript: {}", e))?;
+
+    Ok(path.to_string_lossy().into_owned())
+}
+
+/// Pastes a history entry into the window that was focused before the history
+/// picker opened.
+#[tauri::command]
+#[specta::specta]
+pub async fn paste_history_entry(
+    app: AppHandle,
+    history_manager: State<'_, Arc<HistoryManager>>,
+    id: i64,
+) -> Result<(), String> {
+    let entry = history_manager
+        .get_entry_by_id(id)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("History entry {} not found", id))?;
+    picker::hide_history_picker(&app);
+    // Not linked to the entry: undoing this paste shouldn't discard it
+    paste_target::paste_into_target(&app, entry.pasted_text(), None);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn hide_history_picker(app: AppHandle) {
+    picker::hide_history_picker(&app);
+}
@@ -0,0 +1,35 @@
+use crate::managers::meeting::{MeetingManager, MeetingStatus};
+use crate::settings::{get_settings, write_settings};
+use std::sync::Arc;
+use tauri::{AppHandle, State};
+
+#[tauri::command]
+#[specta::specta]
+pub fn start_meeting(meeting_manager: State<'_, Arc<MeetingManager>>) -> Result<(), String> {
+    meeting_manager.start().map_err(|e| e.to_string())
+}
+
+/// Stops recording the meeting. The transcript is saved to history once the
+/// remaining chunks are transcribed.
+#[tauri::command]
+#[specta::specta]
+pub fn stop_meeting(meeting_manager: State<'_, Arc<MeetingManager>>) -> Result<(), String> {
+    meeting_manager.stop().map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_meeting_status(meeting_manager: State<'_, Arc<MeetingManager>>) -> MeetingStatus {
+    meeting_manager.status()
+}
+
+/// Whether meeting transcripts are split up by speaker. Applies to meetings
+/// finished from now on.
+#[tauri::command]
+#[specta::specta]
+pub fn change_diarization_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = get_settings(&app);
+    settings.diarization_enabled = enabled;
+    write_settings(&app, settings);
+    Ok(())
+}
@@ -1,10 +1,18 @@
 pub mod audio;
 pub mod history;
+pub mod meeting;
 pub mod models;
 pub mod transcription;
 
-use crate::settings::{get_settings, write_settings, AppSettings, LogLevel};
+use crate::managers::config_file::{ConfigFileManager, ConfigFileStatus};
+use crate::managers::secrets::SecretsManager;
+use crate::paste_target;
+use crate::preview::{self, PastePreview};
+use crate::settings::{get_settings, write_settings, AppSettings, LogLevel, SettingsExport};
+use crate::shortcut;
 use crate::utils::cancel_current_operation;
+use log::info;
+use std::sync::Arc;
 use tauri::{AppHandle, Manager};
 use tauri_plugin_opener::OpenerExt;
 
@@ -14,6 +22,33 @@ pub fn cancel_operation(app: AppHandle) {
     cancel_current_operation(&app);
 }
 
+/// Pastes a transcription that was held back because focus moved while it was
+/// being transcribed.
+#[tauri::command]
+#[specta::specta]
+pub fn insert_pending_text(app: AppHandle) -> Result<(), String> {
+    paste_target::insert_pending(&app)
+}
+
+/// Pastes the dictation shown in the overlay preview as `text`, which may have
+/// been edited.
+#[tauri::command]
+#[specta::specta]
+pub fn accept_preview(app: AppHandle, text: String) -> Result<(), String> {
+    app.state::<PastePreview>().accept(text)
+}
+
+/// Post-processes the previewed dictation with another prompt and returns the
+/// new text. Without a prompt the original transcription is returned.
+#[tauri::command]
+#[specta::specta]
+pub async fn switch_preview_prompt(
+    app: AppHandle,
+    prompt_id: Option<String>,
+) -> Result<String, String> {
+    preview::switch_prompt(&app, prompt_id).await
+}
+
 #[tauri::command]
 #[specta::specta]
 pub fn get_app_dir_path(app: AppHandle) -> Result<String, String> {
@@ -25,10 +60,25 @@ pub fn get_app_dir_path(app: AppHandle) -> Result<String, String> {
     Ok(app_data_dir.to_string_lossy().to_string())
 }
 
+/// Names of the connected monitors, for picking where the overlay goes.
+#[tauri::command]
+#[specta::specta]
+pub fn get_monitor_names(app: AppHandle) -> Result<Vec<String>, String> {
+    let monitors = app.available_monitors().map_err(|e| e.to_string())?;
+    Ok(monitors
+        .iter()
+        .filter_map(|monitor| monitor.name().cloned())
+        .collect())
+}
+
 #[tauri::command]
 #[specta::specta]
 pub fn get_app_settings(app: AppHandle) -> Result<AppSettings, String> {
-    Ok(get_settings(&app))
+    let mut settings = get_settings(&app);
+    // API keys live in the secret store; only tell the frontend whether one is set
+    app.state::<Arc<SecretsManager>>()
+        .redact_api_keys(&mut settings);
+    Ok(settings)
 }
 
 #[tauri::command]
@@ -48,9 +98,50 @@ pub fn get_log_dir_path(app: AppHandle) -> Result<String, String> {
     Ok(log_dir.to_string_lossy().to_string())
 }
 
+/// Writes the current settings, prompts, custom words and bindings to a
+/// portable file that can be imported on another machine.
+#[tauri::command]
+#[specta::specta]
+pub fn export_settings(app: AppHandle, path: String) -> Result<(), String> {
+    let export = SettingsExport::new(&get_settings(&app));
+    let json = serde_json::to_string_pretty(&export)
+        .map_err(|e| format!("Failed to serialize settings: {}", e))?;
+
+    std::fs::write(&path, json).map_err(|e| format!("Failed to write settings file: {}", e))?;
+    info!("Exported settings to {}", path);
+
+    Ok(())
+}
+
+/// Replaces the current settings with those from a file written by
+/// `export_settings`. Audio devices and API keys keep their local values.
+#[tauri::command]
 #[specta::specta]
+pub fn import_settings(app: AppHandle, path: String) -> Result<AppSettings, String> {
+    let contents = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read settings file: {}", e))?;
+    let mut imported = SettingsExport::parse(&contents)?;
+    let current = get_settings(&app);
+
+    SettingsExport::keep_machine_settings(&mut imported, &current);
+
+    shortcut::apply_settings(&app, &current, imported)?;
+    info!("Imported settings from {}", path);
+
+    let mut settings = get_settings(&app);
+    app.state::<Arc<SecretsManager>>()
+        .redact_api_keys(&mut settings);
+    Ok(settings)
+}
+
+/// Reports which user config file is in use and whether it could be applied.
 #[tauri::command]
-pub fn set_log_level(app: AppHandle, level: LogLevel) -> Result<(), String> {
+#[specta::specta]
+pub fn get_config_file_status(app: AppHandle) -> Result<ConfigFileStatus, String> {
+    Ok(app.state::<Arc<ConfigFileManager>>().status())
+}
+
+pub(crate) fn apply_log_level(level: LogLevel) {
     let tauri_log_level: tauri_plugin_log::LogLevel = level.into();
     let log_level: log::Level = tauri_log_level.into();
     // Update the file log level atomic so the filter picks up the new level
@@ -58,6 +149,12 @@ pub fn set_log_level(app: AppHandle, level: LogLevel) -> Result<(), String> {
         log_level.to_level_filter() as u8,
         std::sync::atomic::Ordering::Relaxed,
     );
+}
+
+#[specta::specta]
+#[tauri::command]
+pub fn set_log_level(app: AppHandle, level: LogLevel) -> Result<(), String> {
+    apply_log_level(level);
 
     let mut settings = get_settings(&app);
     settings.log_level = level;
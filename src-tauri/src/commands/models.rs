@@ -1,6 +1,8 @@
+use crate::managers::benchmark::{BenchmarkManager, ModelBenchmark};
 use crate::managers::model::{ModelInfo, ModelManager};
 use crate::managers::transcription::TranscriptionManager;
 use crate::settings::{get_settings, write_settings};
+use crate::tray;
 use std::sync::Arc;
 use tauri::{AppHandle, State};
 
@@ -36,12 +38,15 @@ pub async fn download_model(
 #[tauri::command]
 #[specta::specta]
 pub async fn delete_model(
+    app_handle: AppHandle,
     model_manager: State<'_, Arc<ModelManager>>,
     model_id: String,
 ) -> Result<(), String> {
     model_manager
         .delete_model(&model_id)
-        .map_err(|e| e.to_string())
+        .map_err(|e| e.to_string())?;
+    tray::refresh_tray_menu(&app_handle);
+    Ok(())
 }
 
 #[tauri::command]
@@ -70,6 +75,7 @@ pub async fn set_active_model(
     let mut settings = get_settings(&app_handle);
     settings.selected_model = model_id.clone();
     write_settings(&app_handle, settings);
+    tray::refresh_tray_menu(&app_handle);
 
     Ok(())
 }
@@ -131,7 +137,28 @@ pub async fn cancel_download(
 
 #[tauri::command]
 #[specta::specta]
-pub async fn get_recommended_first_model() -> Result<String, String> {
-    // Recommend Parakeet V3 model for first-time users - fastest and most accurate
-    Ok("parakeet-tdt-0.6b-v3".to_string())
+pub async fn get_recommended_first_model(
+    benchmark_manager: State<'_, Arc<BenchmarkManager>>,
+) -> Result<String, String> {
+    Ok(benchmark_manager.recommended_model())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn run_model_benchmark(
+    benchmark_manager: State<'_, Arc<BenchmarkManager>>,
+) -> Result<Vec<ModelBenchmark>, String> {
+    let benchmark_manager = benchmark_manager.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || benchmark_manager.run())
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_model_benchmarks(
+    benchmark_manager: State<'_, Arc<BenchmarkManager>>,
+) -> Result<Vec<ModelBenchmark>, String> {
+    Ok(benchmark_manager.get_results())
 }
@@ -0,0 +1,136 @@
+//! Finds out which window has keyboard focus and gives it back later. Windows
+//! are tracked by X11 window id on Linux, by HWND on Windows and by process on
+//! macOS. Wayland doesn't let clients see or change focus, so nothing is
+//! tracked there.
+
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How long a refocused window gets to actually become focused.
+const ACTIVATE_TIMEOUT: Duration = Duration::from_millis(500);
+const ACTIVATE_POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WindowId(u64);
+
+/// The window with keyboard focus, if the platform lets us know.
+pub fn focused_window() -> Option<WindowId> {
+    platform::focused_window().map(WindowId)
+}
+
+/// Brings `window` to the front and waits for it to take focus. Returns
+/// `false` if it's gone or the window manager refused.
+pub fn activate_window(window: WindowId) -> bool {
+    if !platform::activate_window(window.0) {
+        return false;
+    }
+
+    let deadline = Instant::now() + ACTIVATE_TIMEOUT;
+    while Instant::now() < deadline {
+        if focused_window() == Some(window) {
+            return true;
+        }
+        thread::sleep(ACTIVATE_POLL_INTERVAL);
+    }
+    false
+}
+
+#[cfg(target_os = "linux")]
+mod platform {
+    use crate::utils::is_wayland;
+    use log::debug;
+    use x11rb::connection::Connection;
+    use x11rb::protocol::xproto::{
+        AtomEnum, ClientMessageEvent, ConnectionExt as _, EventMask, Window,
+    };
+    use x11rb::rust_connection::RustConnection;
+    use x11rb::{CURRENT_TIME, NONE};
+
+    /// Tells the window manager the request comes from a pager or taskbar,
+    /// which it honours more readily than one from an application.
+    const SOURCE_PAGER: u32 = 2;
+
+    fn connect() -> Option<(RustConnection, Window, u32)> {
+        if is_wayland() {
+            return None;
+        }
+        let (conn, screen_num) = x11rb::connect(None)
+            .map_err(|e| debug!("Cannot connect to the X server: {}", e))
+            .ok()?;
+        let root = conn.setup().roots[screen_num].root;
+        let atom = conn
+            .intern_atom(false, b"_NET_ACTIVE_WINDOW")
+            .ok()?
+            .reply()
+            .ok()?
+            .atom;
+        Some((conn, root, atom))
+    }
+
+    pub fn focused_window() -> Option<u64> {
+        let (conn, root, active_window) = connect()?;
+        let reply = conn
+            .get_property(false, root, active_window, AtomEnum::WINDOW, 0, 1)
+            .ok()?
+            .reply()
+            .ok()?;
+        let window = reply.value32()?.next()?;
+        (window != NONE).then_some(window as u64)
+    }
+
+    pub fn activate_window(window: u64) -> bool {
+        let Some((conn, root, active_window)) = connect() else {
+            return false;
+        };
+        let event = ClientMessageEvent::new(
+            32,
+            window as Window,
+            active_window,
+            [SOURCE_PAGER, CURRENT_TIME, 0, 0, 0],
+        );
+        conn.send_event(
+            false,
+            root,
+            EventMask::SUBSTRUCTURE_REDIRECT | EventMask::SUBSTRUCTURE_NOTIFY,
+            event,
+        )
+        .is_ok()
+            && conn.flush().is_ok()
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use windows::Win32::Foundation::HWND;
+    use windows::Win32::UI::WindowsAndMessaging::{
+        GetForegroundWindow, IsWindow, SetForegroundWindow,
+    };
+
+    pub fn focused_window() -> Option<u64> {
+        let hwnd = unsafe { GetForegroundWindow() };
+        (!hwnd.is_invalid()).then_some(hwnd.0 as usize as u64)
+    }
+
+    pub fn activate_window(window: u64) -> bool {
+        let hwnd = HWND(window as usize as *mut _);
+        unsafe { IsWindow(Some(hwnd)).as_bool() && SetForegroundWindow(hwnd).as_bool() }
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use objc2_app_kit::{NSApplicationActivationOptions, NSRunningApplication, NSWorkspace};
+
+    pub fn focused_window() -> Option<u64> {
+        let pid = NSWorkspace::sharedWorkspace()
+            .frontmostApplication()?
+            .processIdentifier();
+        (pid > 0).then_some(pid as u64)
+    }
+
+    pub fn activate_window(window: u64) -> bool {
+        NSRunningApplication::runningApplicationWithProcessIdentifier(window as i32)
+            .map(|app| app.activateWithOptions(NSApplicationActivationOptions::empty()))
+            .unwrap_or(false)
+    }
+}
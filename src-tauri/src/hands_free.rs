@@ -0,0 +1,42 @@
+use crate::actions::ACTION_MAP;
+use crate::audio_toolkit::HandsFreeEvent;
+use crate::managers::audio::AudioRecordingManager;
+use log::{debug, warn};
+use std::sync::Arc;
+use std::thread;
+use tauri::{AppHandle, Manager};
+
+/// Binding id for dictations started by voice activity instead of a shortcut.
+pub const HANDS_FREE_BINDING_ID: &str = "hands_free";
+
+/// Drives the transcribe action from the recorder's voice activity events.
+pub fn handle_event(app_handle: &AppHandle, event: HandsFreeEvent) {
+    // Events arrive on the audio thread, which must keep running for the
+    // recorder to answer the start/stop commands the action sends
+    let app_handle = app_handle.clone();
+    thread::spawn(move || {
+        let Some(action) = ACTION_MAP.get("transcribe") else {
+            warn!("No transcribe action defined for hands-free mode");
+            return;
+        };
+        let rm = app_handle.state::<Arc<AudioRecordingManager>>();
+
+        match event {
+            HandsFreeEvent::SpeechStarted => {
+                debug!("Hands-free: speech detected, starting dictation");
+                action.start(&app_handle, HANDS_FREE_BINDING_ID, "voice activity");
+                if !rm.is_recording() {
+                    rm.discard_hands_free_capture();
+                }
+            }
+            HandsFreeEvent::SpeechEnded | HandsFreeEvent::MaxDurationReached => {
+                // The dictation may have been cancelled in the meantime
+                if !rm.is_recording() {
+                    return;
+                }
+                debug!("Hands-free: {:?}, stopping dictation", event);
+                action.stop(&app_handle, HANDS_FREE_BINDING_ID, "voice activity");
+            }
+        }
+    });
+}
@@ -1,7 +1,13 @@
 use enigo::{Enigo, Key, Keyboard, Mouse, Settings};
 use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
 use tauri::{AppHandle, Manager};
 
+/// How long the user may keep a shortcut's modifiers down after its key is
+/// released before keys that would combine with them are given up on.
+const MODIFIER_RELEASE_TIMEOUT: Duration = Duration::from_secs(2);
+
 /// Wrapper for Enigo to store in Tauri's managed state.
 /// Enigo is wrapped in a Mutex since it requires mutable access.
 pub struct EnigoState(pub Mutex<Enigo>);
@@ -121,3 +127,93 @@ pub fn paste_text_direct(enigo: &mut Enigo, text: &str) -> Result<(), String> {
 
     Ok(())
 }
+
+/// Sends Ctrl+Z or Cmd+Z to undo the last edit in the focused application.
+/// Note: On Wayland, this may not work - callers should check for Wayland and use alternative methods.
+pub fn send_undo(enigo: &mut Enigo) -> Result<(), String> {
+    #[cfg(target_os = "macos")]
+    let (modifier_key, z_key_code) = (Key::Meta, Key::Raw(6));
+    #[cfg(target_os = "windows")]
+    let (modifier_key, z_key_code) = (Key::Control, Key::Other(0x5A)); // VK_Z
+    #[cfg(target_os = "linux")]
+    let (modifier_key, z_key_code) = (Key::Control, Key::Unicode('z'));
+
+    enigo
+        .key(modifier_key, enigo::Direction::Press)
+        .map_err(|e| format!("Failed to press modifier key: {}", e))?;
+    enigo
+        .key(z_key_code, enigo::Direction::Click)
+        .map_err(|e| format!("Failed to click Z key: {}", e))?;
+
+    std::thread::sleep(std::time::Duration::from_millis(100));
+
+    enigo
+        .key(modifier_key, enigo::Direction::Release)
+        .map_err(|e| format!("Failed to release modifier key: {}", e))?;
+
+    Ok(())
+}
+
+/// Presses Backspace `count` times.
+/// Note: On Wayland, this may not work - callers should check for Wayland and use alternative methods.
+pub fn send_backspaces(enigo: &mut Enigo, count: usize) -> Result<(), String> {
+    for _ in 0..count {
+        enigo
+            .key(Key::Backspace, enigo::Direction::Click)
+            .map_err(|e| format!("Failed to click Backspace key: {}", e))?;
+    }
+
+    Ok(())
+}
+
+/// Waits until no Shift, Control, Alt/Option or Super/Command key is
+/// physically held, so keys sent next aren't combined with the modifiers of
+/// the shortcut that triggered them (Ctrl+Alt+Backspace can end an X session).
+/// Fails if they are still held after a while. Returns right away where the
+/// state can't be read, such as on Wayland, whose virtual keyboard sends its
+/// own modifier state.
+pub fn wait_for_modifiers_released() -> Result<(), String> {
+    let deadline = Instant::now() + MODIFIER_RELEASE_TIMEOUT;
+    while modifiers_held() {
+        if Instant::now() >= deadline {
+            return Err("Modifier keys are still held down".to_string());
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+    Ok(())
+}
+
+#[cfg(target_os = "macos")]
+fn modifiers_held() -> bool {
+    use objc2_app_kit::{NSEvent, NSEventModifierFlags};
+
+    NSEvent::modifierFlags_class().intersects(
+        NSEventModifierFlags::Shift
+            | NSEventModifierFlags::Control
+            | NSEventModifierFlags::Option
+            | NSEventModifierFlags::Command,
+    )
+}
+
+#[cfg(target_os = "windows")]
+fn modifiers_held() -> bool {
+    use windows::Win32::UI::Input::KeyboardAndMouse::{
+        GetAsyncKeyState, VK_CONTROL, VK_LWIN, VK_MENU, VK_RWIN, VK_SHIFT,
+    };
+
+    [VK_SHIFT, VK_CONTROL, VK_MENU, VK_LWIN, VK_RWIN]
+        .iter()
+        // The high bit is set while the key is down
+        .any(|key| unsafe { GetAsyncKeyState(key.0 as i32) } < 0)
+}
+
+#[cfg(target_os = "linux")]
+fn modifiers_held() -> bool {
+    if crate::utils::is_wayland() {
+        return false;
+    }
+    crate::x11_input::modifiers_held().unwrap_or_else(|e| {
+        log::debug!("Can't read the X11 modifier state: {}", e);
+        false
+    })
+}
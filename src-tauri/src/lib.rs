@@ -3,27 +3,46 @@ mod actions;
 mod apple_intelligence;
 mod audio_feedback;
 pub mod audio_toolkit;
+mod caret;
 mod clipboard;
 mod commands;
+mod focus;
+mod hands_free;
 mod helpers;
 mod input;
 mod llm_client;
 mod managers;
+mod output;
 mod overlay;
+mod paste_target;
+mod picker;
+mod preview;
+mod selection;
 mod settings;
 mod shortcut;
 mod signal_handle;
+mod transcript;
 mod tray;
 mod tray_i18n;
 mod utils;
+#[cfg(target_os = "linux")]
+mod wayland_input;
+#[cfg(target_os = "linux")]
+mod x11_input;
 use specta_typescript::{BigIntExportBehavior, Typescript};
 use tauri_specta::{collect_commands, Builder};
 
 use env_filter::Builder as EnvFilterBuilder;
 use managers::audio::AudioRecordingManager;
+use managers::benchmark::BenchmarkManager;
+use managers::config_file::ConfigFileManager;
 use managers::history::HistoryManager;
+use managers::meeting::MeetingManager;
 use managers::model::ModelManager;
+use managers::secrets::SecretsManager;
 use managers::transcription::TranscriptionManager;
+use paste_target::PasteTarget;
+use preview::PastePreview;
 #[cfg(unix)]
 use signal_hook::consts::SIGUSR2;
 #[cfg(unix)]
@@ -35,7 +54,7 @@ use tauri::image::Image;
 
 use tauri::tray::TrayIconBuilder;
 use tauri::Emitter;
-use tauri::{AppHandle, Manager};
+use tauri::{AppHandle, Listener, Manager};
 use tauri_plugin_autostart::{MacosLauncher, ManagerExt};
 use tauri_plugin_log::{Builder as LogBuilder, RotationStrategy, Target, TargetKind};
 
@@ -122,6 +141,13 @@ fn initialize_core_logic(app_handle: &AppHandle) {
         }
     }
 
+    // Initialize the secrets store first so API keys are moved out of the
+    // settings file before anything else reads or rewrites it
+    let secrets_manager =
+        Arc::new(SecretsManager::new(app_handle).expect("Failed to initialize secrets manager"));
+    secrets_manager.migrate_legacy_api_keys(app_handle);
+    app_handle.manage(secrets_manager);
+
     // Initialize the managers
     let recording_manager = Arc::new(
         AudioRecordingManager::new(app_handle).expect("Failed to initialize recording manager"),
@@ -134,12 +160,23 @@ fn initialize_core_logic(app_handle: &AppHandle) {
     );
     let history_manager =
         Arc::new(HistoryManager::new(app_handle).expect("Failed to initialize history manager"));
+    let benchmark_manager = Arc::new(BenchmarkManager::new(
+        app_handle,
+        model_manager.clone(),
+        transcription_manager.clone(),
+    ));
 
     // Add managers to Tauri's managed state
     app_handle.manage(recording_manager.clone());
     app_handle.manage(model_manager.clone());
     app_handle.manage(transcription_manager.clone());
     app_handle.manage(history_manager.clone());
+    app_handle.manage(benchmark_manager);
+
+    // Resumes unfinished meetings, so it needs the managers above
+    let meeting_manager =
+        Arc::new(MeetingManager::new(app_handle).expect("Failed to initialize meeting manager"));
+    app_handle.manage(meeting_manager);
 
     // Initialize the shortcuts
     shortcut::init_shortcuts(app_handle);
@@ -196,7 +233,34 @@ fn initialize_core_logic(app_handle: &AppHandle) {
             "quit" => {
                 app.exit(0);
             }
-            _ => {}
+            "push_to_talk" => {
+                let enabled = !settings::get_settings(app).push_to_talk;
+                if let Err(e) = shortcut::change_ptt_setting(app.clone(), enabled) {
+                    log::error!("Failed to toggle push-to-talk from the tray: {}", e);
+                }
+                tray::notify_settings_changed(app, "push_to_talk");
+            }
+            "always_on_mic" => {
+                let always_on = !settings::get_settings(app).always_on_microphone;
+                if let Err(e) = commands::audio::update_microphone_mode(app.clone(), always_on) {
+                    log::error!(
+                        "Failed to toggle the always-on microphone from the tray: {}",
+                        e
+                    );
+                }
+                tray::notify_settings_changed(app, "always_on_microphone");
+            }
+            "post_process" => {
+                let enabled = !settings::get_settings(app).post_process_enabled;
+                if let Err(e) = shortcut::change_post_process_enabled_setting(app.clone(), enabled)
+                {
+                    log::error!("Failed to toggle post-processing from the tray: {}", e);
+                }
+                tray::notify_settings_changed(app, "post_process_enabled");
+            }
+            id => {
+                tray::handle_menu_selection(app, id);
+            }
         })
         .build(app_handle)
         .unwrap();
@@ -205,6 +269,16 @@ fn initialize_core_logic(app_handle: &AppHandle) {
     // Initialize tray menu with idle state
     utils::update_tray_menu(app_handle, &utils::TrayIconState::Idle, None);
 
+    // Keep the recent transcriptions and model list in the tray menu current
+    for event in [
+        "history-updated",
+        "model-state-changed",
+        "model-download-complete",
+    ] {
+        let app = app_handle.clone();
+        app_handle.listen(event, move |_| tray::refresh_tray_menu(&app));
+    }
+
     // Get the autostart manager and configure based on user setting
     let autostart_manager = app_handle.autolaunch();
     let settings = settings::get_settings(&app_handle);
@@ -219,6 +293,20 @@ fn initialize_core_logic(app_handle: &AppHandle) {
 
     // Create the recording overlay window (hidden by default)
     utils::create_recording_overlay(app_handle);
+
+    // Create the history picker window (hidden by default)
+    picker::create_history_picker(app_handle);
+
+    // Apply the optional user config file last, since it may re-register
+    // shortcuts and refresh the tray menu
+    let config_file_manager = Arc::new(
+        ConfigFileManager::new(app_handle).expect("Failed to initialize config file manager"),
+    );
+    config_file_manager.reload();
+    if let Err(e) = config_file_manager.start_watching() {
+        log::warn!("Failed to watch config file for changes: {}", e);
+    }
+    app_handle.manage(config_file_manager);
 }
 
 #[tauri::command]
@@ -251,10 +339,17 @@ pub fn run() {
         shortcut::change_translate_to_english_setting,
         shortcut::change_selected_language_setting,
         shortcut::change_overlay_position_setting,
+        shortcut::change_overlay_monitor_setting,
         shortcut::change_debug_mode_setting,
         shortcut::change_word_correction_threshold_setting,
         shortcut::change_paste_method_setting,
+        shortcut::change_typing_delay_setting,
+        shortcut::change_typing_chunk_size_setting,
         shortcut::change_clipboard_handling_setting,
+        shortcut::change_focus_change_behavior_setting,
+        shortcut::change_output_sinks_setting,
+        shortcut::change_paste_preview_enabled_setting,
+        shortcut::change_paste_preview_countdown_setting,
         shortcut::change_post_process_enabled_setting,
         shortcut::change_post_process_base_url_setting,
         shortcut::change_post_process_api_key_setting,
@@ -274,10 +369,17 @@ pub fn run() {
         shortcut::change_update_checks_setting,
         trigger_update_check,
         commands::cancel_operation,
+        commands::insert_pending_text,
+        commands::accept_preview,
+        commands::switch_preview_prompt,
         commands::get_app_dir_path,
+        commands::get_monitor_names,
         commands::get_app_settings,
         commands::get_default_settings,
         commands::get_log_dir_path,
+        commands::export_settings,
+        commands::import_settings,
+        commands::get_config_file_status,
         commands::set_log_level,
         commands::open_recordings_folder,
         commands::open_log_dir,
@@ -294,11 +396,39 @@ pub fn run() {
         commands::models::has_any_models_available,
         commands::models::has_any_models_or_downloads,
         commands::models::get_recommended_first_model,
+        commands::models::run_model_benchmark,
+        commands::models::get_model_benchmarks,
         commands::audio::update_microphone_mode,
         commands::audio::get_microphone_mode,
+        commands::audio::change_hands_free_setting,
+        commands::audio::change_hands_free_silence_setting,
+        commands::audio::change_hands_free_max_duration_setting,
+        commands::audio::change_wake_word_setting,
+        commands::audio::record_wake_word_sample,
+        commands::audio::get_wake_word_sample_count,
+        commands::audio::clear_wake_word_samples,
+        commands::audio::change_vad_engine_setting,
+        commands::audio::change_vad_threshold_setting,
+        commands::audio::change_vad_prefill_setting,
+        commands::audio::change_vad_hangover_setting,
+        commands::audio::change_vad_onset_setting,
+        commands::audio::change_vad_trim_setting,
+        commands::audio::start_vad_preview,
+        commands::audio::stop_vad_preview,
+        commands::audio::change_dsp_high_pass_setting,
+        commands::audio::change_dsp_denoise_setting,
+        commands::audio::change_dsp_agc_setting,
+        commands::audio::change_dsp_clipping_detection_setting,
+        commands::audio::change_dsp_ab_capture_setting,
+        commands::audio::change_pre_roll_setting,
+        commands::audio::open_dsp_comparison_folder,
         commands::audio::get_available_microphones,
         commands::audio::set_selected_microphone,
         commands::audio::get_selected_microphone,
+        commands::audio::get_microphone_capabilities,
+        commands::audio::change_microphone_capture_setting,
+        commands::audio::change_fallback_microphones_setting,
+        commands::audio::change_capture_source_setting,
         commands::audio::get_available_output_devices,
         commands::audio::set_selected_output_device,
         commands::audio::get_selected_output_device,
@@ -316,6 +446,15 @@ pub fn run() {
         commands::history::delete_history_entry,
         commands::history::update_history_limit,
         commands::history::update_recording_retention_period,
+        commands::history::get_transcription_metrics,
+        commands::history::rename_history_speaker,
+        commands::history::export_history_entry,
+        commands::history::paste_history_entry,
+        commands::history::hide_history_picker,
+        commands::meeting::start_meeting,
+        commands::meeting::stop_meeting,
+        commands::meeting::get_meeting_status,
+        commands::meeting::change_diarization_setting,
         helpers::clamshell::is_laptop,
     ]);
 
@@ -374,6 +513,8 @@ pub fn run() {
             Some(vec![]),
         ))
         .manage(Mutex::new(ShortcutToggleStates::default()))
+        .manage(PasteTarget::default())
+        .manage(PastePreview::default())
         .setup(move |app| {
             let settings = get_settings(&app.handle());
             let tauri_log_level: tauri_plugin_log::LogLevel = settings.log_level.into();
@@ -415,6 +556,9 @@ pub fn run() {
                     }
                 }
             }
+            tauri::WindowEvent::Moved(_) if window.label() == "recording_overlay" => {
+                utils::remember_dragged_position(window.app_handle());
+            }
             tauri::WindowEvent::ThemeChanged(theme) => {
                 log::info!("Theme changed to: {:?}", theme);
                 // Update tray icon to match new theme, maintaining idle state
@@ -1,11 +1,32 @@
-use crate::audio_toolkit::{list_input_devices, vad::SmoothedVad, AudioRecorder, SileroVad};
+use crate::audio_toolkit::vad::{EnergyVad, SmoothedVad, WakeWordDetector, MIN_WAKE_WORD_SAMPLES};
+use crate::audio_toolkit::{
+    self, get_cpal_host, list_input_devices, read_wav_file, save_wav_file, AbCapture,
+    AudioRecorder, CaptureConfig, ChunkingConfig, CpalDeviceInfo, DspConfig, HandsFreeConfig,
+    SileroVad, VoiceActivityDetector,
+};
+use crate::hands_free;
 use crate::helpers::clamshell;
-use crate::settings::{get_settings, AppSettings};
+use crate::managers::meeting::MeetingManager;
+use crate::settings::{
+    get_settings, AppSettings, CaptureSource, VadEngine, DEFAULT_MICROPHONE_KEY,
+};
 use crate::utils;
-use log::{debug, error, info};
-use std::sync::{Arc, Mutex};
-use std::time::Instant;
-use tauri::Manager;
+use cpal::traits::{DeviceTrait, HostTrait};
+use log::{debug, error, info, warn};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+use tauri::{Emitter, Manager};
+
+/// How often the connected microphones are compared with the last known set.
+const DEVICE_POLL_INTERVAL: Duration = Duration::from_secs(3);
+
+/// How long an on-demand stream stays open after a recording when pre-roll is
+/// on, so the dictations that follow still get audio from before the shortcut.
+const PRE_ROLL_WARM_DURATION: Duration = Duration::from_secs(30);
 
 fn set_mute(mute: bool) {
     // Expected behavior:
@@ -98,6 +119,12 @@ fn set_mute(mute: bool) {
 
 const WHISPER_SAMPLE_RATE: usize = 16000;
 
+const WAKE_WORD_SAMPLE_BINDING_ID: &str = "wake_word_sample";
+const WAKE_WORD_SAMPLE_DURATION: Duration = Duration::from_secs(2);
+
+/// Number of recent recordings kept by the A/B capture.
+const MAX_AB_CAPTURES: usize = 10;
+
 /* ──────────────────────────────────────────────────────────────── */
 
 #[derive(Clone, Debug)]
@@ -112,31 +139,234 @@ pub enum MicrophoneMode {
     OnDemand,
 }
 
+impl MicrophoneMode {
+    /// Hands-free dictation needs the stream open to hear speech onset.
+    pub fn from_settings(settings: &AppSettings) -> Self {
+        if settings.always_on_microphone || settings.hands_free_enabled {
+            MicrophoneMode::AlwaysOn
+        } else {
+            MicrophoneMode::OnDemand
+        }
+    }
+}
+
+/// Durations of the most recent recording, kept for dictation metrics.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RecordingStats {
+    /// Time between starting and stopping the recording.
+    pub duration: Duration,
+    /// Audio left after VAD trimming, before short recordings are padded.
+    pub speech_duration: Duration,
+}
+
 /* ──────────────────────────────────────────────────────────────── */
 
+/// Builds the configured VAD engine, smoothed with the user's prefill, hangover
+/// and onset frame counts.
+fn create_vad(
+    vad_path: &Path,
+    settings: &AppSettings,
+) -> Result<Box<dyn VoiceActivityDetector>, anyhow::Error> {
+    let inner: Box<dyn VoiceActivityDetector> = match settings.vad_engine {
+        VadEngine::Silero => Box::new(
+            SileroVad::new(vad_path, settings.vad_threshold)
+                .map_err(|e| anyhow::anyhow!("Failed to create SileroVad: {}", e))?,
+        ),
+        VadEngine::Energy => Box::new(EnergyVad::new(settings.vad_threshold)?),
+    };
+
+    Ok(Box::new(SmoothedVad::new(
+        inner,
+        settings.vad_prefill_frames as usize,
+        settings.vad_hangover_frames as usize,
+        settings.vad_onset_frames as usize,
+    )))
+}
+
 fn create_audio_recorder(
-    vad_path: &str,
+    vad_path: &Path,
     app_handle: &tauri::AppHandle,
+    settings: &AppSettings,
+    stream_errors: mpsc::Sender<()>,
 ) -> Result<AudioRecorder, anyhow::Error> {
-    let silero = SileroVad::new(vad_path, 0.3)
-        .map_err(|e| anyhow::anyhow!("Failed to create SileroVad: {}", e))?;
-    let smoothed_vad = SmoothedVad::new(Box::new(silero), 15, 15, 2);
-
     // Recorder with VAD plus a spectrum-level callback that forwards updates to
     // the frontend.
     let recorder = AudioRecorder::new()
         .map_err(|e| anyhow::anyhow!("Failed to create AudioRecorder: {}", e))?
-        .with_vad(Box::new(smoothed_vad))
+        .with_vad(create_vad(vad_path, settings)?)
         .with_level_callback({
             let app_handle = app_handle.clone();
             move |levels| {
                 utils::emit_levels(&app_handle, &levels);
             }
+        })
+        .with_vad_callback({
+            let app_handle = app_handle.clone();
+            move |is_speech| {
+                let _ = app_handle.emit("vad-preview", is_speech);
+            }
+        })
+        .with_clipping_callback({
+            let app_handle = app_handle.clone();
+            move || {
+                warn!("Microphone input is clipping");
+                let _ = app_handle.emit("audio-clipping", ());
+            }
+        })
+        .with_hands_free_callback({
+            let app_handle = app_handle.clone();
+            move |event| hands_free::handle_event(&app_handle, event)
+        })
+        .with_chunk_callback({
+            let app_handle = app_handle.clone();
+            move |chunk| match app_handle.try_state::<Arc<MeetingManager>>() {
+                Some(mm) => mm.add_chunk(chunk),
+                None => warn!("Dropping audio chunk, meetings are not available"),
+            }
+        })
+        // Recovery needs to close the recorder, which cannot happen from the
+        // audio thread, so the device monitor takes care of it
+        .with_error_callback(move |e| {
+            warn!("Microphone stream failed: {}", e);
+            let _ = stream_errors.send(());
         });
 
     Ok(recorder)
 }
 
+/// Directory holding the user's recordings of their wake phrase.
+pub fn wake_word_dir(app_handle: &tauri::AppHandle) -> Result<PathBuf, anyhow::Error> {
+    let dir = app_handle.path().app_data_dir()?.join("wake_word");
+    fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+pub fn wake_word_sample_paths(app_handle: &tauri::AppHandle) -> Vec<PathBuf> {
+    let Ok(entries) = wake_word_dir(app_handle).and_then(|dir| Ok(fs::read_dir(dir)?)) else {
+        return Vec::new();
+    };
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "wav"))
+        .collect();
+    paths.sort();
+    paths
+}
+
+fn create_wake_word_detector(
+    vad_path: &Path,
+    app_handle: &tauri::AppHandle,
+    settings: &AppSettings,
+) -> Option<Box<dyn VoiceActivityDetector>> {
+    if !settings.hands_free_enabled || !settings.wake_word_enabled {
+        return None;
+    }
+
+    let samples: Vec<Vec<f32>> = wake_word_sample_paths(app_handle)
+        .iter()
+        .filter_map(|path| match read_wav_file(path) {
+            Ok(samples) => Some(samples),
+            Err(e) => {
+                warn!("Skipping wake word sample {:?}: {}", path, e);
+                None
+            }
+        })
+        .collect();
+
+    // The detector runs its own VAD so it can segment phrases independently of
+    // the recording pipeline
+    let detector =
+        create_vad(vad_path, settings).and_then(|vad| WakeWordDetector::new(vad, &samples));
+    match detector {
+        Ok(detector) => Some(Box::new(detector)),
+        Err(e) => {
+            warn!("Wake word disabled: {}", e);
+            None
+        }
+    }
+}
+
+fn dsp_config(settings: &AppSettings) -> DspConfig {
+    DspConfig {
+        high_pass: settings.dsp_high_pass_enabled,
+        denoise: settings.dsp_denoise_enabled,
+        agc: settings.dsp_agc_enabled,
+        clipping_detection: settings.dsp_clipping_detection_enabled,
+        ab_capture: settings.dsp_ab_capture_enabled,
+    }
+}
+
+fn capture_config(settings: &AppSettings, device_name: Option<&str>) -> CaptureConfig {
+    let capture = settings
+        .microphone_capture
+        .get(device_name.unwrap_or(DEFAULT_MICROPHONE_KEY))
+        .cloned()
+        .unwrap_or_default();
+    CaptureConfig {
+        channel_mix: capture.channel_mix,
+        sample_rate: capture.sample_rate,
+        buffer_size: capture.buffer_size,
+    }
+}
+
+fn capture_source(settings: &AppSettings) -> audio_toolkit::CaptureSource {
+    match settings.capture_source {
+        CaptureSource::Microphone => audio_toolkit::CaptureSource::Microphone,
+        CaptureSource::SystemAudio => audio_toolkit::CaptureSource::SystemAudio,
+        CaptureSource::Mixed => audio_toolkit::CaptureSource::Mixed,
+    }
+}
+
+fn input_device_names() -> Vec<String> {
+    list_input_devices()
+        .map(|devices| devices.into_iter().map(|d| d.name).collect())
+        .unwrap_or_default()
+}
+
+/// Directory holding raw and processed copies of recordings for comparison.
+pub fn dsp_comparison_dir(app_handle: &tauri::AppHandle) -> Result<PathBuf, anyhow::Error> {
+    let dir = app_handle.path().app_data_dir()?.join("dsp_comparison");
+    fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+/// Saves both versions of a recording, keeping only the most recent pairs.
+fn save_ab_capture(app_handle: &tauri::AppHandle, capture: AbCapture) {
+    let dir = match dsp_comparison_dir(app_handle) {
+        Ok(dir) => dir,
+        Err(e) => {
+            warn!("Failed to save A/B capture: {}", e);
+            return;
+        }
+    };
+
+    tauri::async_runtime::spawn(async move {
+        let stamp = chrono::Utc::now().timestamp_millis();
+        for (suffix, samples) in [("raw", &capture.raw), ("processed", &capture.processed)] {
+            let path = dir.join(format!("{}-{}.wav", stamp, suffix));
+            if let Err(e) = save_wav_file(&path, samples).await {
+                warn!("Failed to save A/B capture {:?}: {}", path, e);
+            }
+        }
+
+        let mut paths: Vec<PathBuf> = fs::read_dir(&dir)
+            .map(|entries| entries.filter_map(|e| e.ok().map(|e| e.path())).collect())
+            .unwrap_or_default();
+        paths.sort();
+        let excess = paths.len().saturating_sub(MAX_AB_CAPTURES * 2);
+        for path in &paths[..excess] {
+            let _ = fs::remove_file(path);
+        }
+    });
+}
+
+fn hands_free_config(settings: &AppSettings) -> Option<HandsFreeConfig> {
+    settings.hands_free_enabled.then(|| HandsFreeConfig {
+        silence: Duration::from_millis(settings.hands_free_silence_ms as u64),
+        max_duration: Duration::from_secs(settings.hands_free_max_duration_secs as u64),
+    })
+}
+
 /* ──────────────────────────────────────────────────────────────── */
 
 #[derive(Clone)]
@@ -149,6 +379,16 @@ pub struct AudioRecordingManager {
     is_open: Arc<Mutex<bool>>,
     is_recording: Arc<Mutex<bool>>,
     did_mute: Arc<Mutex<bool>>,
+    recording_started_at: Arc<Mutex<Option<Instant>>>,
+    last_recording_stats: Arc<Mutex<RecordingStats>>,
+    vad_preview: Arc<Mutex<bool>>,
+    /// Name of the device the stream was last opened on.
+    active_microphone: Arc<Mutex<Option<String>>>,
+    stream_errors: mpsc::Sender<()>,
+    chunking: Arc<Mutex<Option<ChunkingConfig>>>,
+    /// Bumped each time an on-demand stream is due to close, so only the
+    /// latest recording's warm period closes it.
+    release_generation: Arc<AtomicU64>,
 }
 
 impl AudioRecordingManager {
@@ -156,11 +396,8 @@ impl AudioRecordingManager {
 
     pub fn new(app: &tauri::AppHandle) -> Result<Self, anyhow::Error> {
         let settings = get_settings(app);
-        let mode = if settings.always_on_microphone {
-            MicrophoneMode::AlwaysOn
-        } else {
-            MicrophoneMode::OnDemand
-        };
+        let mode = MicrophoneMode::from_settings(&settings);
+        let (stream_errors, stream_error_rx) = mpsc::channel();
 
         let manager = Self {
             state: Arc::new(Mutex::new(RecordingState::Idle)),
@@ -171,45 +408,167 @@ impl AudioRecordingManager {
             is_open: Arc::new(Mutex::new(false)),
             is_recording: Arc::new(Mutex::new(false)),
             did_mute: Arc::new(Mutex::new(false)),
+            recording_started_at: Arc::new(Mutex::new(None)),
+            last_recording_stats: Arc::new(Mutex::new(RecordingStats::default())),
+            vad_preview: Arc::new(Mutex::new(false)),
+            active_microphone: Arc::new(Mutex::new(None)),
+            stream_errors,
+            chunking: Arc::new(Mutex::new(None)),
+            release_generation: Arc::new(AtomicU64::new(0)),
         };
 
-        // Always-on?  Open immediately.
+        // Always-on?  Open immediately. Without a usable microphone the
+        // device monitor opens it once one is connected.
         if matches!(mode, MicrophoneMode::AlwaysOn) {
-            manager.start_microphone_stream()?;
+            if let Err(e) = manager.start_microphone_stream() {
+                warn!("Failed to open microphone: {}", e);
+            }
         }
 
+        manager.spawn_device_monitor(stream_error_rx);
+
         Ok(manager)
     }
 
     /* ---------- helper methods --------------------------------------------- */
 
-    fn get_effective_microphone_device(&self, settings: &AppSettings) -> Option<cpal::Device> {
-        // Check if we're in clamshell mode and have a clamshell microphone configured
-        let use_clamshell_mic = if let Ok(is_clamshell) = clamshell::is_clamshell() {
-            is_clamshell && settings.clamshell_microphone.is_some()
-        } else {
-            false
+    /// Picks the microphone to record from: the clamshell microphone while the
+    /// lid is closed, then the selected one, then the first connected
+    /// fallback. `None` means the system default.
+    fn resolve_microphone(&self, settings: &AppSettings) -> Option<CpalDeviceInfo> {
+        let use_clamshell_mic = clamshell::is_clamshell().unwrap_or(false);
+        let candidates = settings
+            .clamshell_microphone
+            .iter()
+            .filter(|_| use_clamshell_mic)
+            .chain(settings.selected_microphone.iter())
+            .chain(settings.fallback_microphones.iter());
+
+        let mut devices = match list_input_devices() {
+            Ok(devices) => devices,
+            Err(e) => {
+                debug!("Failed to list devices, using default: {}", e);
+                return None;
+            }
         };
 
-        let device_name = if use_clamshell_mic {
-            settings.clamshell_microphone.as_ref().unwrap()
-        } else {
-            settings.selected_microphone.as_ref()?
-        };
+        for name in candidates {
+            if let Some(index) = devices.iter().position(|d| d.name == *name) {
+                return Some(devices.swap_remove(index));
+            }
+        }
+        None
+    }
+
+    /// Name of the device `resolve_microphone` picked, including the default.
+    fn microphone_label(microphone: Option<&CpalDeviceInfo>) -> Option<String> {
+        match microphone {
+            Some(info) => Some(info.name.clone()),
+            None => get_cpal_host()
+                .default_input_device()
+                .and_then(|d| d.name().ok()),
+        }
+    }
+
+    /// Records the device in use, telling the UI when it differs from the one
+    /// used before. `None` means no microphone could be opened.
+    fn set_active_microphone(&self, name: Option<String>) {
+        let mut active = self.active_microphone.lock().unwrap();
+        if *active == name {
+            return;
+        }
+        if active.is_some() {
+            info!("Active microphone changed to {:?}", name);
+            let _ = self.app_handle.emit("microphone-changed", name.clone());
+        }
+        *active = name;
+    }
+
+    /// Watches for microphones being connected or removed and for stream
+    /// failures, reopening the stream on the best available device.
+    fn spawn_device_monitor(&self, stream_errors: mpsc::Receiver<()>) {
+        let manager = self.clone();
+        thread::spawn(move || {
+            let mut known = input_device_names();
+            let mut stream_failed = false;
+
+            loop {
+                match stream_errors.recv_timeout(DEVICE_POLL_INTERVAL) {
+                    Ok(()) => {
+                        // One failure often raises several reports
+                        while stream_errors.try_recv().is_ok() {}
+                        stream_failed = true;
+                    }
+                    Err(mpsc::RecvTimeoutError::Timeout) => {}
+                    Err(mpsc::RecvTimeoutError::Disconnected) => break,
+                }
+
+                let names = input_device_names();
+                let devices_changed = names != known;
+                if devices_changed {
+                    debug!("Input devices changed: {:?}", names);
+                    known = names;
+                    let _ = manager.app_handle.emit("audio-devices-changed", ());
+                }
+
+                if (devices_changed || stream_failed) && manager.recover_stream(stream_failed) {
+                    stream_failed = false;
+                }
+            }
+        });
+    }
+
+    /// Reopens the stream if it failed or a better device became available.
+    /// Returns false if recovery has to be retried later.
+    fn recover_stream(&self, stream_failed: bool) -> bool {
+        let is_open = *self.is_open.lock().unwrap();
+        let always_on = matches!(*self.mode.lock().unwrap(), MicrophoneMode::AlwaysOn);
+        if !is_open && !always_on {
+            // The next recording opens whichever device is best by then
+            return true;
+        }
+
+        // Reopening now would discard what has been recorded so far
+        if self.is_recording() {
+            return false;
+        }
+
+        let settings = get_settings(&self.app_handle);
+        let uses_microphone = settings.capture_source != CaptureSource::SystemAudio;
+        if is_open && !stream_failed {
+            if !uses_microphone {
+                return true;
+            }
+            let wanted = Self::microphone_label(self.resolve_microphone(&settings).as_ref());
+            if wanted == *self.active_microphone.lock().unwrap() {
+                return true;
+            }
+        }
 
-        // Find the device by name
-        match list_input_devices() {
-            Ok(devices) => devices
-                .into_iter()
-                .find(|d| d.name == *device_name)
-                .map(|d| d.device),
+        info!("Reopening microphone stream");
+        self.stop_microphone_stream();
+        match self.start_microphone_stream() {
+            Ok(()) => true,
             Err(e) => {
-                debug!("Failed to list devices, using default: {}", e);
-                None
+                warn!("Failed to reopen microphone: {}", e);
+                if uses_microphone {
+                    self.set_active_microphone(None);
+                }
+                false
             }
         }
     }
 
+    fn vad_path(&self) -> Result<PathBuf, anyhow::Error> {
+        self.app_handle
+            .path()
+            .resolve(
+                "resources/models/silero_vad_v4.onnx",
+                tauri::path::BaseDirectory::Resource,
+            )
+            .map_err(|e| anyhow::anyhow!("Failed to resolve VAD path: {}", e))
+    }
+
     /* ---------- microphone life-cycle -------------------------------------- */
 
     /// Applies mute if mute_while_recording is enabled and stream is open
@@ -247,33 +606,59 @@ impl AudioRecordingManager {
         let mut did_mute_guard = self.did_mute.lock().unwrap();
         *did_mute_guard = false;
 
-        let vad_path = self
-            .app_handle
-            .path()
-            .resolve(
-                "resources/models/silero_vad_v4.onnx",
-                tauri::path::BaseDirectory::Resource,
-            )
-            .map_err(|e| anyhow::anyhow!("Failed to resolve VAD path: {}", e))?;
+        let vad_path = self.vad_path()?;
+        let settings = get_settings(&self.app_handle);
         let mut recorder_opt = self.recorder.lock().unwrap();
 
         if recorder_opt.is_none() {
             *recorder_opt = Some(create_audio_recorder(
-                vad_path.to_str().unwrap(),
+                &vad_path,
                 &self.app_handle,
+                &settings,
+                self.stream_errors.clone(),
             )?);
         }
 
-        // Get the selected device from settings, considering clamshell mode
-        let settings = get_settings(&self.app_handle);
-        let selected_device = self.get_effective_microphone_device(&settings);
+        // Get the device to use from settings, considering clamshell mode and
+        // fallbacks. Capture settings follow the device actually opened.
+        let microphone = self.resolve_microphone(&settings);
+        let label = Self::microphone_label(microphone.as_ref());
 
         if let Some(rec) = recorder_opt.as_mut() {
-            rec.open(selected_device)
+            rec.set_capture_source(capture_source(&settings));
+            rec.set_capture_config(capture_config(
+                &settings,
+                microphone.as_ref().map(|m| m.name.as_str()),
+            ));
+            rec.set_trimming(settings.vad_trim_enabled)
+                .map_err(|e| anyhow::anyhow!("Failed to configure VAD trimming: {}", e))?;
+            rec.set_dsp(dsp_config(&settings))
+                .map_err(|e| anyhow::anyhow!("Failed to configure audio processing: {}", e))?;
+            rec.set_pre_roll(Duration::from_millis(settings.pre_roll_ms as u64))
+                .map_err(|e| anyhow::anyhow!("Failed to configure pre-roll: {}", e))?;
+            rec.set_hands_free(hands_free_config(&settings))
+                .map_err(|e| anyhow::anyhow!("Failed to configure hands-free mode: {}", e))?;
+            rec.set_chunking(*self.chunking.lock().unwrap())
+                .map_err(|e| anyhow::anyhow!("Failed to configure chunked recording: {}", e))?;
+            rec.set_wake_word(create_wake_word_detector(
+                &vad_path,
+                &self.app_handle,
+                &settings,
+            ))
+            .map_err(|e| anyhow::anyhow!("Failed to configure wake word: {}", e))?;
+            rec.open(microphone.map(|m| m.device))
                 .map_err(|e| anyhow::anyhow!("Failed to open recorder: {}", e))?;
+            // A reopened stream starts without the preview running
+            if *self.vad_preview.lock().unwrap() {
+                rec.set_preview(true)
+                    .map_err(|e| anyhow::anyhow!("Failed to resume VAD preview: {}", e))?;
+            }
         }
 
         *open_flag = true;
+        if settings.capture_source != CaptureSource::SystemAudio {
+            self.set_active_microphone(label);
+        }
         info!(
             "Microphone stream initialized in {:?}",
             start_time.elapsed()
@@ -330,6 +715,140 @@ impl AudioRecordingManager {
         Ok(())
     }
 
+    /// Applies the hands-free settings to the open stream, opening it if needed.
+    pub fn update_hands_free(&self, settings: &AppSettings) -> Result<(), anyhow::Error> {
+        self.update_mode(MicrophoneMode::from_settings(settings))?;
+
+        if let Some(rec) = self.recorder.lock().unwrap().as_mut() {
+            rec.set_hands_free(hands_free_config(settings))
+                .map_err(|e| anyhow::anyhow!("Failed to configure hands-free mode: {}", e))?;
+        }
+        self.update_wake_word(settings)
+    }
+
+    /// Rebuilds the wake word detector from the current samples.
+    pub fn update_wake_word(&self, settings: &AppSettings) -> Result<(), anyhow::Error> {
+        let vad_path = self.vad_path()?;
+        if let Some(rec) = self.recorder.lock().unwrap().as_mut() {
+            rec.set_wake_word(create_wake_word_detector(
+                &vad_path,
+                &self.app_handle,
+                settings,
+            ))
+            .map_err(|e| anyhow::anyhow!("Failed to configure wake word: {}", e))?;
+        }
+        Ok(())
+    }
+
+    /// Swaps in a VAD built from the current settings, including the one the
+    /// wake word detector uses to segment phrases.
+    pub fn update_vad(&self, settings: &AppSettings) -> Result<(), anyhow::Error> {
+        let vad_path = self.vad_path()?;
+        if let Some(rec) = self.recorder.lock().unwrap().as_mut() {
+            rec.replace_vad(create_vad(&vad_path, settings)?);
+            rec.set_trimming(settings.vad_trim_enabled)
+                .map_err(|e| anyhow::anyhow!("Failed to configure VAD trimming: {}", e))?;
+        }
+        self.update_wake_word(settings)
+    }
+
+    pub fn update_dsp(&self, settings: &AppSettings) -> Result<(), anyhow::Error> {
+        if let Some(rec) = self.recorder.lock().unwrap().as_mut() {
+            rec.set_dsp(dsp_config(settings))
+                .map_err(|e| anyhow::anyhow!("Failed to configure audio processing: {}", e))?;
+        }
+        Ok(())
+    }
+
+    pub fn update_pre_roll(&self, settings: &AppSettings) -> Result<(), anyhow::Error> {
+        if let Some(rec) = self.recorder.lock().unwrap().as_mut() {
+            rec.set_pre_roll(Duration::from_millis(settings.pre_roll_ms as u64))
+                .map_err(|e| anyhow::anyhow!("Failed to configure pre-roll: {}", e))?;
+        }
+        Ok(())
+    }
+
+    /// Streams the VAD's classification of live microphone input as
+    /// `vad-preview` events, opening the microphone for as long as it runs.
+    pub fn set_vad_preview(&self, enabled: bool) -> Result<(), anyhow::Error> {
+        *self.vad_preview.lock().unwrap() = enabled;
+
+        if enabled {
+            self.start_microphone_stream()?;
+        }
+        if let Some(rec) = self.recorder.lock().unwrap().as_ref() {
+            rec.set_preview(enabled)
+                .map_err(|e| anyhow::anyhow!("Failed to toggle VAD preview: {}", e))?;
+        }
+
+        if !enabled
+            && matches!(*self.mode.lock().unwrap(), MicrophoneMode::OnDemand)
+            && matches!(*self.state.lock().unwrap(), RecordingState::Idle)
+        {
+            self.stop_microphone_stream();
+        }
+        Ok(())
+    }
+
+    /// Whether an on-demand stream can be closed now that a recording is over.
+    fn should_release_stream(&self) -> bool {
+        matches!(*self.mode.lock().unwrap(), MicrophoneMode::OnDemand)
+            && !*self.vad_preview.lock().unwrap()
+    }
+
+    /// Closes an on-demand stream after a recording. With pre-roll on it stays
+    /// open for a while first, since pre-roll can only keep audio from a stream
+    /// that was already open when the shortcut was pressed.
+    fn release_stream(&self) {
+        if get_settings(&self.app_handle).pre_roll_ms == 0 {
+            self.stop_microphone_stream();
+            return;
+        }
+
+        let generation = self.release_generation.fetch_add(1, Ordering::SeqCst) + 1;
+        let app_handle = self.app_handle.clone();
+        thread::spawn(move || {
+            thread::sleep(PRE_ROLL_WARM_DURATION);
+            let rm = app_handle.state::<Arc<AudioRecordingManager>>();
+            if rm.release_generation.load(Ordering::SeqCst) == generation
+                && !rm.is_recording()
+                && rm.should_release_stream()
+            {
+                debug!("Closing the microphone after the pre-roll warm period");
+                rm.stop_microphone_stream();
+            }
+        });
+    }
+
+    /// Records the user saying their wake phrase, trimmed by the VAD.
+    pub fn record_wake_word_sample(&self) -> Result<Vec<f32>, anyhow::Error> {
+        if !self.try_start_recording(WAKE_WORD_SAMPLE_BINDING_ID) {
+            anyhow::bail!("Cannot record a wake word sample while another recording is active");
+        }
+        std::thread::sleep(WAKE_WORD_SAMPLE_DURATION);
+
+        let mut samples = self
+            .stop_recording(WAKE_WORD_SAMPLE_BINDING_ID)
+            .unwrap_or_default();
+        // Drop the silence stop_recording pads short recordings with
+        while samples.last() == Some(&0.0) {
+            samples.pop();
+        }
+        if samples.is_empty() {
+            anyhow::bail!("No speech detected in the wake word sample");
+        }
+        Ok(samples)
+    }
+
+    pub fn wake_word_sample_count(&self) -> usize {
+        wake_word_sample_paths(&self.app_handle).len()
+    }
+
+    /// Whether enough samples exist to enable the wake word.
+    pub fn has_enough_wake_word_samples(&self) -> bool {
+        self.wake_word_sample_count() >= MIN_WAKE_WORD_SAMPLES
+    }
+
     /* ---------- recording --------------------------------------------------- */
 
     pub fn try_start_recording(&self, binding_id: &str) -> bool {
@@ -347,6 +866,7 @@ impl AudioRecordingManager {
             if let Some(rec) = self.recorder.lock().unwrap().as_ref() {
                 if rec.start().is_ok() {
                     *self.is_recording.lock().unwrap() = true;
+                    *self.recording_started_at.lock().unwrap() = Some(Instant::now());
                     *state = RecordingState::Recording {
                         binding_id: binding_id.to_string(),
                     };
@@ -361,6 +881,17 @@ impl AudioRecordingManager {
         }
     }
 
+    /// Splits the following recordings into chunks for the meeting manager
+    /// instead of returning their audio from `stop_recording`.
+    pub fn set_chunking(&self, config: Option<ChunkingConfig>) -> Result<(), anyhow::Error> {
+        *self.chunking.lock().unwrap() = config;
+        if let Some(rec) = self.recorder.lock().unwrap().as_mut() {
+            rec.set_chunking(config)
+                .map_err(|e| anyhow::anyhow!("Failed to configure chunked recording: {}", e))?;
+        }
+        Ok(())
+    }
+
     pub fn update_selected_device(&self) -> Result<(), anyhow::Error> {
         // If currently open, restart the microphone stream to use the new device
         if *self.is_open.lock().unwrap() {
@@ -382,7 +913,12 @@ impl AudioRecordingManager {
 
                 let samples = if let Some(rec) = self.recorder.lock().unwrap().as_ref() {
                     match rec.stop() {
-                        Ok(buf) => buf,
+                        Ok(buf) => {
+                            if let Some(capture) = rec.take_ab_capture() {
+                                save_ab_capture(&self.app_handle, capture);
+                            }
+                            buf
+                        }
                         Err(e) => {
                             error!("stop() failed: {e}");
                             Vec::new()
@@ -395,9 +931,23 @@ impl AudioRecordingManager {
 
                 *self.is_recording.lock().unwrap() = false;
 
+                let duration = self
+                    .recording_started_at
+                    .lock()
+                    .unwrap()
+                    .take()
+                    .map(|started| started.elapsed())
+                    .unwrap_or_default();
+                *self.last_recording_stats.lock().unwrap() = RecordingStats {
+                    duration,
+                    speech_duration: Duration::from_secs_f64(
+                        samples.len() as f64 / WHISPER_SAMPLE_RATE as f64,
+                    ),
+                };
+
                 // In on-demand mode turn the mic off again
-                if matches!(*self.mode.lock().unwrap(), MicrophoneMode::OnDemand) {
-                    self.stop_microphone_stream();
+                if self.should_release_stream() {
+                    self.release_stream();
                 }
 
                 // Pad if very short
@@ -414,6 +964,10 @@ impl AudioRecordingManager {
             _ => None,
         }
     }
+    pub fn last_recording_stats(&self) -> RecordingStats {
+        *self.last_recording_stats.lock().unwrap()
+    }
+
     pub fn is_recording(&self) -> bool {
         matches!(
             *self.state.lock().unwrap(),
@@ -421,6 +975,16 @@ impl AudioRecordingManager {
         )
     }
 
+    /// Drops audio the recorder started capturing on speech onset when no
+    /// dictation could be started for it.
+    pub fn discard_hands_free_capture(&self) {
+        if matches!(*self.state.lock().unwrap(), RecordingState::Idle) {
+            if let Some(rec) = self.recorder.lock().unwrap().as_ref() {
+                let _ = rec.stop();
+            }
+        }
+    }
+
     /// Cancel any ongoing recording without returning audio samples
     pub fn cancel_recording(&self) {
         let mut state = self.state.lock().unwrap();
@@ -436,7 +1000,7 @@ impl AudioRecordingManager {
             *self.is_recording.lock().unwrap() = false;
 
             // In on-demand mode turn the mic off again
-            if matches!(*self.mode.lock().unwrap(), MicrophoneMode::OnDemand) {
+            if self.should_release_stream() {
                 self.stop_microphone_stream();
             }
         }
@@ -0,0 +1,314 @@
+use anyhow::Result;
+use chrono::Utc;
+use log::{debug, info, warn};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_store::StoreExt;
+
+use crate::audio_toolkit::constants::WHISPER_SAMPLE_RATE;
+use crate::audio_toolkit::{read_wav_file, word_errors};
+use crate::managers::audio::AudioRecordingManager;
+use crate::managers::model::ModelManager;
+use crate::managers::transcription::TranscriptionManager;
+
+const BENCHMARK_STORE_PATH: &str = "benchmark_store.json";
+
+/// Each clip directory holds WAV files plus a manifest listing their transcripts.
+const CLIPS_MANIFEST: &str = "clips.json";
+
+/// Models slower than this are not recommended for interactive dictation.
+const MAX_RECOMMENDED_RTF: f64 = 0.5;
+
+/// Used until the user has benchmarked any model.
+const DEFAULT_RECOMMENDED_MODEL: &str = "parakeet-tdt-0.6b-v3";
+
+#[derive(Deserialize)]
+struct ReferenceClip {
+    file: String,
+    transcript: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Type)]
+pub struct ModelBenchmark {
+    pub model_id: String,
+    pub real_time_factor: f64,
+    pub word_error_rate: f64,
+    pub clip_count: u32,
+    pub timestamp: i64,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct BenchmarkProgress {
+    pub model_id: String,
+    pub clip: u32,
+    pub clip_count: u32,
+}
+
+/// Measures every downloaded model on this machine against reference clips, so
+/// model recommendations reflect the user's hardware rather than fixed scores.
+pub struct BenchmarkManager {
+    app_handle: AppHandle,
+    model_manager: Arc<ModelManager>,
+    transcription_manager: Arc<TranscriptionManager>,
+    is_running: AtomicBool,
+}
+
+impl BenchmarkManager {
+    pub fn new(
+        app_handle: &AppHandle,
+        model_manager: Arc<ModelManager>,
+        transcription_manager: Arc<TranscriptionManager>,
+    ) -> Self {
+        Self {
+            app_handle: app_handle.clone(),
+            model_manager,
+            transcription_manager,
+            is_running: AtomicBool::new(false),
+        }
+    }
+
+    /// Bundled clips, followed by any the user added to the app data dir.
+    fn clip_dirs(&self) -> Vec<PathBuf> {
+        let mut dirs = Vec::new();
+        if let Ok(dir) = self
+            .app_handle
+            .path()
+            .resolve("resources/benchmark", tauri::path::BaseDirectory::Resource)
+        {
+            dirs.push(dir);
+        }
+        if let Ok(dir) = self.app_handle.path().app_data_dir() {
+            dirs.push(dir.join("benchmark"));
+        }
+        dirs
+    }
+
+    fn load_clips(&self) -> Vec<(Vec<f32>, String)> {
+        let mut clips = Vec::new();
+        for dir in self.clip_dirs() {
+            let manifest_path = dir.join(CLIPS_MANIFEST);
+            if !manifest_path.exists() {
+                continue;
+            }
+
+            match read_manifest(&manifest_path) {
+                Ok(manifest) => {
+                    for clip in manifest {
+                        match read_wav_file(dir.join(&clip.file)) {
+                            Ok(samples) => clips.push((samples, clip.transcript)),
+                            Err(e) => warn!("Skipping benchmark clip '{}': {}", clip.file, e),
+                        }
+                    }
+                }
+                Err(e) => warn!("Failed to read {:?}: {}", manifest_path, e),
+            }
+        }
+        clips
+    }
+
+    /// Transcribes every reference clip with every downloaded model. This takes
+    /// a while and keeps the transcription engine busy, so it refuses to start
+    /// while recording, and dictation waits until it's done. The previously
+    /// loaded model is restored afterwards.
+    pub fn run(&self) -> Result<Vec<ModelBenchmark>> {
+        // Claimed before checking for a recording, so a dictation can't start in between
+        if self.is_running.swap(true, Ordering::SeqCst) {
+            anyhow::bail!("A benchmark is already running");
+        }
+        if self
+            .app_handle
+            .state::<Arc<AudioRecordingManager>>()
+            .is_recording()
+        {
+            self.is_running.store(false, Ordering::SeqCst);
+            anyhow::bail!("Cannot run a benchmark while recording");
+        }
+
+        let result = self.run_all_models();
+        self.is_running.store(false, Ordering::SeqCst);
+        result
+    }
+
+    /// Whether a benchmark is swapping models in and out right now.
+    pub fn is_running(&self) -> bool {
+        self.is_running.load(Ordering::SeqCst)
+    }
+
+    fn run_all_models(&self) -> Result<Vec<ModelBenchmark>> {
+        let clips = self.load_clips();
+        if clips.is_empty() {
+            anyhow::bail!(
+                "No benchmark clips found. Add WAV files and a {} manifest to {:?}",
+                CLIPS_MANIFEST,
+                self.clip_dirs()
+            );
+        }
+
+        let mut model_ids: Vec<String> = self
+            .model_manager
+            .get_available_models()
+            .into_iter()
+            .filter(|model| model.is_downloaded)
+            .map(|model| model.id)
+            .collect();
+        model_ids.sort();
+        if model_ids.is_empty() {
+            anyhow::bail!("No downloaded models to benchmark");
+        }
+
+        let previous_model = self.transcription_manager.get_current_model();
+        let mut results = Vec::new();
+        for model_id in &model_ids {
+            match self.benchmark_model(model_id, &clips) {
+                Ok(result) => results.push(result),
+                Err(e) => warn!("Benchmark failed for model '{}': {}", model_id, e),
+            }
+        }
+
+        match previous_model {
+            Some(model_id) => self.transcription_manager.load_model(&model_id)?,
+            None => self.transcription_manager.unload_model()?,
+        }
+
+        self.save_results(&results);
+        Ok(results)
+    }
+
+    fn benchmark_model(
+        &self,
+        model_id: &str,
+        clips: &[(Vec<f32>, String)],
+    ) -> Result<ModelBenchmark> {
+        info!("Benchmarking model '{}' on {} clips", model_id, clips.len());
+
+        let mut inference = Duration::ZERO;
+        let mut audio_secs = 0.0;
+        let mut errors = 0;
+        let mut reference_words = 0;
+
+        for (index, (samples, transcript)) in clips.iter().enumerate() {
+            let _ = self.app_handle.emit(
+                "benchmark-progress",
+                BenchmarkProgress {
+                    model_id: model_id.to_string(),
+                    clip: index as u32 + 1,
+                    clip_count: clips.len() as u32,
+                },
+            );
+
+            // The engine may unload itself after each transcription depending on
+            // the unload timeout, so make sure the right model is loaded each time
+            if self.transcription_manager.get_current_model().as_deref() != Some(model_id) {
+                self.transcription_manager.load_model(model_id)?;
+            }
+
+            let result = self.transcription_manager.transcribe(samples.clone())?;
+            let (clip_errors, clip_words) = word_errors(transcript, &result.text);
+            debug!(
+                "Clip {} with '{}': {} errors in {} words, inference {:?}",
+                index + 1,
+                model_id,
+                clip_errors,
+                clip_words,
+                result.inference_duration
+            );
+
+            inference += result.inference_duration;
+            audio_secs += samples.len() as f64 / WHISPER_SAMPLE_RATE as f64;
+            errors += clip_errors;
+            reference_words += clip_words;
+        }
+
+        let benchmark = ModelBenchmark {
+            model_id: model_id.to_string(),
+            real_time_factor: inference.as_secs_f64() / audio_secs.max(f64::EPSILON),
+            word_error_rate: errors as f64 / reference_words.max(1) as f64,
+            clip_count: clips.len() as u32,
+            timestamp: Utc::now().timestamp(),
+        };
+        info!(
+            "Model '{}': RTF {:.3}, WER {:.1}%",
+            model_id,
+            benchmark.real_time_factor,
+            benchmark.word_error_rate * 100.0
+        );
+        Ok(benchmark)
+    }
+
+    fn stored_results(&self) -> HashMap<String, ModelBenchmark> {
+        self.app_handle
+            .store(BENCHMARK_STORE_PATH)
+            .ok()
+            .and_then(|store| store.get("results"))
+            .and_then(|value| serde_json::from_value(value).ok())
+            .unwrap_or_default()
+    }
+
+    fn save_results(&self, results: &[ModelBenchmark]) {
+        let mut stored = self.stored_results();
+        for result in results {
+            stored.insert(result.model_id.clone(), result.clone());
+        }
+
+        match self.app_handle.store(BENCHMARK_STORE_PATH) {
+            Ok(store) => store.set("results", serde_json::to_value(&stored).unwrap()),
+            Err(e) => warn!("Failed to save benchmark results: {}", e),
+        }
+    }
+
+    /// Latest measured results for every model that has been benchmarked.
+    pub fn get_results(&self) -> Vec<ModelBenchmark> {
+        let mut results: Vec<ModelBenchmark> = self.stored_results().into_values().collect();
+        results.sort_by(|a, b| a.model_id.cmp(&b.model_id));
+        results
+    }
+
+    /// The most accurate benchmarked model that is fast enough for dictation,
+    /// falling back to the fastest one, or a sensible default without results.
+    pub fn recommended_model(&self) -> String {
+        let results = self.get_results();
+
+        let most_accurate = results
+            .iter()
+            .filter(|result| result.real_time_factor <= MAX_RECOMMENDED_RTF)
+            .min_by(|a, b| a.word_error_rate.total_cmp(&b.word_error_rate));
+        let fastest = || {
+            results
+                .iter()
+                .min_by(|a, b| a.real_time_factor.total_cmp(&b.real_time_factor))
+        };
+
+        most_accurate
+            .or_else(fastest)
+            .map(|result| result.model_id.clone())
+            .unwrap_or_else(|| DEFAULT_RECOMMENDED_MODEL.to_string())
+    }
+}
+
+fn read_manifest(path: &Path) -> Result<Vec<ReferenceClip>> {
+    let contents = fs::read_to_string(path)?;
+    Ok(serde_json::from_str(&contents)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bundled_manifest_lists_existing_clips() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("resources/benchmark");
+        let manifest = read_manifest(&dir.join(CLIPS_MANIFEST)).unwrap();
+        assert!(!manifest.is_empty(), "no bundled benchmark clips");
+        for clip in &manifest {
+            assert!(dir.join(&clip.file).is_file(), "missing {}", clip.file);
+            assert!(!clip.transcript.trim().is_empty());
+        }
+    }
+}
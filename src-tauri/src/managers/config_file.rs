@@ -0,0 +1,258 @@
+use anyhow::Result;
+use log::{debug, error, info, warn};
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::Serialize;
+use serde_json::Value;
+use specta::Type;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::managers::secrets::SecretsManager;
+use crate::settings::{get_settings, validate_settings, AppSettings};
+use crate::shortcut;
+
+/// Config files we look for in the app config dir, in order of preference.
+const CONFIG_FILE_NAMES: &[&str] = &["aleflow.toml", "aleflow.json"];
+
+/// Editors often save a file in several steps, so wait for events to settle
+/// before reloading.
+const RELOAD_DEBOUNCE: Duration = Duration::from_millis(300);
+
+#[derive(Clone, Debug, Default, Serialize, Type)]
+pub struct ConfigFileStatus {
+    // The config file in use, if one exists
+    pub path: Option<String>,
+    // Why the last attempt to apply the file failed
+    pub error: Option<String>,
+}
+
+/// Applies an optional user-editable config file on top of the settings store
+/// and re-applies it whenever the file changes. Only the settings present in
+/// the file are overridden, so it can be as small as a single line.
+pub struct ConfigFileManager {
+    app_handle: AppHandle,
+    config_dir: PathBuf,
+    status: Mutex<ConfigFileStatus>,
+    watcher: Mutex<Option<RecommendedWatcher>>,
+}
+
+impl ConfigFileManager {
+    pub fn new(app_handle: &AppHandle) -> Result<Self> {
+        let config_dir = app_handle
+            .path()
+            .app_config_dir()
+            .map_err(|e| anyhow::anyhow!("Failed to get app config dir: {}", e))?;
+        fs::create_dir_all(&config_dir)?;
+
+        Ok(Self {
+            app_handle: app_handle.clone(),
+            config_dir,
+            status: Mutex::new(ConfigFileStatus::default()),
+            watcher: Mutex::new(None),
+        })
+    }
+
+    pub fn status(&self) -> ConfigFileStatus {
+        self.status.lock().unwrap().clone()
+    }
+
+    /// Watches the config dir rather than the file itself so that files created
+    /// later, or replaced by editors that write to a temp file and rename it,
+    /// are picked up too.
+    pub fn start_watching(self: &Arc<Self>) -> Result<()> {
+        let (tx, rx) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(move |res| {
+            let _ = tx.send(res);
+        })?;
+        watcher.watch(&self.config_dir, RecursiveMode::NonRecursive)?;
+
+        let manager = self.clone();
+        thread::spawn(move || {
+            while let Ok(res) = rx.recv() {
+                let event: notify::Event = match res {
+                    Ok(event) => event,
+                    Err(e) => {
+                        warn!("Config file watcher error: {}", e);
+                        continue;
+                    }
+                };
+
+                // Reading the file ourselves produces access events, ignore them
+                let is_write = matches!(
+                    event.kind,
+                    EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+                );
+                if !is_write || !event.paths.iter().any(|p| is_config_file(p)) {
+                    continue;
+                }
+
+                while rx.recv_timeout(RELOAD_DEBOUNCE).is_ok() {}
+                debug!("Config file changed, reloading");
+                manager.reload();
+            }
+        });
+
+        *self.watcher.lock().unwrap() = Some(watcher);
+        debug!("Watching {:?} for config file changes", self.config_dir);
+        Ok(())
+    }
+
+    /// Applies the config file, if any. Errors are logged and reported to the
+    /// frontend, and leave the current settings untouched.
+    pub fn reload(&self) {
+        let path = CONFIG_FILE_NAMES
+            .iter()
+            .map(|name| self.config_dir.join(name))
+            .find(|path| path.exists());
+
+        let Some(path) = path else {
+            *self.status.lock().unwrap() = ConfigFileStatus::default();
+            return;
+        };
+
+        let result = self.apply_file(&path);
+        let status = ConfigFileStatus {
+            path: Some(path.to_string_lossy().to_string()),
+            error: result.err(),
+        };
+
+        match &status.error {
+            Some(e) => {
+                error!("Failed to apply config file {:?}: {}", path, e);
+                let _ = self.app_handle.emit("config-file-error", status.clone());
+            }
+            None => info!("Applied config file {:?}", path),
+        }
+
+        *self.status.lock().unwrap() = status;
+    }
+
+    fn apply_file(&self, path: &Path) -> Result<(), String> {
+        let contents =
+            fs::read_to_string(path).map_err(|e| format!("Failed to read file: {}", e))?;
+        let mut overrides = parse_config(path, &contents)?;
+        let overrides_map = overrides
+            .as_object_mut()
+            .ok_or_else(|| "The config file must contain a table of settings".to_string())?;
+
+        // The version belongs to the store, and API keys go to the secret store
+        overrides_map.remove("settings_version");
+        let api_keys = overrides_map.remove("post_process_api_keys");
+
+        let current = get_settings(&self.app_handle);
+        let mut merged = serde_json::to_value(&current).map_err(|e| e.to_string())?;
+
+        for key in overrides_map.keys() {
+            if merged.get(key).is_none() {
+                return Err(format!("Unknown setting '{}'", key));
+            }
+        }
+
+        merge_values(&mut merged, overrides);
+        let settings: AppSettings =
+            serde_json::from_value(merged).map_err(|e| format!("Invalid settings: {}", e))?;
+        validate_settings(&settings)?;
+
+        if let Some(api_keys) = api_keys {
+            self.store_api_keys(api_keys)?;
+        }
+
+        let unchanged = serde_json::to_value(&settings).ok() == serde_json::to_value(&current).ok();
+        if unchanged {
+            return Ok(());
+        }
+
+        shortcut::apply_settings(&self.app_handle, &current, settings)
+    }
+
+    fn store_api_keys(&self, api_keys: Value) -> Result<(), String> {
+        let api_keys: std::collections::HashMap<String, String> = serde_json::from_value(api_keys)
+            .map_err(|e| format!("Invalid post_process_api_keys: {}", e))?;
+
+        let secrets = self.app_handle.state::<Arc<SecretsManager>>();
+        for (provider_id, api_key) in api_keys {
+            if api_key.is_empty() || secrets.get_api_key(&provider_id) == api_key {
+                continue;
+            }
+            secrets
+                .set_api_key(&provider_id, &api_key)
+                .map_err(|e| format!("Failed to store API key for '{}': {}", provider_id, e))?;
+        }
+
+        Ok(())
+    }
+}
+
+fn is_config_file(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| CONFIG_FILE_NAMES.contains(&name))
+}
+
+fn parse_config(path: &Path, contents: &str) -> Result<Value, String> {
+    let is_toml = path.extension().and_then(|ext| ext.to_str()) == Some("toml");
+    if is_toml {
+        let table: toml::Table =
+            toml::from_str(contents).map_err(|e| format!("Invalid TOML: {}", e))?;
+        serde_json::to_value(table).map_err(|e| format!("Invalid TOML: {}", e))
+    } else {
+        serde_json::from_str(contents).map_err(|e| format!("Invalid JSON: {}", e))
+    }
+}
+
+/// Recursively merges `overlay` into `base`. Objects are merged key by key so
+/// that e.g. a single binding can be overridden, anything else is replaced.
+fn merge_values(base: &mut Value, overlay: Value) {
+    match (base, overlay) {
+        (Value::Object(base), Value::Object(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(&key) {
+                    Some(existing) => merge_values(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::settings::get_default_settings;
+
+    #[test]
+    fn test_toml_overrides_merge_over_settings() {
+        let overrides = parse_config(
+            Path::new("aleflow.toml"),
+            r#"
+                push_to_talk = false
+                custom_words = ["AleFlow", "Tauri"]
+
+                [bindings.transcribe]
+                current_binding = "ctrl+alt+space"
+            "#,
+        )
+        .unwrap();
+
+        let mut merged = serde_json::to_value(get_default_settings()).unwrap();
+        merge_values(&mut merged, overrides);
+        let settings: AppSettings = serde_json::from_value(merged).unwrap();
+
+        assert!(!settings.push_to_talk);
+        assert_eq!(settings.custom_words, vec!["AleFlow", "Tauri"]);
+        assert_eq!(
+            settings.bindings["transcribe"].current_binding,
+            "ctrl+alt+space"
+        );
+        // Untouched fields of the binding keep their stored values
+        assert_eq!(settings.bindings["transcribe"].id, "transcribe");
+        assert!(settings.bindings.contains_key("cancel"));
+    }
+}
@@ -5,11 +5,14 @@ use rusqlite::{params, Connection, OptionalExtension};
 use rusqlite_migration::{Migrations, M};
 use serde::{Deserialize, Serialize};
 use specta::Type;
+use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
+use std::sync::Mutex;
 use tauri::{AppHandle, Emitter, Manager};
 
 use crate::audio_toolkit::save_wav_file;
+use crate::transcript::{format_plain, format_transcript, TranscriptFormat, TranscriptSegment};
 
 /// Database migrations for transcription history.
 /// Each migration is applied in order. The library tracks which migrations
@@ -31,6 +34,41 @@ static MIGRATIONS: &[M] = &[
     ),
     M::up("ALTER TABLE transcription_history ADD COLUMN post_processed_text TEXT;"),
     M::up("ALTER TABLE transcription_history ADD COLUMN post_process_prompt TEXT;"),
+    M::up(
+        "CREATE TABLE IF NOT EXISTS transcription_metrics (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            timestamp INTEGER NOT NULL,
+            model_id TEXT NOT NULL,
+            audio_duration_ms INTEGER NOT NULL,
+            speech_duration_ms INTEGER NOT NULL,
+            load_ms INTEGER NOT NULL,
+            inference_ms INTEGER NOT NULL,
+            post_process_ms INTEGER,
+            paste_ms INTEGER,
+            real_time_factor REAL NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS idx_transcription_metrics_timestamp
+            ON transcription_metrics (timestamp);",
+    ),
+    M::up(
+        "CREATE TABLE IF NOT EXISTS transcription_segments (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            history_id INTEGER NOT NULL,
+            start_ms INTEGER NOT NULL,
+            end_ms INTEGER NOT NULL,
+            speaker INTEGER,
+            text TEXT NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS idx_transcription_segments_history_id
+            ON transcription_segments (history_id);
+        CREATE TABLE IF NOT EXISTS speaker_names (
+            history_id INTEGER NOT NULL,
+            speaker INTEGER NOT NULL,
+            name TEXT NOT NULL,
+            PRIMARY KEY (history_id, speaker)
+        );",
+    ),
+    M::up("ALTER TABLE transcription_history ADD COLUMN discarded BOOLEAN NOT NULL DEFAULT 0;"),
 ];
 
 #[derive(Clone, Debug, Serialize, Deserialize, Type)]
@@ -39,10 +77,102 @@ pub struct HistoryEntry {
     pub file_name: String,
     pub timestamp: i64,
     pub saved: bool,
+    // Set when the dictation was undone after pasting
+    pub discarded: bool,
     pub title: String,
     pub transcription_text: String,
     pub post_processed_text: Option<String>,
     pub post_process_prompt: Option<String>,
+    // Speakers found by diarization, empty for entries without it
+    pub speakers: Vec<SpeakerLabel>,
+}
+
+impl HistoryEntry {
+    /// The text that was pasted: the post-processed text when there is one.
+    pub fn pasted_text(self) -> String {
+        self.post_processed_text.unwrap_or(self.transcription_text)
+    }
+}
+
+/// Recordings of meetings are named with this prefix, dictations aren't.
+pub const MEETING_FILE_PREFIX: &str = "aleflow-meeting-";
+
+/// Ties a pasted dictation to its history entry, which may still be being
+/// saved when the dictation is undone.
+#[derive(Debug, Default)]
+pub struct EntryLink(Mutex<EntryLinkState>);
+
+#[derive(Debug, Default)]
+enum EntryLinkState {
+    #[default]
+    Saving,
+    Saved(i64),
+    /// Undone before the entry was saved.
+    Discarded,
+}
+
+impl EntryLink {
+    /// Records the saved entry. Returns `true` if the dictation was undone in
+    /// the meantime, so the entry should be discarded right away.
+    pub fn set_saved(&self, id: i64) -> bool {
+        let mut state = self.0.lock().unwrap();
+        let discarded = matches!(*state, EntryLinkState::Discarded);
+        *state = EntryLinkState::Saved(id);
+        discarded
+    }
+
+    /// The entry to discard, or `None` while it's still being saved, in which
+    /// case `set_saved` reports it instead.
+    pub fn discard(&self) -> Option<i64> {
+        let mut state = self.0.lock().unwrap();
+        match *state {
+            EntryLinkState::Saved(id) => Some(id),
+            _ => {
+                *state = EntryLinkState::Discarded;
+                None
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Type)]
+pub struct SpeakerLabel {
+    pub speaker: u32,
+    // `None` until the user renames the speaker
+    pub name: Option<String>,
+}
+
+/// Timings for a single dictation. Durations are in milliseconds.
+#[derive(Clone, Debug, Default)]
+pub struct DictationMetrics {
+    pub model_id: String,
+    /// Length of the recording as captured.
+    pub audio_duration_ms: u64,
+    /// Length of the audio left after VAD trimming.
+    pub speech_duration_ms: u64,
+    pub load_ms: u64,
+    pub inference_ms: u64,
+    /// `None` when no post-processing or conversion ran.
+    pub post_process_ms: Option<u64>,
+    /// `None` when nothing was pasted.
+    pub paste_ms: Option<u64>,
+    /// Inference time divided by the duration of the audio given to the model.
+    pub real_time_factor: f64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Type)]
+pub struct MetricsSummary {
+    pub model_id: String,
+    // Local date in `YYYY-MM-DD` format
+    pub day: String,
+    pub dictation_count: u32,
+    pub avg_audio_duration_ms: f64,
+    pub avg_speech_duration_ms: f64,
+    pub avg_load_ms: f64,
+    pub avg_inference_ms: f64,
+    pub avg_post_process_ms: Option<f64>,
+    pub avg_paste_ms: Option<f64>,
+    pub avg_real_time_factor: f64,
 }
 
 pub struct HistoryManager {
@@ -183,7 +313,7 @@ impl HistoryManager {
         transcription_text: String,
         post_processed_text: Option<String>,
         post_process_prompt: Option<String>,
-    ) -> Result<()> {
+    ) -> Result<i64> {
         let timestamp = Utc::now().timestamp();
         let file_name = format!("aleflow-{}.wav", timestamp);
         let title = self.format_timestamp_title(timestamp);
@@ -193,7 +323,7 @@ impl HistoryManager {
         save_wav_file(file_path, &audio_samples).await?;
 
         // Save to database
-        self.save_to_database(
+        let id = self.save_to_database(
             file_name,
             timestamp,
             title,
@@ -210,6 +340,38 @@ impl HistoryManager {
             error!("Failed to emit history-updated event: {}", e);
         }
 
+        Ok(id)
+    }
+
+    /// Save a meeting transcript whose audio has already been written to the
+    /// recordings directory. The entry is dated when the meeting started.
+    pub fn save_meeting(
+        &self,
+        file_name: String,
+        started_at: i64,
+        segments: &[TranscriptSegment],
+    ) -> Result<()> {
+        let title = self.format_timestamp_title(started_at);
+        let transcription_text = format_plain(segments, &HashMap::new());
+        let id =
+            self.save_to_database(file_name, started_at, title, transcription_text, None, None)?;
+
+        let mut conn = self.get_connection()?;
+        let tx = conn.transaction()?;
+        for segment in segments {
+            tx.execute(
+                "INSERT INTO transcription_segments (history_id, start_ms, end_ms, speaker, text) VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![id, segment.start_ms, segment.end_ms, segment.speaker, segment.text],
+            )?;
+        }
+        tx.commit()?;
+
+        self.cleanup_old_entries()?;
+
+        if let Err(e) = self.app_handle.emit("history-updated", ()) {
+            error!("Failed to emit history-updated event: {}", e);
+        }
+
         Ok(())
     }
 
@@ -221,7 +383,7 @@ impl HistoryManager {
         transcription_text: String,
         post_processed_text: Option<String>,
         post_process_prompt: Option<String>,
-    ) -> Result<()> {
+    ) -> Result<i64> {
         let conn = self.get_connection()?;
         conn.execute(
             "INSERT INTO transcription_history (file_name, timestamp, saved, title, transcription_text, post_processed_text, post_process_prompt) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
@@ -229,7 +391,7 @@ impl HistoryManager {
         )?;
 
         debug!("Saved transcription to database");
-        Ok(())
+        Ok(conn.last_insert_rowid())
     }
 
     pub fn cleanup_old_entries(&self) -> Result<()> {
@@ -266,6 +428,7 @@ impl HistoryManager {
                 "DELETE FROM transcription_history WHERE id = ?1",
                 params![id],
             )?;
+            Self::delete_segments(&conn, *id)?;
 
             // Delete WAV file
             let file_path = self.recordings_dir.join(file_name);
@@ -279,6 +442,10 @@ impl HistoryManager {
             }
         }
 
+        if let Err(e) = self.app_handle.emit("history-updated", ()) {
+            error!("Failed to emit history-updated event: {}", e);
+        }
+
         Ok(deleted_count)
     }
 
@@ -355,7 +522,7 @@ impl HistoryManager {
     pub async fn get_history_entries(&self) -> Result<Vec<HistoryEntry>> {
         let conn = self.get_connection()?;
         let mut stmt = conn.prepare(
-            "SELECT id, file_name, timestamp, saved, title, transcription_text, post_processed_text, post_process_prompt FROM transcription_history ORDER BY timestamp DESC"
+            "SELECT id, file_name, timestamp, saved, discarded, title, transcription_text, post_processed_text, post_process_prompt FROM transcription_history ORDER BY timestamp DESC"
         )?;
 
         let rows = stmt.query_map([], |row| {
@@ -364,21 +531,63 @@ impl HistoryManager {
                 file_name: row.get("file_name")?,
                 timestamp: row.get("timestamp")?,
                 saved: row.get("saved")?,
+                discarded: row.get("discarded")?,
                 title: row.get("title")?,
                 transcription_text: row.get("transcription_text")?,
                 post_processed_text: row.get("post_processed_text")?,
                 post_process_prompt: row.get("post_process_prompt")?,
+                speakers: Vec::new(),
             })
         })?;
 
+        let mut speakers = Self::load_speakers(&conn, None)?;
         let mut entries = Vec::new();
         for row in rows {
-            entries.push(row?);
+            let mut entry = row?;
+            entry.speakers = speakers.remove(&entry.id).unwrap_or_default();
+            entries.push(entry);
         }
 
         Ok(entries)
     }
 
+    /// The newest dictation that wasn't undone. Meeting transcripts are left
+    /// out.
+    pub async fn get_latest_entry(&self) -> Result<Option<HistoryEntry>> {
+        match latest_dictation_id(&self.get_connection()?)? {
+            Some(id) => self.get_entry_by_id(id).await,
+            None => Ok(None),
+        }
+    }
+
+    /// The newest dictations that weren't undone, without speakers. Not async
+    /// so the tray menu can list them while it's being built.
+    pub fn recent_entries(&self, limit: usize) -> Result<Vec<HistoryEntry>> {
+        let conn = self.get_connection()?;
+        let mut stmt = conn.prepare(
+            "SELECT id, file_name, timestamp, saved, discarded, title, transcription_text, post_processed_text, post_process_prompt FROM transcription_history WHERE discarded = 0 ORDER BY timestamp DESC, id DESC LIMIT ?1"
+        )?;
+
+        let entries = stmt
+            .query_map([limit as i64], |row| {
+                Ok(HistoryEntry {
+                    id: row.get("id")?,
+                    file_name: row.get("file_name")?,
+                    timestamp: row.get("timestamp")?,
+                    saved: row.get("saved")?,
+                    discarded: row.get("discarded")?,
+                    title: row.get("title")?,
+                    transcription_text: row.get("transcription_text")?,
+                    post_processed_text: row.get("post_processed_text")?,
+                    post_process_prompt: row.get("post_process_prompt")?,
+                    speakers: Vec::new(),
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        Ok(entries)
+    }
+
     pub async fn toggle_saved_status(&self, id: i64) -> Result<()> {
         let conn = self.get_connection()?;
 
@@ -406,6 +615,41 @@ impl HistoryManager {
         Ok(())
     }
 
+    /// Marks an entry as discarded after its dictation was undone.
+    pub async fn discard_entry(&self, id: i64) -> Result<()> {
+        let conn = self.get_connection()?;
+        let updated = conn.execute(
+            "UPDATE transcription_history SET discarded = 1 WHERE id = ?1",
+            params![id],
+        )?;
+
+        if updated == 0 {
+            debug!("History entry {} of the discarded dictation is gone", id);
+            return Ok(());
+        }
+
+        if let Err(e) = self.app_handle.emit("history-updated", ()) {
+            error!("Failed to emit history-updated event: {}", e);
+        }
+
+        Ok(())
+    }
+
+    pub fn save_metrics(&self, metrics: &DictationMetrics) -> Result<()> {
+        insert_metrics(&self.get_connection()?, Utc::now().timestamp(), metrics)?;
+        debug!("Saved dictation metrics to database");
+        Ok(())
+    }
+
+    /// Averages dictation metrics per model and per local day, newest first.
+    /// `days` limits the result to the most recent days.
+    pub async fn get_metrics_summary(&self, days: Option<u32>) -> Result<Vec<MetricsSummary>> {
+        let since = days
+            .map(|days| Utc::now().timestamp() - i64::from(days) * 24 * 60 * 60)
+            .unwrap_or(0);
+        metrics_summary(&self.get_connection()?, since)
+    }
+
     pub fn get_audio_file_path(&self, file_name: &str) -> PathBuf {
         self.recordings_dir.join(file_name)
     }
@@ -413,7 +657,7 @@ impl HistoryManager {
     pub async fn get_entry_by_id(&self, id: i64) -> Result<Option<HistoryEntry>> {
         let conn = self.get_connection()?;
         let mut stmt = conn.prepare(
-            "SELECT id, file_name, timestamp, saved, title, transcription_text, post_processed_text, post_process_prompt
+            "SELECT id, file_name, timestamp, saved, discarded, title, transcription_text, post_processed_text, post_process_prompt
              FROM transcription_history WHERE id = ?1",
         )?;
 
@@ -424,15 +668,23 @@ impl HistoryManager {
                     file_name: row.get("file_name")?,
                     timestamp: row.get("timestamp")?,
                     saved: row.get("saved")?,
+                    discarded: row.get("discarded")?,
                     title: row.get("title")?,
                     transcription_text: row.get("transcription_text")?,
                     post_processed_text: row.get("post_processed_text")?,
                     post_process_prompt: row.get("post_process_prompt")?,
+                    speakers: Vec::new(),
                 })
             })
             .optional()?;
 
-        Ok(entry)
+        let Some(mut entry) = entry else {
+            return Ok(None);
+        };
+        entry.speakers = Self::load_speakers(&conn, Some(id))?
+            .remove(&id)
+            .unwrap_or_default();
+        Ok(Some(entry))
     }
 
     pub async fn delete_entry(&self, id: i64) -> Result<()> {
@@ -455,6 +707,7 @@ impl HistoryManager {
             "DELETE FROM transcription_history WHERE id = ?1",
             params![id],
         )?;
+        Self::delete_segments(&conn, id)?;
 
         debug!("Deleted history entry with id: {}", id);
 
@@ -466,6 +719,150 @@ impl HistoryManager {
         Ok(())
     }
 
+    /// Names a speaker of a diarized entry and relabels its transcript. An
+    /// empty name goes back to the default label.
+    pub async fn rename_speaker(&self, id: i64, speaker: u32, name: String) -> Result<()> {
+        let mut conn = self.get_connection()?;
+        let tx = conn.transaction()?;
+
+        let segments = Self::load_segments(&tx, id)?;
+        if !segments
+            .iter()
+            .any(|segment| segment.speaker == Some(speaker))
+        {
+            anyhow::bail!("Entry {} has no speaker {}", id, speaker);
+        }
+
+        let name = name.trim();
+        if name.is_empty() {
+            tx.execute(
+                "DELETE FROM speaker_names WHERE history_id = ?1 AND speaker = ?2",
+                params![id, speaker],
+            )?;
+        } else {
+            tx.execute(
+                "INSERT OR REPLACE INTO speaker_names (history_id, speaker, name) VALUES (?1, ?2, ?3)",
+                params![id, speaker, name],
+            )?;
+        }
+
+        let names = Self::load_speaker_names(&tx, id)?;
+        tx.execute(
+            "UPDATE transcription_history SET transcription_text = ?1 WHERE id = ?2",
+            params![format_plain(&segments, &names), id],
+        )?;
+        tx.commit()?;
+
+        debug!("Renamed speaker {} of entry {}", speaker, id);
+
+        if let Err(e) = self.app_handle.emit("history-updated", ()) {
+            error!("Failed to emit history-updated event: {}", e);
+        }
+
+        Ok(())
+    }
+
+    /// Writes the transcript of an entry next to its recording and returns
+    /// the path. Entries without timed segments become a single cue spanning
+    /// the whole recording.
+    pub async fn export_entry(&self, id: i64, format: TranscriptFormat) -> Result<PathBuf> {
+        let entry = self
+            .get_entry_by_id(id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("History entry {} not found", id))?;
+
+        let conn = self.get_connection()?;
+        let mut segments = Self::load_segments(&conn, id)?;
+        let names = Self::load_speaker_names(&conn, id)?;
+
+        let audio_path = self.get_audio_file_path(&entry.file_name);
+        if segments.is_empty() {
+            let end_ms = hound::WavReader::open(&audio_path)
+                .map(|reader| reader.duration() as u64 * 1000 / reader.spec().sample_rate as u64)
+                .unwrap_or(0);
+            segments.push(TranscriptSegment {
+                start_ms: 0,
+                end_ms,
+                speaker: None,
+                text: entry
+                    .post_processed_text
+                    .unwrap_or(entry.transcription_text),
+            });
+        }
+
+        let path = audio_path.with_extension(format.extension());
+        fs::write(
+            &path,
+            format_transcript(&segments, &names, format, &entry.title),
+        )?;
+        info!("Exported history entry {} to {:?}", id, path);
+        Ok(path)
+    }
+
+    fn load_segments(conn: &Connection, id: i64) -> Result<Vec<TranscriptSegment>> {
+        let mut stmt = conn.prepare(
+            "SELECT start_ms, end_ms, speaker, text FROM transcription_segments WHERE history_id = ?1 ORDER BY start_ms, id",
+        )?;
+        let rows = stmt.query_map([id], |row| {
+            Ok(TranscriptSegment {
+                start_ms: row.get("start_ms")?,
+                end_ms: row.get("end_ms")?,
+                speaker: row.get("speaker")?,
+                text: row.get("text")?,
+            })
+        })?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    fn load_speaker_names(conn: &Connection, id: i64) -> Result<HashMap<u32, String>> {
+        let mut stmt =
+            conn.prepare("SELECT speaker, name FROM speaker_names WHERE history_id = ?1")?;
+        let rows = stmt.query_map([id], |row| Ok((row.get("speaker")?, row.get("name")?)))?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    /// Speakers of one entry, or of all entries when `id` is `None`.
+    fn load_speakers(
+        conn: &Connection,
+        id: Option<i64>,
+    ) -> Result<HashMap<i64, Vec<SpeakerLabel>>> {
+        let mut stmt = conn.prepare(
+            "SELECT DISTINCT s.history_id AS history_id, s.speaker AS speaker, n.name AS name
+             FROM transcription_segments s
+             LEFT JOIN speaker_names n ON n.history_id = s.history_id AND n.speaker = s.speaker
+             WHERE s.speaker IS NOT NULL AND (?1 IS NULL OR s.history_id = ?1)
+             ORDER BY s.history_id, s.speaker",
+        )?;
+        let rows = stmt.query_map([id], |row| {
+            Ok((
+                row.get::<_, i64>("history_id")?,
+                SpeakerLabel {
+                    speaker: row.get("speaker")?,
+                    name: row.get("name")?,
+                },
+            ))
+        })?;
+
+        let mut speakers: HashMap<i64, Vec<SpeakerLabel>> = HashMap::new();
+        for row in rows {
+            let (history_id, label) = row?;
+            speakers.entry(history_id).or_default().push(label);
+        }
+        Ok(speakers)
+    }
+
+    fn delete_segments(conn: &Connection, id: i64) -> Result<()> {
+        conn.execute(
+            "DELETE FROM transcription_segments WHERE history_id = ?1",
+            params![id],
+        )?;
+        conn.execute(
+            "DELETE FROM speaker_names WHERE history_id = ?1",
+            params![id],
+        )?;
+        Ok(())
+    }
+
     fn format_timestamp_title(&self, timestamp: i64) -> String {
         if let Some(utc_datetime) = DateTime::from_timestamp(timestamp, 0) {
             // Convert UTC to local timezone
@@ -476,3 +873,191 @@ impl HistoryManager {
         }
     }
 }
+
+fn latest_dictation_id(conn: &Connection) -> Result<Option<i64>> {
+    Ok(conn
+        .query_row(
+            "SELECT id FROM transcription_history
+             WHERE discarded = 0 AND file_name NOT LIKE ?1 || '%'
+             ORDER BY timestamp DESC, id DESC LIMIT 1",
+            params![MEETING_FILE_PREFIX],
+            |row| row.get("id"),
+        )
+        .optional()?)
+}
+
+fn insert_metrics(conn: &Connection, timestamp: i64, metrics: &DictationMetrics) -> Result<()> {
+    conn.execute(
+        "INSERT INTO transcription_metrics (timestamp, model_id, audio_duration_ms, speech_duration_ms, load_ms, inference_ms, post_process_ms, paste_ms, real_time_factor) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+        params![
+            timestamp,
+            metrics.model_id,
+            metrics.audio_duration_ms,
+            metrics.speech_duration_ms,
+            metrics.load_ms,
+            metrics.inference_ms,
+            metrics.post_process_ms,
+            metrics.paste_ms,
+            metrics.real_time_factor,
+        ],
+    )?;
+    Ok(())
+}
+
+/// Metrics recorded at or after `since` (a Unix timestamp), averaged per model
+/// and per local day.
+fn metrics_summary(conn: &Connection, since: i64) -> Result<Vec<MetricsSummary>> {
+    let mut stmt = conn.prepare(
+        "SELECT model_id,
+                date(timestamp, 'unixepoch', 'localtime') AS day,
+                COUNT(*) AS dictation_count,
+                AVG(audio_duration_ms) AS avg_audio_duration_ms,
+                AVG(speech_duration_ms) AS avg_speech_duration_ms,
+                AVG(load_ms) AS avg_load_ms,
+                AVG(inference_ms) AS avg_inference_ms,
+                AVG(post_process_ms) AS avg_post_process_ms,
+                AVG(paste_ms) AS avg_paste_ms,
+                AVG(real_time_factor) AS avg_real_time_factor
+         FROM transcription_metrics
+         WHERE timestamp >= ?1
+         GROUP BY model_id, day
+         ORDER BY day DESC, model_id",
+    )?;
+
+    let rows = stmt.query_map(params![since], |row| {
+        Ok(MetricsSummary {
+            model_id: row.get("model_id")?,
+            day: row.get("day")?,
+            dictation_count: row.get("dictation_count")?,
+            avg_audio_duration_ms: row.get("avg_audio_duration_ms")?,
+            avg_speech_duration_ms: row.get("avg_speech_duration_ms")?,
+            avg_load_ms: row.get("avg_load_ms")?,
+            avg_inference_ms: row.get("avg_inference_ms")?,
+            avg_post_process_ms: row.get("avg_post_process_ms")?,
+            avg_paste_ms: row.get("avg_paste_ms")?,
+            avg_real_time_factor: row.get("avg_real_time_factor")?,
+        })
+    })?;
+
+    let mut summaries = Vec::new();
+    for row in rows {
+        summaries.push(row?);
+    }
+
+    Ok(summaries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn latest_dictation_skips_undone_dictations_and_meetings() {
+        let conn = test_connection();
+        let insert = |file_name: &str, timestamp: i64, discarded: bool| {
+            conn.execute(
+                "INSERT INTO transcription_history (file_name, timestamp, saved, discarded, title, transcription_text) VALUES (?1, ?2, 0, ?3, '', 'text')",
+                params![file_name, timestamp, discarded],
+            )
+            .unwrap();
+            conn.last_insert_rowid()
+        };
+        assert_eq!(latest_dictation_id(&conn).unwrap(), None);
+
+        let kept = insert("aleflow-100.wav", 100, false);
+        insert("aleflow-200.wav", 200, true);
+        insert("aleflow-meeting-300.wav", 300, false);
+        assert_eq!(latest_dictation_id(&conn).unwrap(), Some(kept));
+    }
+
+    #[test]
+    fn entry_link_discards_once_saved() {
+        let link = EntryLink::default();
+        // Undone while the entry is still being saved
+        assert_eq!(link.discard(), None);
+        assert!(link.set_saved(7));
+
+        let link = EntryLink::default();
+        assert!(!link.set_saved(7));
+        assert_eq!(link.discard(), Some(7));
+    }
+
+    fn test_connection() -> Connection {
+        let mut conn = Connection::open_in_memory().unwrap();
+        Migrations::new(MIGRATIONS.to_vec())
+            .to_latest(&mut conn)
+            .unwrap();
+        conn
+    }
+
+    fn metrics(model_id: &str, inference_ms: u64, paste_ms: Option<u64>) -> DictationMetrics {
+        DictationMetrics {
+            model_id: model_id.to_string(),
+            audio_duration_ms: 4_000,
+            speech_duration_ms: 3_000,
+            load_ms: 0,
+            inference_ms,
+            post_process_ms: None,
+            paste_ms,
+            real_time_factor: inference_ms as f64 / 3_000.0,
+        }
+    }
+
+    #[test]
+    fn metrics_are_averaged_per_model_and_day() {
+        let conn = test_connection();
+        let noon = Local
+            .with_ymd_and_hms(2026, 3, 10, 12, 0, 0)
+            .unwrap()
+            .timestamp();
+        insert_metrics(&conn, noon, &metrics("small", 300, Some(20))).unwrap();
+        insert_metrics(&conn, noon + 60, &metrics("small", 600, None)).unwrap();
+        insert_metrics(&conn, noon + 120, &metrics("turbo", 900, Some(40))).unwrap();
+        insert_metrics(
+            &conn,
+            noon - 3 * 24 * 60 * 60,
+            &metrics("small", 1_200, None),
+        )
+        .unwrap();
+
+        let summary = metrics_summary(&conn, 0).unwrap();
+        let days: Vec<_> = summary
+            .iter()
+            .map(|s| (s.day.as_str(), s.model_id.as_str(), s.dictation_count))
+            .collect();
+        assert_eq!(
+            days,
+            [
+                ("2026-03-10", "small", 2),
+                ("2026-03-10", "turbo", 1),
+                ("2026-03-07", "small", 1),
+            ]
+        );
+        assert_eq!(summary[0].avg_inference_ms, 450.0);
+        // Dictations that weren't pasted are left out of the paste average
+        assert_eq!(summary[0].avg_paste_ms, Some(20.0));
+        assert_eq!(summary[2].avg_paste_ms, None);
+    }
+
+    #[test]
+    fn metrics_summary_skips_older_dictations() {
+        let conn = test_connection();
+        let noon = Local
+            .with_ymd_and_hms(2026, 3, 10, 12, 0, 0)
+            .unwrap()
+            .timestamp();
+        insert_metrics(&conn, noon, &metrics("small", 300, None)).unwrap();
+        insert_metrics(
+            &conn,
+            noon - 3 * 24 * 60 * 60,
+            &metrics("small", 1_200, None),
+        )
+        .unwrap();
+
+        let summary = metrics_summary(&conn, noon - 24 * 60 * 60).unwrap();
+        assert_eq!(summary.len(), 1);
+        assert_eq!(summary[0].day, "2026-03-10");
+        assert_eq!(summary[0].avg_inference_ms, 300.0);
+    }
+}
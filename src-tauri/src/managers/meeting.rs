@@ -0,0 +1,721 @@
+use crate::audio_toolkit::constants::WHISPER_SAMPLE_RATE;
+use crate::audio_toolkit::{
+    cluster_speakers, concat_wav_files, read_wav_file, write_wav_file, AudioChunk, ChunkingConfig,
+    SpeakerEmbedder,
+};
+use crate::managers::audio::AudioRecordingManager;
+use crate::managers::history::{HistoryManager, MEETING_FILE_PREFIX};
+use crate::managers::transcription::TranscriptionManager;
+use crate::settings::get_settings;
+use crate::transcript::{format_offset, TranscriptSegment};
+use crate::tray::{change_tray_icon, TrayIconState};
+use anyhow::Result;
+use chrono::Utc;
+use log::{debug, error, info, warn};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// Binding id of the meeting shortcut, also used for meeting recordings.
+pub const MEETING_BINDING_ID: &str = "meeting";
+
+/// Pause in speech at which the recording is cut into a new chunk.
+const CHUNK_SILENCE: Duration = Duration::from_millis(800);
+
+/// Longest chunk given to the model, reached only by speech without pauses.
+const CHUNK_MAX_DURATION: Duration = Duration::from_secs(30);
+
+const MANIFEST_FILE: &str = "session.json";
+
+/// Times a chunk is given to the model before it's left out of the transcript.
+const MAX_CHUNK_ATTEMPTS: u32 = 3;
+
+/// Wait before the first retry of a chunk, doubled for every retry after it.
+const CHUNK_RETRY_BACKOFF: Duration = Duration::from_secs(5);
+
+/// Stands in the transcript for a chunk that could not be transcribed.
+const FAILED_CHUNK_TEXT: &str = "[untranscribed audio]";
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct ChunkRecord {
+    file_name: String,
+    start_ms: u64,
+    duration_ms: u64,
+    /// `None` until the chunk has been transcribed.
+    text: Option<String>,
+    /// Timed pieces of `text`, when the engine reports them.
+    #[serde(default)]
+    segments: Vec<SegmentRecord>,
+    /// Failed transcription attempts so far.
+    #[serde(default)]
+    attempts: u32,
+    /// Set once every attempt has failed. The chunk is marked in the
+    /// transcript instead of holding the meeting back.
+    #[serde(default)]
+    failed: bool,
+}
+
+impl ChunkRecord {
+    /// Still waiting for a transcription or a retry.
+    fn is_pending(&self) -> bool {
+        self.text.is_none() && !self.failed
+    }
+}
+
+/// Times are offsets into the meeting, like the chunk's.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct SegmentRecord {
+    start_ms: u64,
+    end_ms: u64,
+    text: String,
+}
+
+/// Written next to the chunks of a session, so a meeting interrupted by a
+/// crash can still be transcribed on the next launch.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct SessionManifest {
+    started_at: i64,
+    chunks: Vec<ChunkRecord>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Type)]
+pub struct MeetingStatus {
+    pub recording: bool,
+    pub started_at: Option<i64>,
+    pub recorded_chunks: u32,
+    // Chunks of all sessions, including finished recordings, that still need
+    // to be transcribed
+    pub pending_chunks: u32,
+}
+
+enum Job {
+    Transcribe { session: String, index: usize },
+    Finish { session: String },
+}
+
+/// Records meetings as a series of chunks on disk, transcribes them in the
+/// background while recording goes on, and saves one timestamped transcript
+/// to history when the meeting ends.
+#[derive(Clone)]
+pub struct MeetingManager {
+    app_handle: AppHandle,
+    meetings_dir: PathBuf,
+    sessions: Arc<Mutex<HashMap<String, SessionManifest>>>,
+    /// Session currently being recorded.
+    active: Arc<Mutex<Option<String>>>,
+    jobs: mpsc::Sender<Job>,
+}
+
+impl MeetingManager {
+    /// Needs the transcription and history managers to be managed already,
+    /// since sessions left over from a previous run are resumed right away.
+    pub fn new(app_handle: &AppHandle) -> Result<Self> {
+        let meetings_dir = app_handle.path().app_data_dir()?.join("meetings");
+        fs::create_dir_all(&meetings_dir)?;
+
+        let (jobs, job_rx) = mpsc::channel();
+        let manager = Self {
+            app_handle: app_handle.clone(),
+            meetings_dir,
+            sessions: Arc::new(Mutex::new(HashMap::new())),
+            active: Arc::new(Mutex::new(None)),
+            jobs,
+        };
+
+        manager.resume_sessions();
+
+        let worker = manager.clone();
+        thread::spawn(move || worker.run_jobs(job_rx));
+
+        Ok(manager)
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.active.lock().unwrap().is_some()
+    }
+
+    pub fn status(&self) -> MeetingStatus {
+        let active = self.active.lock().unwrap().clone();
+        let sessions = self.sessions.lock().unwrap();
+        let current = active.as_ref().and_then(|session| sessions.get(session));
+
+        MeetingStatus {
+            recording: current.is_some(),
+            started_at: current.map(|m| m.started_at),
+            recorded_chunks: current.map_or(0, |m| m.chunks.len() as u32),
+            pending_chunks: sessions
+                .values()
+                .flat_map(|m| &m.chunks)
+                .filter(|chunk| chunk.is_pending())
+                .count() as u32,
+        }
+    }
+
+    pub fn start(&self) -> Result<()> {
+        let rm = self.app_handle.state::<Arc<AudioRecordingManager>>();
+        let started_at = Utc::now().timestamp();
+        let session = format!("meeting-{}", Utc::now().timestamp_millis());
+
+        {
+            let mut active = self.active.lock().unwrap();
+            if active.is_some() {
+                anyhow::bail!("A meeting is already being recorded");
+            }
+            if rm.is_recording() {
+                anyhow::bail!("Cannot start a meeting while another recording is active");
+            }
+
+            let manifest = SessionManifest {
+                started_at,
+                chunks: Vec::new(),
+            };
+            fs::create_dir_all(self.meetings_dir.join(&session))?;
+            self.save_manifest(&session, &manifest)?;
+            self.sessions
+                .lock()
+                .unwrap()
+                .insert(session.clone(), manifest);
+
+            // Chunks can arrive as soon as recording starts
+            *active = Some(session.clone());
+        }
+
+        let started = rm
+            .set_chunking(Some(ChunkingConfig {
+                silence: CHUNK_SILENCE,
+                max_duration: CHUNK_MAX_DURATION,
+            }))
+            .map(|()| rm.try_start_recording(MEETING_BINDING_ID));
+        if !matches!(started, Ok(true)) {
+            if let Err(e) = rm.set_chunking(None) {
+                warn!("Failed to turn off chunked recording: {}", e);
+            }
+            *self.active.lock().unwrap() = None;
+            self.discard_session(&session);
+            return Err(started
+                .err()
+                .unwrap_or_else(|| anyhow::anyhow!("Failed to start recording")));
+        }
+
+        info!("Meeting {} started", session);
+        self.app_handle
+            .state::<Arc<TranscriptionManager>>()
+            .initiate_model_load();
+        change_tray_icon(&self.app_handle, TrayIconState::Recording);
+        self.emit_status();
+        Ok(())
+    }
+
+    /// Ends the recording. Transcription of the remaining chunks and saving
+    /// the transcript continue in the background.
+    pub fn stop(&self) -> Result<()> {
+        let Some(session) = self.active.lock().unwrap().clone() else {
+            anyhow::bail!("No meeting is being recorded");
+        };
+
+        // The last chunk is delivered before the recording has stopped
+        let rm = self.app_handle.state::<Arc<AudioRecordingManager>>();
+        rm.stop_recording(MEETING_BINDING_ID);
+        if let Err(e) = rm.set_chunking(None) {
+            warn!("Failed to turn off chunked recording: {}", e);
+        }
+        *self.active.lock().unwrap() = None;
+
+        info!("Meeting {} stopped", session);
+        change_tray_icon(&self.app_handle, TrayIconState::Idle);
+        self.queue(Job::Finish { session });
+        self.emit_status();
+        Ok(())
+    }
+
+    /// Stops the meeting being recorded and throws away its audio.
+    pub fn cancel(&self) {
+        let Some(session) = self.active.lock().unwrap().take() else {
+            return;
+        };
+
+        let rm = self.app_handle.state::<Arc<AudioRecordingManager>>();
+        rm.cancel_recording();
+        if let Err(e) = rm.set_chunking(None) {
+            warn!("Failed to turn off chunked recording: {}", e);
+        }
+
+        info!("Meeting {} cancelled", session);
+        self.discard_session(&session);
+        self.emit_status();
+    }
+
+    /// Stores a chunk of the meeting being recorded and queues it for
+    /// transcription. Called from the audio thread.
+    pub fn add_chunk(&self, chunk: AudioChunk) {
+        let Some(session) = self.active.lock().unwrap().clone() else {
+            debug!("Dropping audio chunk received outside of a meeting");
+            return;
+        };
+
+        match self.store_chunk(&session, chunk) {
+            Ok(index) => self.queue(Job::Transcribe { session, index }),
+            Err(e) => error!("Failed to store meeting chunk: {}", e),
+        }
+        self.emit_status();
+    }
+
+    fn store_chunk(&self, session: &str, chunk: AudioChunk) -> Result<usize> {
+        let mut sessions = self.sessions.lock().unwrap();
+        let manifest = sessions
+            .get_mut(session)
+            .ok_or_else(|| anyhow::anyhow!("Unknown meeting session {}", session))?;
+
+        let index = manifest.chunks.len();
+        let file_name = format!("chunk-{:05}.wav", index);
+        write_wav_file(
+            self.meetings_dir.join(session).join(&file_name),
+            &chunk.samples,
+        )?;
+
+        manifest.chunks.push(ChunkRecord {
+            file_name,
+            start_ms: chunk.start.as_millis() as u64,
+            duration_ms: chunk.samples.len() as u64 * 1000 / WHISPER_SAMPLE_RATE as u64,
+            text: None,
+            segments: Vec::new(),
+            attempts: 0,
+            failed: false,
+        });
+        self.save_manifest(session, manifest)?;
+        Ok(index)
+    }
+
+    /// Queues sessions left behind by a crash, or by quitting before their
+    /// transcription finished, so they end up in history like any other.
+    fn resume_sessions(&self) {
+        let entries = match fs::read_dir(&self.meetings_dir) {
+            Ok(entries) => entries,
+            Err(e) => {
+                warn!("Failed to look for unfinished meetings: {}", e);
+                return;
+            }
+        };
+
+        for entry in entries.flatten() {
+            let Some(session) = entry.file_name().to_str().map(String::from) else {
+                continue;
+            };
+            let manifest = match self.load_manifest(&session) {
+                Ok(manifest) => manifest,
+                Err(e) => {
+                    warn!("Skipping meeting session {}: {}", session, e);
+                    continue;
+                }
+            };
+
+            info!(
+                "Resuming meeting {} with {} recorded chunks",
+                session,
+                manifest.chunks.len()
+            );
+            let untranscribed: Vec<usize> = manifest
+                .chunks
+                .iter()
+                .enumerate()
+                .filter(|(_, chunk)| chunk.is_pending())
+                .map(|(index, _)| index)
+                .collect();
+            self.sessions
+                .lock()
+                .unwrap()
+                .insert(session.clone(), manifest);
+
+            for index in untranscribed {
+                self.queue(Job::Transcribe {
+                    session: session.clone(),
+                    index,
+                });
+            }
+            self.queue(Job::Finish { session });
+        }
+    }
+
+    fn run_jobs(&self, jobs: mpsc::Receiver<Job>) {
+        for job in jobs {
+            match job {
+                Job::Transcribe { session, index } => {
+                    if let Err(e) = self.transcribe_chunk(&session, index) {
+                        error!("Failed to transcribe chunk {} of {}: {}", index, session, e);
+                        self.retry_chunk(&session, index, &e);
+                    }
+                }
+                Job::Finish { session } => {
+                    if let Err(e) = self.finish_session(&session) {
+                        error!("Failed to finish meeting {}: {}", session, e);
+                        let _ = self.app_handle.emit("meeting-error", e.to_string());
+                    }
+                }
+            }
+            self.emit_status();
+        }
+    }
+
+    fn transcribe_chunk(&self, session: &str, index: usize) -> Result<()> {
+        let (path, start_ms, end_ms) = {
+            let sessions = self.sessions.lock().unwrap();
+            let chunk = sessions.get(session).and_then(|m| m.chunks.get(index));
+            match chunk {
+                Some(chunk) => (
+                    self.meetings_dir.join(session).join(&chunk.file_name),
+                    chunk.start_ms,
+                    chunk.start_ms + chunk.duration_ms,
+                ),
+                // Cancelled in the meantime
+                None => return Ok(()),
+            }
+        };
+
+        let (text, segments) = match read_wav_file(&path) {
+            Ok(mut samples) => {
+                // Pad very short chunks, as for dictations
+                let min_samples = WHISPER_SAMPLE_RATE as usize;
+                if samples.len() < min_samples {
+                    samples.resize(min_samples * 5 / 4, 0.0);
+                }
+                let tm = self.app_handle.state::<Arc<TranscriptionManager>>();
+                tm.initiate_model_load();
+                let transcription = tm.transcribe(samples)?;
+
+                // Padding can stretch the last segment past the chunk
+                let offset = |secs: f32| (start_ms + (secs.max(0.0) * 1000.0) as u64).min(end_ms);
+                let segments: Vec<SegmentRecord> = transcription
+                    .segments
+                    .into_iter()
+                    .map(|segment| SegmentRecord {
+                        start_ms: offset(segment.start),
+                        end_ms: offset(segment.end),
+                        text: segment.text,
+                    })
+                    .collect();
+                (transcription.text, segments)
+            }
+            // A chunk that was being written during a crash is lost
+            Err(e) => {
+                warn!("Skipping unreadable meeting chunk {:?}: {}", path, e);
+                (String::new(), Vec::new())
+            }
+        };
+
+        let mut sessions = self.sessions.lock().unwrap();
+        if let Some(manifest) = sessions.get_mut(session) {
+            if let Some(chunk) = manifest.chunks.get_mut(index) {
+                chunk.text = Some(text);
+                chunk.segments = segments;
+            }
+            self.save_manifest(session, manifest)?;
+        }
+        Ok(())
+    }
+
+    /// Queues another attempt at a chunk after a growing delay, or gives up
+    /// on it once it has failed too often. A stopped session is finished
+    /// after the last of its retries.
+    fn retry_chunk(&self, session: &str, index: usize, error: &anyhow::Error) {
+        let attempts = {
+            let mut sessions = self.sessions.lock().unwrap();
+            let Some(manifest) = sessions.get_mut(session) else {
+                return;
+            };
+            let Some(chunk) = manifest.chunks.get_mut(index) else {
+                return;
+            };
+            chunk.attempts += 1;
+            chunk.failed = chunk.attempts >= MAX_CHUNK_ATTEMPTS;
+            let attempts = chunk.attempts;
+            if let Err(e) = self.save_manifest(session, manifest) {
+                warn!("Failed to save meeting {}: {}", session, e);
+            }
+            attempts
+        };
+
+        if attempts >= MAX_CHUNK_ATTEMPTS {
+            warn!(
+                "Giving up on chunk {} of {} after {} attempts",
+                index, session, attempts
+            );
+            let _ = self.app_handle.emit(
+                "meeting-error",
+                format!(
+                    "Part of the meeting at {} could not be transcribed: {}",
+                    self.chunk_time(session, index),
+                    error
+                ),
+            );
+            if !self.is_recording_session(session) {
+                self.queue(Job::Finish {
+                    session: session.to_string(),
+                });
+            }
+            return;
+        }
+
+        let delay = CHUNK_RETRY_BACKOFF * 2u32.pow(attempts - 1);
+        debug!("Retrying chunk {} of {} in {:?}", index, session, delay);
+        let manager = self.clone();
+        let session = session.to_string();
+        thread::spawn(move || {
+            thread::sleep(delay);
+            manager.queue(Job::Transcribe {
+                session: session.clone(),
+                index,
+            });
+            // Finishing was put off while the chunk was pending
+            if !manager.is_recording_session(&session) {
+                manager.queue(Job::Finish { session });
+            }
+        });
+    }
+
+    fn is_recording_session(&self, session: &str) -> bool {
+        self.active.lock().unwrap().as_deref() == Some(session)
+    }
+
+    /// Offset of a chunk into its meeting, as shown in transcripts.
+    fn chunk_time(&self, session: &str, index: usize) -> String {
+        let start_ms = self
+            .sessions
+            .lock()
+            .unwrap()
+            .get(session)
+            .and_then(|m| m.chunks.get(index))
+            .map_or(0, |chunk| chunk.start_ms);
+        format_offset(start_ms)
+    }
+
+    /// Saves the transcript and the joined audio of a stopped session to
+    /// history and removes its chunks. While chunks are waiting for a retry
+    /// this does nothing, and the retry finishes the session instead. Chunks
+    /// that failed every attempt are marked in the transcript.
+    fn finish_session(&self, session: &str) -> Result<()> {
+        let Some(manifest) = self.sessions.lock().unwrap().get(session).cloned() else {
+            return Ok(());
+        };
+
+        let pending = manifest
+            .chunks
+            .iter()
+            .filter(|chunk| chunk.is_pending())
+            .count();
+        if pending > 0 {
+            debug!(
+                "Meeting {} is waiting for {} chunks to be transcribed",
+                session, pending
+            );
+            return Ok(());
+        }
+
+        let dir = self.meetings_dir.join(session);
+        let segments = self.collect_segments(&dir, &manifest.chunks);
+        if segments.is_empty() {
+            info!("Meeting {} contained no speech", session);
+        } else {
+            let hm = self.app_handle.state::<Arc<HistoryManager>>();
+            let file_name = format!("{}{}.wav", MEETING_FILE_PREFIX, manifest.started_at);
+            let parts: Vec<(Duration, PathBuf)> = manifest
+                .chunks
+                .iter()
+                .map(|chunk| {
+                    (
+                        Duration::from_millis(chunk.start_ms),
+                        dir.join(&chunk.file_name),
+                    )
+                })
+                .collect();
+            concat_wav_files(hm.get_audio_file_path(&file_name), &parts)?;
+            hm.save_meeting(file_name, manifest.started_at, &segments)?;
+            info!("Meeting {} saved to history", session);
+        }
+
+        self.sessions.lock().unwrap().remove(session);
+        fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+
+    /// The segments of all chunks, attributed to speakers if diarization is
+    /// enabled.
+    fn collect_segments(&self, dir: &Path, chunks: &[ChunkRecord]) -> Vec<TranscriptSegment> {
+        let embedder = get_settings(&self.app_handle)
+            .diarization_enabled
+            .then(SpeakerEmbedder::new);
+
+        let mut segments = Vec::new();
+        let mut embeddings = Vec::new();
+        for chunk in chunks {
+            let chunk_segments = chunk_segments(chunk);
+            if let Some(embedder) = &embedder {
+                if !chunk_segments.is_empty() {
+                    let samples = read_wav_file(dir.join(&chunk.file_name)).unwrap_or_else(|e| {
+                        warn!("Failed to read meeting chunk {}: {}", chunk.file_name, e);
+                        Vec::new()
+                    });
+                    let sample_at = |ms: u64| {
+                        let index = ms.saturating_sub(chunk.start_ms) as usize
+                            * WHISPER_SAMPLE_RATE as usize
+                            / 1000;
+                        index.min(samples.len())
+                    };
+                    for segment in &chunk_segments {
+                        let voice =
+                            &samples[sample_at(segment.start_ms)..sample_at(segment.end_ms)];
+                        embeddings.push(embedder.embed(voice));
+                    }
+                }
+            }
+            segments.extend(chunk_segments);
+        }
+
+        if embedder.is_some() {
+            let speakers = cluster_speakers(&embeddings);
+            for (segment, speaker) in segments.iter_mut().zip(speakers) {
+                segment.speaker = speaker;
+            }
+        }
+        segments
+    }
+
+    fn discard_session(&self, session: &str) {
+        self.sessions.lock().unwrap().remove(session);
+        if let Err(e) = fs::remove_dir_all(self.meetings_dir.join(session)) {
+            warn!("Failed to remove meeting session {}: {}", session, e);
+        }
+    }
+
+    fn load_manifest(&self, session: &str) -> Result<SessionManifest> {
+        let json = fs::read_to_string(self.meetings_dir.join(session).join(MANIFEST_FILE))?;
+        Ok(serde_json::from_str(&json)?)
+    }
+
+    fn save_manifest(&self, session: &str, manifest: &SessionManifest) -> Result<()> {
+        let dir = self.meetings_dir.join(session);
+        let tmp_path = dir.join(format!("{}.tmp", MANIFEST_FILE));
+        fs::write(&tmp_path, serde_json::to_vec(manifest)?)?;
+        // Replacing the manifest in one step keeps the previous one intact if
+        // writing is interrupted
+        fs::rename(tmp_path, dir.join(MANIFEST_FILE))?;
+        Ok(())
+    }
+
+    fn queue(&self, job: Job) {
+        if self.jobs.send(job).is_err() {
+            error!("Meeting worker is not running");
+        }
+    }
+
+    fn emit_status(&self) {
+        if let Err(e) = self.app_handle.emit("meeting-status", self.status()) {
+            error!("Failed to emit meeting-status event: {}", e);
+        }
+    }
+}
+
+/// The transcribed segments of a chunk. A chunk the engine didn't time
+/// becomes a single segment spanning all of it.
+fn chunk_segments(chunk: &ChunkRecord) -> Vec<TranscriptSegment> {
+    let segment = |start_ms, end_ms, text: &str| TranscriptSegment {
+        start_ms,
+        end_ms,
+        speaker: None,
+        text: text.trim().to_string(),
+    };
+
+    let segments: Vec<TranscriptSegment> = if chunk.failed {
+        vec![segment(
+            chunk.start_ms,
+            chunk.start_ms + chunk.duration_ms,
+            FAILED_CHUNK_TEXT,
+        )]
+    } else if chunk.segments.is_empty() {
+        let text = chunk.text.as_deref().unwrap_or_default();
+        vec![segment(
+            chunk.start_ms,
+            chunk.start_ms + chunk.duration_ms,
+            text,
+        )]
+    } else {
+        chunk
+            .segments
+            .iter()
+            .map(|s| segment(s.start_ms, s.end_ms, &s.text))
+            .collect()
+    };
+    segments
+        .into_iter()
+        .filter(|segment| !segment.text.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transcript::format_plain;
+
+    fn chunk(start_ms: u64, text: Option<&str>, segments: &[(u64, u64, &str)]) -> ChunkRecord {
+        ChunkRecord {
+            file_name: String::new(),
+            start_ms,
+            duration_ms: 3_000,
+            text: text.map(String::from),
+            segments: segments
+                .iter()
+                .map(|&(start_ms, end_ms, text)| SegmentRecord {
+                    start_ms,
+                    end_ms,
+                    text: text.to_string(),
+                })
+                .collect(),
+            attempts: 0,
+            failed: false,
+        }
+    }
+
+    #[test]
+    fn transcript_lines_are_timestamped_and_skip_silence() {
+        let chunks = [
+            chunk(1_500, Some(" Hello everyone. "), &[]),
+            chunk(9_000, Some(""), &[]),
+            chunk(
+                3_725_000,
+                Some("Let's wrap up. Thanks."),
+                &[
+                    (3_725_000, 3_726_500, " Let's wrap up."),
+                    (3_726_600, 3_727_900, "Thanks."),
+                ],
+            ),
+        ];
+        let segments: Vec<_> = chunks.iter().flat_map(chunk_segments).collect();
+
+        assert_eq!(segments[0].end_ms, 4_500);
+        assert_eq!(
+            format_plain(&segments, &HashMap::new()),
+            "[00:00:01] Hello everyone.\n[01:02:05] Let's wrap up.\n[01:02:06] Thanks."
+        );
+    }
+
+    #[test]
+    fn failed_chunks_are_marked_in_the_transcript() {
+        let mut failed = chunk(4_000, None, &[]);
+        assert!(failed.is_pending());
+        failed.attempts = MAX_CHUNK_ATTEMPTS;
+        failed.failed = true;
+        assert!(!failed.is_pending());
+
+        let chunks = [chunk(0, Some("Welcome."), &[]), failed];
+        let segments: Vec<_> = chunks.iter().flat_map(chunk_segments).collect();
+        assert_eq!(
+            format_plain(&segments, &HashMap::new()),
+            format!("[00:00:00] Welcome.\n[00:00:04] {}", FAILED_CHUNK_TEXT)
+        );
+    }
+}
@@ -1,4 +1,8 @@
 pub mod audio;
+pub mod benchmark;
+pub mod config_file;
 pub mod history;
+pub mod meeting;
 pub mod model;
+pub mod secrets;
 pub mod transcription;
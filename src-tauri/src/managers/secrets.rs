@@ -0,0 +1,355 @@
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use anyhow::Result;
+use log::{debug, info, warn};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+
+use crate::settings::{get_settings, write_settings, AppSettings};
+
+/// Service name used for every entry we create in the OS keyring.
+const KEYRING_SERVICE: &str = "com.zyahav.aleflow";
+
+/// Placeholder returned to the frontend instead of a stored API key.
+/// Writing this value back through `change_post_process_api_key_setting` is a no-op.
+pub const REDACTED_API_KEY: &str = "********";
+
+const SECRETS_FILE_NAME: &str = "secrets.enc";
+const SECRETS_KEY_FILE_NAME: &str = "secrets.key";
+const NONCE_LEN: usize = 12;
+
+fn api_key_entry(provider_id: &str) -> String {
+    format!("post_process_api_key:{}", provider_id)
+}
+
+/// A place where secrets can be stored and retrieved by name.
+pub trait SecretStore: Send + Sync {
+    /// Human readable backend name, used for logging.
+    fn name(&self) -> &'static str;
+
+    fn get(&self, key: &str) -> Result<Option<String>>;
+
+    fn set(&self, key: &str, value: &str) -> Result<()>;
+
+    fn delete(&self, key: &str) -> Result<()>;
+}
+
+/* ──────────────────────────────────────────────────────────────── */
+
+/// Stores secrets in the platform keyring (Keychain on macOS, Credential
+/// Manager on Windows, Secret Service on Linux).
+pub struct KeyringStore;
+
+impl KeyringStore {
+    /// Verifies that the keyring is reachable by round-tripping a throwaway
+    /// entry. On Linux this fails when no Secret Service daemon is running.
+    pub fn probe() -> Result<Self> {
+        let store = Self;
+        let probe_key = "aleflow_keyring_probe";
+        store.set(probe_key, "ok")?;
+        let value = store.get(probe_key)?;
+        let _ = store.delete(probe_key);
+
+        if value.as_deref() != Some("ok") {
+            anyhow::bail!("keyring did not return the probe value");
+        }
+        Ok(store)
+    }
+
+    fn entry(key: &str) -> Result<keyring::Entry> {
+        keyring::Entry::new(KEYRING_SERVICE, key)
+            .map_err(|e| anyhow::anyhow!("Failed to open keyring entry '{}': {}", key, e))
+    }
+}
+
+impl SecretStore for KeyringStore {
+    fn name(&self) -> &'static str {
+        "os-keyring"
+    }
+
+    fn get(&self, key: &str) -> Result<Option<String>> {
+        match Self::entry(key)?.get_password() {
+            Ok(value) => Ok(Some(value)),
+            Err(keyring::Error::NoEntry) => Ok(None),
+            Err(e) => Err(anyhow::anyhow!("Failed to read keyring entry: {}", e)),
+        }
+    }
+
+    fn set(&self, key: &str, value: &str) -> Result<()> {
+        Self::entry(key)?
+            .set_password(value)
+            .map_err(|e| anyhow::anyhow!("Failed to write keyring entry: {}", e))
+    }
+
+    fn delete(&self, key: &str) -> Result<()> {
+        match Self::entry(key)?.delete_credential() {
+            Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+            Err(e) => Err(anyhow::anyhow!("Failed to delete keyring entry: {}", e)),
+        }
+    }
+}
+
+/* ──────────────────────────────────────────────────────────────── */
+
+/// Fallback store that keeps all secrets in a single AES-256-GCM encrypted
+/// file. The key lives in a separate owner-only file next to it, so this
+/// keeps keys out of the settings file and its backups rather than
+/// protecting against a local attacker with access to the user account.
+pub struct EncryptedFileStore {
+    data_path: PathBuf,
+    key_path: PathBuf,
+    lock: Mutex<()>,
+}
+
+impl EncryptedFileStore {
+    pub fn new(dir: &Path) -> Result<Self> {
+        fs::create_dir_all(dir)?;
+        Ok(Self {
+            data_path: dir.join(SECRETS_FILE_NAME),
+            key_path: dir.join(SECRETS_KEY_FILE_NAME),
+            lock: Mutex::new(()),
+        })
+    }
+
+    fn load_or_create_key(&self) -> Result<Key<Aes256Gcm>> {
+        if self.key_path.exists() {
+            let bytes = fs::read(&self.key_path)?;
+            if bytes.len() != 32 {
+                anyhow::bail!("Secrets key file has an invalid length");
+            }
+            return Ok(*Key::<Aes256Gcm>::from_slice(&bytes));
+        }
+
+        let key = Aes256Gcm::generate_key(OsRng);
+        write_private_file(&self.key_path, key.as_slice())?;
+        debug!("Created secrets key at {:?}", self.key_path);
+        Ok(key)
+    }
+
+    fn read_all(&self) -> Result<HashMap<String, String>> {
+        if !self.data_path.exists() {
+            return Ok(HashMap::new());
+        }
+
+        let data = fs::read(&self.data_path)?;
+        if data.len() < NONCE_LEN {
+            anyhow::bail!("Secrets file is truncated");
+        }
+
+        let cipher = Aes256Gcm::new(&self.load_or_create_key()?);
+        let (nonce, ciphertext) = data.split_at(NONCE_LEN);
+        let plaintext = cipher
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| anyhow::anyhow!("Failed to decrypt secrets file"))?;
+
+        Ok(serde_json::from_slice(&plaintext)?)
+    }
+
+    fn write_all(&self, secrets: &HashMap<String, String>) -> Result<()> {
+        let cipher = Aes256Gcm::new(&self.load_or_create_key()?);
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let plaintext = serde_json::to_vec(secrets)?;
+        let ciphertext = cipher
+            .encrypt(&nonce, plaintext.as_slice())
+            .map_err(|_| anyhow::anyhow!("Failed to encrypt secrets"))?;
+
+        let mut data = nonce.to_vec();
+        data.extend_from_slice(&ciphertext);
+        write_private_file(&self.data_path, &data)
+    }
+}
+
+impl SecretStore for EncryptedFileStore {
+    fn name(&self) -> &'static str {
+        "encrypted-file"
+    }
+
+    fn get(&self, key: &str) -> Result<Option<String>> {
+        let _guard = self.lock.lock().unwrap();
+        Ok(self.read_all()?.get(key).cloned())
+    }
+
+    fn set(&self, key: &str, value: &str) -> Result<()> {
+        let _guard = self.lock.lock().unwrap();
+        let mut secrets = self.read_all()?;
+        secrets.insert(key.to_string(), value.to_string());
+        self.write_all(&secrets)
+    }
+
+    fn delete(&self, key: &str) -> Result<()> {
+        let _guard = self.lock.lock().unwrap();
+        let mut secrets = self.read_all()?;
+        if secrets.remove(key).is_some() {
+            self.write_all(&secrets)?;
+        }
+        Ok(())
+    }
+}
+
+/// Writes a file that only the current user can read.
+fn write_private_file(path: &Path, data: &[u8]) -> Result<()> {
+    let tmp_path = path.with_extension("tmp");
+    fs::write(&tmp_path, data)?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&tmp_path, fs::Permissions::from_mode(0o600))?;
+    }
+
+    fs::rename(&tmp_path, path)?;
+    Ok(())
+}
+
+/* ──────────────────────────────────────────────────────────────── */
+
+pub struct SecretsManager {
+    store: Box<dyn SecretStore>,
+}
+
+impl SecretsManager {
+    pub fn new(app_handle: &AppHandle) -> Result<Self> {
+        let store: Box<dyn SecretStore> = match KeyringStore::probe() {
+            Ok(store) => Box::new(store),
+            Err(e) => {
+                warn!(
+                    "OS keyring unavailable ({}), falling back to encrypted file storage",
+                    e
+                );
+                let config_dir = app_handle
+                    .path()
+                    .app_config_dir()
+                    .map_err(|e| anyhow::anyhow!("Failed to get app config dir: {}", e))?;
+                Box::new(EncryptedFileStore::new(&config_dir)?)
+            }
+        };
+
+        info!("Using '{}' backend for secrets", store.name());
+        Ok(Self { store })
+    }
+
+    /// Returns the API key for a post-processing provider, or an empty string.
+    pub fn get_api_key(&self, provider_id: &str) -> String {
+        match self.store.get(&api_key_entry(provider_id)) {
+            Ok(value) => value.unwrap_or_default(),
+            Err(e) => {
+                warn!("Failed to read API key for '{}': {}", provider_id, e);
+                String::new()
+            }
+        }
+    }
+
+    /// Stores the API key for a provider. An empty key removes the entry.
+    pub fn set_api_key(&self, provider_id: &str, api_key: &str) -> Result<()> {
+        let entry = api_key_entry(provider_id);
+        if api_key.is_empty() {
+            self.store.delete(&entry)
+        } else {
+            self.store.set(&entry, api_key)
+        }
+    }
+
+    pub fn has_api_key(&self, provider_id: &str) -> bool {
+        !self.get_api_key(provider_id).is_empty()
+    }
+
+    /// Replaces every API key in `settings` with a placeholder so raw keys never
+    /// reach the frontend. Providers without a key get an empty string.
+    pub fn redact_api_keys(&self, settings: &mut AppSettings) {
+        let provider_ids: Vec<String> = settings
+            .post_process_providers
+            .iter()
+            .map(|provider| provider.id.clone())
+            .collect();
+
+        for provider_id in provider_ids {
+            let value = if self.has_api_key(&provider_id) {
+                REDACTED_API_KEY.to_string()
+            } else {
+                String::new()
+            };
+            settings.post_process_api_keys.insert(provider_id, value);
+        }
+    }
+
+    /// Moves API keys that older versions stored in clear text in the settings
+    /// file into the secret store. Keys are only removed from the settings once
+    /// they have been stored successfully.
+    pub fn migrate_legacy_api_keys(&self, app_handle: &AppHandle) {
+        let mut settings = get_settings(app_handle);
+        let mut migrated = 0;
+
+        for (provider_id, api_key) in settings.post_process_api_keys.iter_mut() {
+            if api_key.is_empty() {
+                continue;
+            }
+
+            match self.set_api_key(provider_id, api_key) {
+                Ok(()) => {
+                    api_key.clear();
+                    migrated += 1;
+                }
+                Err(e) => warn!(
+                    "Failed to migrate API key for '{}', keeping it in settings: {}",
+                    provider_id, e
+                ),
+            }
+        }
+
+        if migrated > 0 {
+            write_settings(app_handle, settings);
+            info!(
+                "Migrated {} API key(s) from settings to '{}'",
+                migrated,
+                self.store.name()
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("aleflow-secrets-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_encrypted_file_store_round_trip() {
+        let dir = temp_dir("round-trip");
+        let store = EncryptedFileStore::new(&dir).unwrap();
+
+        assert_eq!(store.get("openai").unwrap(), None);
+        store.set("openai", "sk-test").unwrap();
+        assert_eq!(store.get("openai").unwrap().as_deref(), Some("sk-test"));
+
+        // A fresh instance must be able to read what the first one wrote
+        let reopened = EncryptedFileStore::new(&dir).unwrap();
+        assert_eq!(reopened.get("openai").unwrap().as_deref(), Some("sk-test"));
+
+        reopened.delete("openai").unwrap();
+        assert_eq!(reopened.get("openai").unwrap(), None);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_encrypted_file_store_does_not_contain_plaintext() {
+        let dir = temp_dir("plaintext");
+        let store = EncryptedFileStore::new(&dir).unwrap();
+        store.set("groq", "gsk-very-secret").unwrap();
+
+        let raw = fs::read(dir.join(SECRETS_FILE_NAME)).unwrap();
+        let needle = b"gsk-very-secret";
+        assert!(!raw.windows(needle.len()).any(|w| w == needle));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
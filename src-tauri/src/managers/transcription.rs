@@ -27,6 +27,27 @@ pub struct ModelStateEvent {
     pub error: Option<String>,
 }
 
+/// Result of a transcription together with where the time went.
+#[derive(Clone, Debug)]
+pub struct TimedTranscription {
+    pub text: String,
+    /// Timed pieces of `text`, when the engine reports them.
+    pub segments: Vec<TimedSegment>,
+    pub model_id: Option<String>,
+    /// Time spent waiting for the model to finish loading, zero if it was ready.
+    pub load_duration: Duration,
+    /// Time spent in the engine, including custom word correction.
+    pub inference_duration: Duration,
+}
+
+/// A stretch of the transcription, timed in seconds from the start of the audio.
+#[derive(Clone, Debug)]
+pub struct TimedSegment {
+    pub start: f32,
+    pub end: f32,
+    pub text: String,
+}
+
 enum LoadedEngine {
     Whisper(WhisperEngine),
     Parakeet(ParakeetEngine),
@@ -315,7 +336,7 @@ impl TranscriptionManager {
         current_model.clone()
     }
 
-    pub fn transcribe(&self, audio: Vec<f32>) -> Result<String> {
+    pub fn transcribe(&self, audio: Vec<f32>) -> Result<TimedTranscription> {
         // Update last activity timestamp
         self.last_activity.store(
             SystemTime::now()
@@ -332,11 +353,17 @@ impl TranscriptionManager {
         if audio.is_empty() {
             debug!("Empty audio vector");
             self.maybe_unload_immediately("empty audio");
-            return Ok(String::new());
+            return Ok(TimedTranscription {
+                text: String::new(),
+                segments: Vec::new(),
+                model_id: self.get_current_model(),
+                load_duration: Duration::ZERO,
+                inference_duration: Duration::ZERO,
+            });
         }
 
         // Check if model is loaded, if not try to load it
-        {
+        let load_duration = {
             // If the model is loading, wait for it to complete.
             let mut is_loading = self.is_loading.lock().unwrap();
             while *is_loading {
@@ -347,7 +374,9 @@ impl TranscriptionManager {
             if engine_guard.is_none() {
                 return Err(anyhow::anyhow!("Model is not loaded for transcription."));
             }
-        }
+            st.elapsed()
+        };
+        let inference_start = std::time::Instant::now();
 
         // Get current settings for configuration
         let settings = get_settings(&self.app_handle);
@@ -402,15 +431,31 @@ impl TranscriptionManager {
         };
 
         // Apply word correction if custom words are configured
-        let corrected_result = if !settings.custom_words.is_empty() {
-            apply_custom_words(
-                &result.text,
-                &settings.custom_words,
-                settings.word_correction_threshold,
-            )
-        } else {
-            result.text
+        let correct = |text: &str| {
+            if settings.custom_words.is_empty() {
+                text.to_string()
+            } else {
+                apply_custom_words(
+                    text,
+                    &settings.custom_words,
+                    settings.word_correction_threshold,
+                )
+            }
         };
+        let corrected_result = correct(&result.text);
+        let segments = result
+            .segments
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|segment| {
+                let text = correct(&segment.text).trim().to_string();
+                (!text.is_empty()).then_some(TimedSegment {
+                    start: segment.start,
+                    end: segment.end,
+                    text,
+                })
+            })
+            .collect();
 
         let et = std::time::Instant::now();
         let translation_note = if settings.translate_to_english {
@@ -432,9 +477,17 @@ impl TranscriptionManager {
             info!("Transcription result: {}", final_result);
         }
 
+        let inference_duration = inference_start.elapsed();
+        let model_id = self.get_current_model();
         self.maybe_unload_immediately("transcription");
 
-        Ok(final_result)
+        Ok(TimedTranscription {
+            text: final_result,
+            segments,
+            model_id,
+            load_duration,
+            inference_duration,
+        })
     }
 }
 
@@ -0,0 +1,312 @@
+//! Sends finished dictations to the output sinks configured in settings, on
+//! top of pasting them. A sink with a `prompt_id` only receives dictations
+//! post-processed with that prompt. File and pipe paths may use strftime
+//! patterns such as `%Y-%m-%d` and a leading `~` for the home directory.
+//!
+//! Each sink runs on its own task so a slow webhook or a pipe without a reader
+//! never holds up pasting or the other sinks.
+
+use crate::settings::{get_settings, OutputSink, OutputSinkKind};
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, Local};
+use log::{debug, error};
+use serde::Serialize;
+use std::collections::HashSet;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Serialize)]
+struct WebhookPayload<'a> {
+    text: &'a str,
+    timestamp: String,
+    prompt_id: Option<&'a str>,
+}
+
+/// Hands `text` to every enabled sink that applies to dictations
+/// post-processed with `prompt_id`.
+pub fn deliver(app: &AppHandle, text: &str, prompt_id: Option<&str>) {
+    let sinks: Vec<OutputSink> = get_settings(app)
+        .output_sinks
+        .into_iter()
+        .filter(|sink| applies_to(sink, prompt_id))
+        .collect();
+    if sinks.is_empty() {
+        return;
+    }
+
+    let home = app.path().home_dir().ok();
+    let now = Local::now();
+    for sink in sinks {
+        let text = text.to_string();
+        let prompt_id = prompt_id.map(str::to_string);
+        let home = home.clone();
+        tauri::async_runtime::spawn(async move {
+            let result = match &sink.kind {
+                OutputSinkKind::Webhook { url } => {
+                    post_webhook(url, &text, now, prompt_id.as_deref()).await
+                }
+                kind => {
+                    let kind = kind.clone();
+                    tauri::async_runtime::spawn_blocking(move || {
+                        write_blocking(&kind, &text, home.as_deref(), now)
+                    })
+                    .await
+                    .unwrap_or_else(|e| Err(e.to_string()))
+                }
+            };
+            match result {
+                Ok(()) => debug!("Delivered dictation to output sink {}", sink.id),
+                Err(e) => error!("Output sink {} failed: {}", sink.id, e),
+            }
+        });
+    }
+}
+
+/// Checks a list of sinks before it is saved: ids must be set and unique, and
+/// every target valid.
+pub fn validate_sinks(sinks: &[OutputSink]) -> Result<(), String> {
+    let mut ids = HashSet::new();
+    for sink in sinks {
+        if sink.id.is_empty() || !ids.insert(sink.id.as_str()) {
+            return Err(format!("Output sink id '{}' is empty or repeated", sink.id));
+        }
+        validate_sink(sink)?;
+    }
+    Ok(())
+}
+
+/// Checks a sink's target before it is saved.
+pub fn validate_sink(sink: &OutputSink) -> Result<(), String> {
+    match &sink.kind {
+        OutputSinkKind::File { path } => {
+            if path.trim().is_empty() {
+                return Err("Output file path is empty".to_string());
+            }
+            if !has_valid_date_pattern(path) {
+                return Err(format!("Invalid date pattern in '{}'", path));
+            }
+        }
+        OutputSinkKind::Webhook { url } => {
+            let parsed = reqwest::Url::parse(url)
+                .map_err(|e| format!("Invalid webhook URL '{}': {}", url, e))?;
+            if !matches!(parsed.scheme(), "http" | "https") {
+                return Err(format!("Webhook URL '{}' must use http or https", url));
+            }
+        }
+        OutputSinkKind::Pipe { path } if path.trim().is_empty() => {
+            return Err("Pipe path is empty".to_string());
+        }
+        OutputSinkKind::Command { command } if command.trim().is_empty() => {
+            return Err("Output command is empty".to_string());
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn has_valid_date_pattern(template: &str) -> bool {
+    !StrftimeItems::new(template).any(|item| matches!(item, Item::Error))
+}
+
+fn applies_to(sink: &OutputSink, prompt_id: Option<&str>) -> bool {
+    sink.enabled
+        && sink
+            .prompt_id
+            .as_deref()
+            .is_none_or(|id| Some(id) == prompt_id)
+}
+
+async fn post_webhook(
+    url: &str,
+    text: &str,
+    now: DateTime<Local>,
+    prompt_id: Option<&str>,
+) -> Result<(), String> {
+    let payload = WebhookPayload {
+        text,
+        timestamp: now.to_rfc3339(),
+        prompt_id,
+    };
+    reqwest::Client::new()
+        .post(url)
+        .timeout(WEBHOOK_TIMEOUT)
+        .json(&payload)
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+fn write_blocking(
+    kind: &OutputSinkKind,
+    text: &str,
+    home: Option<&Path>,
+    now: DateTime<Local>,
+) -> Result<(), String> {
+    match kind {
+        OutputSinkKind::File { path } => {
+            let path = expand_path(path, home, now);
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+            }
+            let mut file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&path)
+                .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+            writeln!(file, "{}\n", text).map_err(|e| e.to_string())
+        }
+        OutputSinkKind::Pipe { path } => write_pipe(&expand_path(path, home, now), text),
+        OutputSinkKind::Stdout => {
+            let mut stdout = std::io::stdout().lock();
+            writeln!(stdout, "{}", text)
+                .and_then(|_| stdout.flush())
+                .map_err(|e| e.to_string())
+        }
+        OutputSinkKind::Command { command } => run_command(command, text),
+        OutputSinkKind::Webhook { .. } => unreachable!("webhooks are posted asynchronously"),
+    }
+}
+
+/// Writes a line to a named pipe. Opening a FIFO waits for a reader, so on
+/// Unix it's opened without blocking and fails right away when nobody listens.
+fn write_pipe(path: &Path, text: &str) -> Result<(), String> {
+    let mut options = OpenOptions::new();
+    options.write(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.custom_flags(libc::O_NONBLOCK);
+    }
+    let mut pipe = options
+        .open(path)
+        .map_err(|e| format!("Failed to open pipe {}: {}", path.display(), e))?;
+    writeln!(pipe, "{}", text).map_err(|e| e.to_string())
+}
+
+/// Runs `command` through the shell with the text on its stdin.
+fn run_command(command: &str, text: &str) -> Result<(), String> {
+    #[cfg(target_os = "windows")]
+    let mut cmd = {
+        let mut cmd = Command::new("cmd");
+        cmd.args(["/C", command]);
+        cmd
+    };
+    #[cfg(not(target_os = "windows"))]
+    let mut cmd = {
+        let mut cmd = Command::new("sh");
+        cmd.args(["-c", command]);
+        cmd
+    };
+
+    let mut child = cmd
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to run '{}': {}", command, e))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // A command that ignores its input closes the pipe early, which is fine
+        let _ = stdin.write_all(text.as_bytes());
+    }
+
+    let output = child.wait_with_output().map_err(|e| e.to_string())?;
+    if !output.status.success() {
+        return Err(format!(
+            "'{}' exited with {}: {}",
+            command,
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(())
+}
+
+/// Fills in the date in `template` and expands a leading `~`. A template
+/// with a broken date pattern is used as it is.
+fn expand_path(template: &str, home: Option<&Path>, now: DateTime<Local>) -> PathBuf {
+    let path = if has_valid_date_pattern(template) {
+        now.format(template).to_string()
+    } else {
+        template.to_string()
+    };
+    match (path.strip_prefix("~/"), home) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn sink(kind: OutputSinkKind, prompt_id: Option<&str>) -> OutputSink {
+        OutputSink {
+            id: "sink".to_string(),
+            enabled: true,
+            kind,
+            prompt_id: prompt_id.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn file_paths_get_the_date_and_home_directory() {
+        let now = Local.with_ymd_and_hms(2026, 3, 7, 9, 30, 0).unwrap();
+        let home = Path::new("/home/me");
+        assert_eq!(
+            expand_path("~/journal/%Y-%m-%d.md", Some(home), now),
+            PathBuf::from("/home/me/journal/2026-03-07.md")
+        );
+        assert_eq!(
+            expand_path("/tmp/notes.md", Some(home), now),
+            PathBuf::from("/tmp/notes.md")
+        );
+    }
+
+    #[test]
+    fn sinks_apply_to_their_prompt_only() {
+        let every = sink(OutputSinkKind::Stdout, None);
+        let email = sink(OutputSinkKind::Stdout, Some("email"));
+        assert!(applies_to(&every, None));
+        assert!(applies_to(&every, Some("email")));
+        assert!(applies_to(&email, Some("email")));
+        assert!(!applies_to(&email, None));
+        assert!(!applies_to(&email, Some("notes")));
+    }
+
+    #[test]
+    fn invalid_targets_are_rejected() {
+        let file = |path: &str| sink(OutputSinkKind::File { path: path.into() }, None);
+        let webhook = |url: &str| sink(OutputSinkKind::Webhook { url: url.into() }, None);
+        assert!(validate_sink(&file("~/notes/%Y.md")).is_ok());
+        assert!(validate_sink(&file("~/notes/%!.md")).is_err());
+        assert!(validate_sink(&webhook("http://localhost:8080/hook")).is_ok());
+        assert!(validate_sink(&webhook("file:///etc/passwd")).is_err());
+        assert!(validate_sink(&sink(
+            OutputSinkKind::Command {
+                command: " ".into()
+            },
+            None
+        ))
+        .is_err());
+    }
+
+    #[test]
+    fn sink_ids_must_be_set_and_unique() {
+        let stdout = |id: &str| OutputSink {
+            id: id.to_string(),
+            ..sink(OutputSinkKind::Stdout, None)
+        };
+        assert!(validate_sinks(&[stdout("a"), stdout("b")]).is_ok());
+        assert!(validate_sinks(&[stdout("a"), stdout("a")]).is_err());
+        assert!(validate_sinks(&[stdout("")]).is_err());
+    }
+}
@@ -1,10 +1,16 @@
+use crate::caret;
 use crate::input;
+use crate::preview::PreviewPayload;
 use crate::settings;
-use crate::settings::OverlayPosition;
-use tauri::{AppHandle, Emitter, Manager, PhysicalPosition, PhysicalSize};
-
-#[cfg(not(target_os = "macos"))]
+use crate::settings::{AppSettings, OverlayPoint, OverlayPosition};
+use crate::tray::{self, TrayIconState};
 use log::debug;
+#[cfg(target_os = "macos")]
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, Monitor, PhysicalPosition, PhysicalSize};
 
 #[cfg(not(target_os = "macos"))]
 use tauri::WebviewWindowBuilder;
@@ -19,7 +25,8 @@ use tauri_nspanel::{tauri_panel, CollectionBehavior, PanelBuilder, PanelLevel};
 tauri_panel! {
     panel!(RecordingOverlayPanel {
         config: {
-            can_become_key_window: false,
+            // Only takes key focus while a preview is shown for editing
+            can_become_key_window: PANEL_TAKES_KEY.load(Ordering::SeqCst),
             is_floating_panel: true
         }
     })
@@ -28,6 +35,9 @@ tauri_panel! {
 const OVERLAY_WIDTH: f64 = 172.0;
 const OVERLAY_HEIGHT: f64 = 36.0;
 
+const PREVIEW_WIDTH: f64 = 420.0;
+const PREVIEW_HEIGHT: f64 = 180.0;
+
 #[cfg(target_os = "macos")]
 const OVERLAY_TOP_OFFSET: f64 = 46.0;
 #[cfg(any(target_os = "windows", target_os = "linux"))]
@@ -39,6 +49,30 @@ const OVERLAY_BOTTOM_OFFSET: f64 = 15.0;
 #[cfg(any(target_os = "windows", target_os = "linux"))]
 const OVERLAY_BOTTOM_OFFSET: f64 = 40.0;
 
+const CORNER_MARGIN: f64 = 16.0;
+
+/// Gap between the text caret and the overlay below it.
+const CARET_GAP: f64 = 8.0;
+
+/// How long the overlay has to stay put after a drag before it's saved.
+const DRAG_SETTLE_DELAY: Duration = Duration::from_millis(400);
+
+/// Bumped on every move of the overlay, so only the last move of a drag is saved.
+static DRAG_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Where we last put the overlay ourselves, in logical pixels. Our own moves
+/// are reported like drags, and must not be saved as one.
+static PLACED_POSITION: Mutex<Option<(f64, f64)>> = Mutex::new(None);
+
+/// Whether the overlay panel may become the key window. Only set while a
+/// preview is shown, so the other states never steal keyboard focus.
+#[cfg(target_os = "macos")]
+static PANEL_TAKES_KEY: AtomicBool = AtomicBool::new(false);
+
+/// Bumped whenever the overlay is shown, so a delayed hide doesn't hide an
+/// overlay that was shown again in the meantime.
+static OVERLAY_GENERATION: AtomicU64 = AtomicU64::new(0);
+
 /// Forces a window to be topmost using Win32 API (Windows only)
 /// This is more reliable than Tauri's set_always_on_top which can be overridden
 #[cfg(target_os = "windows")]
@@ -69,7 +103,7 @@ fn force_overlay_topmost(overlay_window: &tauri::webview::WebviewWindow) {
     });
 }
 
-fn get_monitor_with_cursor(app_handle: &AppHandle) -> Option<tauri::Monitor> {
+pub(crate) fn get_monitor_with_cursor(app_handle: &AppHandle) -> Option<tauri::Monitor> {
     if let Some(mouse_location) = input::get_cursor_position(app_handle) {
         if let Ok(monitors) = app_handle.available_monitors() {
             for monitor in monitors {
@@ -106,35 +140,227 @@ fn is_mouse_within_monitor(
         && mouse_y < (monitor_y + monitor_height as i32)
 }
 
-fn calculate_overlay_position(app_handle: &AppHandle) -> Option<(f64, f64)> {
-    if let Some(monitor) = get_monitor_with_cursor(app_handle) {
-        let work_area = monitor.work_area();
-        let scale = monitor.scale_factor();
-        let work_area_width = work_area.size.width as f64 / scale;
-        let work_area_height = work_area.size.height as f64 / scale;
-        let work_area_x = work_area.position.x as f64 / scale;
-        let work_area_y = work_area.position.y as f64 / scale;
-
-        let settings = settings::get_settings(app_handle);
-
-        let x = work_area_x + (work_area_width - OVERLAY_WIDTH) / 2.0;
-        let y = match settings.overlay_position {
-            OverlayPosition::Top => work_area_y + OVERLAY_TOP_OFFSET,
-            OverlayPosition::Bottom | OverlayPosition::None => {
-                // don't subtract the overlay height it puts it too far up
-                work_area_y + work_area_height - OVERLAY_BOTTOM_OFFSET
-            }
-        };
+/// The monitor the overlay goes on: the one picked in settings while it's
+/// connected, otherwise the one with the mouse.
+fn overlay_monitor(app_handle: &AppHandle, settings: &AppSettings) -> Option<Monitor> {
+    if let Some(name) = &settings.overlay_monitor {
+        let monitor = app_handle
+            .available_monitors()
+            .ok()
+            .and_then(|monitors| monitors.into_iter().find(|m| m.name() == Some(name)));
+        if monitor.is_some() {
+            return monitor;
+        }
+    }
+    get_monitor_with_cursor(app_handle)
+}
+
+/// The monitor containing the logical point `(x, y)`.
+fn monitor_at(app_handle: &AppHandle, x: f64, y: f64) -> Option<Monitor> {
+    app_handle.available_monitors().ok()?.into_iter().find(|m| {
+        let scale = m.scale_factor();
+        is_mouse_within_monitor(
+            ((x * scale) as i32, (y * scale) as i32),
+            m.position(),
+            m.size(),
+        )
+    })
+}
+
+/// The monitor's work area in logical coordinates as `(x, y, width, height)`.
+fn logical_work_area(monitor: &Monitor) -> (f64, f64, f64, f64) {
+    let work_area = monitor.work_area();
+    let scale = monitor.scale_factor();
+    (
+        work_area.position.x as f64 / scale,
+        work_area.position.y as f64 / scale,
+        work_area.size.width as f64 / scale,
+        work_area.size.height as f64 / scale,
+    )
+}
 
-        return Some((x, y));
+/// Moves `(x, y)` so an overlay of `width` by `height` stays in the work area.
+fn clamp_to_work_area(monitor: &Monitor, x: f64, y: f64, width: f64, height: f64) -> (f64, f64) {
+    let (area_x, area_y, area_width, area_height) = logical_work_area(monitor);
+    (
+        x.clamp(area_x, (area_x + area_width - width).max(area_x)),
+        y.clamp(area_y, (area_y + area_height - height).max(area_y)),
+    )
+}
+
+fn caret_overlay_position(app_handle: &AppHandle, width: f64, height: f64) -> Option<(f64, f64)> {
+    let (x, y, monitor) = match caret::caret_position()? {
+        tauri::Position::Physical(point) => {
+            let monitor = app_handle
+                .available_monitors()
+                .ok()?
+                .into_iter()
+                .find(|m| is_mouse_within_monitor((point.x, point.y), m.position(), m.size()))?;
+            let scale = monitor.scale_factor();
+            (point.x as f64 / scale, point.y as f64 / scale, monitor)
+        }
+        tauri::Position::Logical(point) => {
+            (point.x, point.y, monitor_at(app_handle, point.x, point.y)?)
+        }
+    };
+    Some(clamp_to_work_area(
+        &monitor,
+        x,
+        y + CARET_GAP,
+        width,
+        height,
+    ))
+}
+
+fn custom_overlay_position(
+    app_handle: &AppHandle,
+    settings: &AppSettings,
+    width: f64,
+    height: f64,
+) -> Option<(f64, f64)> {
+    let point = settings
+        .overlay_custom_positions
+        .get(&monitor_layout_key(app_handle)?)?;
+    let monitor = monitor_at(app_handle, point.x, point.y)?;
+    Some(clamp_to_work_area(
+        &monitor, point.x, point.y, width, height,
+    ))
+}
+
+/// Names the connected monitors and their arrangement, so a dragged position
+/// is only reused with the same layout.
+fn monitor_layout_key(app_handle: &AppHandle) -> Option<String> {
+    let mut monitors: Vec<String> = app_handle
+        .available_monitors()
+        .ok()?
+        .iter()
+        .map(|m| {
+            format!(
+                "{}@{},{}:{}x{}",
+                m.name().map(String::as_str).unwrap_or("unknown"),
+                m.position().x,
+                m.position().y,
+                m.size().width,
+                m.size().height
+            )
+        })
+        .collect();
+    monitors.sort();
+    Some(monitors.join(";"))
+}
+
+fn calculate_overlay_position(
+    app_handle: &AppHandle,
+    width: f64,
+    height: f64,
+) -> Option<(f64, f64)> {
+    let settings = settings::get_settings(app_handle);
+    let placed = match settings.overlay_position {
+        OverlayPosition::Caret => caret_overlay_position(app_handle, width, height),
+        OverlayPosition::Custom => custom_overlay_position(app_handle, &settings, width, height),
+        _ => None,
+    };
+    if placed.is_some() {
+        return placed;
+    }
+
+    let monitor = overlay_monitor(app_handle, &settings)?;
+    let (area_x, area_y, area_width, area_height) = logical_work_area(&monitor);
+    let left = area_x + CORNER_MARGIN;
+    let center = area_x + (area_width - width) / 2.0;
+    let right = area_x + area_width - width - CORNER_MARGIN;
+    let top = area_y + OVERLAY_TOP_OFFSET;
+    // don't subtract the overlay height it puts it too far up,
+    // only how much taller than usual it is
+    let bottom = area_y + area_height - OVERLAY_BOTTOM_OFFSET - (height - OVERLAY_HEIGHT);
+
+    Some(match settings.overlay_position {
+        OverlayPosition::Top => (center, top),
+        OverlayPosition::TopLeft => (left, top),
+        OverlayPosition::TopRight => (right, top),
+        OverlayPosition::BottomLeft => (left, bottom),
+        OverlayPosition::BottomRight => (right, bottom),
+        OverlayPosition::Bottom
+        | OverlayPosition::None
+        | OverlayPosition::Caret
+        | OverlayPosition::Custom
+        | OverlayPosition::Tray => (center, bottom),
+    })
+}
+
+/// Saves where the overlay was dragged to when it's placed by hand. Moves come
+/// in as a stream while dragging, so only the last one before a short pause
+/// is kept.
+pub fn remember_dragged_position(app_handle: &AppHandle) {
+    let generation = DRAG_GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    let app_handle = app_handle.clone();
+    std::thread::spawn(move || {
+        std::thread::sleep(DRAG_SETTLE_DELAY);
+        if DRAG_GENERATION.load(Ordering::SeqCst) == generation {
+            save_custom_position(&app_handle);
+        }
+    });
+}
+
+fn save_custom_position(app_handle: &AppHandle) {
+    let mut settings = settings::get_settings(app_handle);
+    if settings.overlay_position != OverlayPosition::Custom {
+        return;
+    }
+    let Some(overlay_window) = app_handle.get_webview_window("recording_overlay") else {
+        return;
+    };
+    let (Ok(size), Ok(position), Ok(scale)) = (
+        overlay_window.inner_size(),
+        overlay_window.outer_position(),
+        overlay_window.scale_factor(),
+    ) else {
+        return;
+    };
+    // While resized for a preview the overlay is only ever moved by us
+    if (size.width as f64 / scale - OVERLAY_WIDTH).abs() > 1.0 {
+        return;
+    }
+    let Some(layout) = monitor_layout_key(app_handle) else {
+        return;
+    };
+
+    let point = OverlayPoint {
+        x: position.x as f64 / scale,
+        y: position.y as f64 / scale,
+    };
+    if let Some((x, y)) = *PLACED_POSITION.lock().unwrap() {
+        // Still where we put it, so it wasn't dragged
+        if (point.x - x).abs() <= 1.0 && (point.y - y).abs() <= 1.0 {
+            return;
+        }
+    }
+    if settings.overlay_custom_positions.get(&layout) == Some(&point) {
+        return;
+    }
+    debug!("Overlay dragged to {:?} for layout {}", point, layout);
+    settings.overlay_custom_positions.insert(layout, point);
+    settings::write_settings(app_handle, settings);
+}
+
+/// Whether the overlay window is shown for `state`. In tray mode the state is
+/// shown on the tray icon instead.
+fn shows_window(app_handle: &AppHandle, state: TrayIconState) -> bool {
+    match settings::get_settings(app_handle).overlay_position {
+        OverlayPosition::None => false,
+        OverlayPosition::Tray => {
+            tray::set_tray_status(app_handle, Some(state));
+            false
+        }
+        _ => true,
     }
-    None
 }
 
 /// Creates the recording overlay window and keeps it hidden by default
 #[cfg(not(target_os = "macos"))]
 pub fn create_recording_overlay(app_handle: &AppHandle) {
-    if let Some((x, y)) = calculate_overlay_position(app_handle) {
+    if let Some((x, y)) = calculate_overlay_position(app_handle, OVERLAY_WIDTH, OVERLAY_HEIGHT) {
+        *PLACED_POSITION.lock().unwrap() = Some((x, y));
         match WebviewWindowBuilder::new(
             app_handle,
             "recording_overlay",
@@ -170,7 +396,8 @@ pub fn create_recording_overlay(app_handle: &AppHandle) {
 /// Creates the recording overlay panel and keeps it hidden by default (macOS)
 #[cfg(target_os = "macos")]
 pub fn create_recording_overlay(app_handle: &AppHandle) {
-    if let Some((x, y)) = calculate_overlay_position(app_handle) {
+    if let Some((x, y)) = calculate_overlay_position(app_handle, OVERLAY_WIDTH, OVERLAY_HEIGHT) {
+        *PLACED_POSITION.lock().unwrap() = Some((x, y));
         // PanelBuilder creates a Tauri window then converts it to NSPanel.
         // The window remains registered, so get_webview_window() still works.
         match PanelBuilder::<_, RecordingOverlayPanel>::new(app_handle, "recording_overlay")
@@ -207,18 +434,16 @@ pub fn create_recording_overlay(app_handle: &AppHandle) {
 /// Shows the recording overlay window with fade-in animation
 pub fn show_recording_overlay(app_handle: &AppHandle) {
     // Check if overlay should be shown based on position setting
-    let settings = settings::get_settings(app_handle);
-    if settings.overlay_position == OverlayPosition::None {
+    if !shows_window(app_handle, TrayIconState::Recording) {
         return;
     }
 
     if let Some(overlay_window) = app_handle.get_webview_window("recording_overlay") {
         // Update position before showing to prevent flicker from position changes
-        if let Some((x, y)) = calculate_overlay_position(app_handle) {
-            let _ = overlay_window
-                .set_position(tauri::Position::Logical(tauri::LogicalPosition { x, y }));
-        }
+        place_overlay(app_handle, &overlay_window, OVERLAY_WIDTH, OVERLAY_HEIGHT);
 
+        OVERLAY_GENERATION.fetch_add(1, Ordering::SeqCst);
+        set_takes_key(false);
         let _ = overlay_window.show();
 
         // On Windows, aggressively re-assert "topmost" in the native Z-order after showing
@@ -233,14 +458,14 @@ pub fn show_recording_overlay(app_handle: &AppHandle) {
 /// Shows the transcribing overlay window
 pub fn show_transcribing_overlay(app_handle: &AppHandle) {
     // Check if overlay should be shown based on position setting
-    let settings = settings::get_settings(app_handle);
-    if settings.overlay_position == OverlayPosition::None {
+    if !shows_window(app_handle, TrayIconState::Transcribing) {
         return;
     }
 
-    update_overlay_position(app_handle);
-
     if let Some(overlay_window) = app_handle.get_webview_window("recording_overlay") {
+        place_overlay(app_handle, &overlay_window, OVERLAY_WIDTH, OVERLAY_HEIGHT);
+        OVERLAY_GENERATION.fetch_add(1, Ordering::SeqCst);
+        set_takes_key(false);
         let _ = overlay_window.show();
 
         // On Windows, aggressively re-assert "topmost" in the native Z-order after showing
@@ -252,12 +477,75 @@ pub fn show_transcribing_overlay(app_handle: &AppHandle) {
     }
 }
 
+/// Shows the overlay asking the user to click to insert held text. Shown even
+/// when the overlay is otherwise turned off, since it's the only way to get
+/// the text.
+pub fn show_insert_prompt_overlay(app_handle: &AppHandle) {
+    if let Some(overlay_window) = app_handle.get_webview_window("recording_overlay") {
+        place_overlay(app_handle, &overlay_window, OVERLAY_WIDTH, OVERLAY_HEIGHT);
+        OVERLAY_GENERATION.fetch_add(1, Ordering::SeqCst);
+        set_takes_key(false);
+        let _ = overlay_window.show();
+
+        #[cfg(target_os = "windows")]
+        force_overlay_topmost(&overlay_window);
+
+        let _ = overlay_window.emit("show-overlay", "insert");
+    }
+}
+
+/// Shows a dictation's text in the overlay for review before it's pasted. Like
+/// the insert prompt it's shown even when the overlay is off, and it takes
+/// keyboard focus so the text can be accepted with Enter or edited.
+pub fn show_preview_overlay(app_handle: &AppHandle, payload: &PreviewPayload) {
+    if let Some(overlay_window) = app_handle.get_webview_window("recording_overlay") {
+        place_overlay(app_handle, &overlay_window, PREVIEW_WIDTH, PREVIEW_HEIGHT);
+        OVERLAY_GENERATION.fetch_add(1, Ordering::SeqCst);
+        set_takes_key(true);
+        let _ = overlay_window.show();
+
+        #[cfg(target_os = "windows")]
+        force_overlay_topmost(&overlay_window);
+
+        let _ = overlay_window.set_focus();
+        let _ = overlay_window.emit("show-preview", payload);
+    }
+}
+
+/// Lets the overlay panel take key focus or not. Only the preview needs it.
+#[cfg(target_os = "macos")]
+fn set_takes_key(takes_key: bool) {
+    PANEL_TAKES_KEY.store(takes_key, Ordering::SeqCst);
+}
+
+#[cfg(not(target_os = "macos"))]
+fn set_takes_key(_takes_key: bool) {}
+
+/// Sizes the overlay and moves it to its place for that size.
+fn place_overlay(
+    app_handle: &AppHandle,
+    overlay_window: &tauri::webview::WebviewWindow,
+    width: f64,
+    height: f64,
+) {
+    let _ = overlay_window.set_size(tauri::Size::Logical(tauri::LogicalSize { width, height }));
+    if let Some((x, y)) = calculate_overlay_position(app_handle, width, height) {
+        move_overlay(overlay_window, x, y);
+    }
+}
+
+/// Moves the overlay, noting that it wasn't the user who moved it.
+fn move_overlay(overlay_window: &tauri::webview::WebviewWindow, x: f64, y: f64) {
+    *PLACED_POSITION.lock().unwrap() = Some((x, y));
+    let _ = overlay_window.set_position(tauri::Position::Logical(tauri::LogicalPosition { x, y }));
+}
+
 /// Updates the overlay window position based on current settings
 pub fn update_overlay_position(app_handle: &AppHandle) {
     if let Some(overlay_window) = app_handle.get_webview_window("recording_overlay") {
-        if let Some((x, y)) = calculate_overlay_position(app_handle) {
-            let _ = overlay_window
-                .set_position(tauri::Position::Logical(tauri::LogicalPosition { x, y }));
+        if let Some((x, y)) = calculate_overlay_position(app_handle, OVERLAY_WIDTH, OVERLAY_HEIGHT)
+        {
+            move_overlay(&overlay_window, x, y);
         }
     }
 }
@@ -266,14 +554,19 @@ pub fn update_overlay_position(app_handle: &AppHandle) {
 pub fn hide_recording_overlay(app_handle: &AppHandle) {
     // Always hide the overlay regardless of settings - if setting was changed while recording,
     // we still want to hide it properly
+    tray::set_tray_status(app_handle, None);
+    set_takes_key(false);
     if let Some(overlay_window) = app_handle.get_webview_window("recording_overlay") {
         // Emit event to trigger fade-out animation
         let _ = overlay_window.emit("hide-overlay", ());
         // Hide the window after a short delay to allow animation to complete
         let window_clone = overlay_window.clone();
+        let generation = OVERLAY_GENERATION.load(Ordering::SeqCst);
         std::thread::spawn(move || {
             std::thread::sleep(std::time::Duration::from_millis(300));
-            let _ = window_clone.hide();
+            if OVERLAY_GENERATION.load(Ordering::SeqCst) == generation {
+                let _ = window_clone.hide();
+            }
         });
     }
 }
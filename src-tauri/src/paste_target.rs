@@ -0,0 +1,112 @@
+use crate::focus::{self, WindowId};
+use crate::managers::history::EntryLink;
+use crate::settings::{get_settings, FocusChangeBehavior};
+use crate::utils;
+use log::{debug, error, info};
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Manager};
+
+/// The window that had focus when dictation started, and text held back
+/// because focus moved elsewhere before it could be pasted.
+#[derive(Default)]
+pub struct PasteTarget {
+    window: Mutex<Option<WindowId>>,
+    pending: Mutex<Option<(String, Arc<EntryLink>)>>,
+}
+
+impl PasteTarget {
+    /// Remembers the focused window as the destination for the next paste.
+    pub fn lock(&self) {
+        *self.pending.lock().unwrap() = None;
+        self.remember_focus();
+    }
+
+    /// Like `lock`, but keeps any text still waiting to be inserted.
+    pub fn remember_focus(&self) {
+        *self.window.lock().unwrap() = focus::focused_window();
+    }
+
+    /// Drops text waiting to be inserted, if any.
+    pub fn discard_pending(&self) -> bool {
+        self.pending.lock().unwrap().take().is_some()
+    }
+
+    fn window(&self) -> Option<WindowId> {
+        *self.window.lock().unwrap()
+    }
+}
+
+/// Makes sure `text` goes to the window that was focused when dictation
+/// started. Returns `true` when it's ready to paste; otherwise the text is
+/// held and the overlay asks the user to click to insert it.
+pub async fn prepare(app: &AppHandle, text: &str, entry: &Arc<EntryLink>) -> bool {
+    let target = app.state::<PasteTarget>();
+    let Some(window) = target.window() else {
+        return true;
+    };
+    if focus::focused_window() == Some(window) {
+        return true;
+    }
+
+    let behavior = get_settings(app).focus_change_behavior;
+    debug!("Focus moved during dictation, handling with {:?}", behavior);
+    match behavior {
+        FocusChangeBehavior::PasteAnyway => return true,
+        FocusChangeBehavior::Refocus => {
+            // Activating polls until the window has focus, off the async workers
+            let activated =
+                tauri::async_runtime::spawn_blocking(move || focus::activate_window(window))
+                    .await
+                    .unwrap_or(false);
+            if activated {
+                return true;
+            }
+            info!("Could not refocus the original window");
+        }
+        FocusChangeBehavior::Prompt => {}
+    }
+
+    *target.pending.lock().unwrap() = Some((text.to_string(), entry.clone()));
+    utils::show_insert_prompt_overlay(app);
+    false
+}
+
+/// Pastes the held text into the original window once the user asks for it.
+pub fn insert_pending(app: &AppHandle) -> Result<(), String> {
+    let target = app.state::<PasteTarget>();
+    let (text, entry) = target
+        .pending
+        .lock()
+        .unwrap()
+        .take()
+        .ok_or("No text is waiting to be inserted")?;
+
+    utils::hide_recording_overlay(app);
+    paste_into_target(app, text, Some(entry));
+    Ok(())
+}
+
+/// Gives focus back to the remembered window and pastes `text` into it. Used
+/// when one of our own windows was clicked and may have taken focus. `entry`
+/// is the history entry the text came from, if any.
+pub fn paste_into_target(app: &AppHandle, text: String, entry: Option<Arc<EntryLink>>) {
+    let window = app.state::<PasteTarget>().window();
+    let app = app.clone();
+    std::thread::spawn(move || {
+        if let Some(window) = window {
+            if !focus::activate_window(window) {
+                info!("Could not refocus the original window, pasting into the focused one");
+            }
+        }
+
+        let app_inner = app.clone();
+        let _ = app.run_on_main_thread(move || match utils::paste(text, app_inner) {
+            Ok(()) => {
+                if let Some(entry) = entry {
+                    utils::link_last_insertion(entry);
+                }
+            }
+            Err(e) => error!("Failed to paste into the original window: {}", e),
+        });
+    });
+}
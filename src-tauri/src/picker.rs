@@ -0,0 +1,64 @@
+//! A small window for finding a past dictation and pasting it again. It's
+//! opened with a shortcut, driven from the keyboard and hidden as soon as an
+//! entry is chosen.
+
+use crate::overlay::get_monitor_with_cursor;
+use crate::paste_target::PasteTarget;
+use log::{debug, error};
+use tauri::{AppHandle, Emitter, Manager, WebviewUrl, WebviewWindowBuilder};
+
+const PICKER_LABEL: &str = "history_picker";
+const PICKER_WIDTH: f64 = 480.0;
+const PICKER_HEIGHT: f64 = 360.0;
+
+/// Creates the picker window and keeps it hidden until it's opened.
+pub fn create_history_picker(app_handle: &AppHandle) {
+    match WebviewWindowBuilder::new(
+        app_handle,
+        PICKER_LABEL,
+        WebviewUrl::App("src/picker/index.html".into()),
+    )
+    .title("History")
+    .inner_size(PICKER_WIDTH, PICKER_HEIGHT)
+    .resizable(false)
+    .maximizable(false)
+    .minimizable(false)
+    .decorations(false)
+    .always_on_top(true)
+    .skip_taskbar(true)
+    .visible(false)
+    .build()
+    {
+        Ok(_window) => debug!("History picker window created successfully (hidden)"),
+        Err(e) => error!("Failed to create history picker window: {}", e),
+    }
+}
+
+/// Opens the picker on the screen with the cursor, remembering the focused
+/// window so the chosen entry is pasted there.
+pub fn show_history_picker(app_handle: &AppHandle) {
+    let Some(window) = app_handle.get_webview_window(PICKER_LABEL) else {
+        return;
+    };
+    app_handle.state::<PasteTarget>().remember_focus();
+
+    if let Some(monitor) = get_monitor_with_cursor(app_handle) {
+        let work_area = monitor.work_area();
+        let scale = monitor.scale_factor();
+        let x = work_area.position.x as f64 / scale
+            + (work_area.size.width as f64 / scale - PICKER_WIDTH) / 2.0;
+        let y = work_area.position.y as f64 / scale
+            + (work_area.size.height as f64 / scale - PICKER_HEIGHT) / 3.0;
+        let _ = window.set_position(tauri::Position::Logical(tauri::LogicalPosition { x, y }));
+    }
+
+    let _ = window.show();
+    let _ = window.set_focus();
+    let _ = window.emit("show-history-picker", ());
+}
+
+pub fn hide_history_picker(app_handle: &AppHandle) {
+    if let Some(window) = app_handle.get_webview_window(PICKER_LABEL) {
+        let _ = window.hide();
+    }
+}
@@ -0,0 +1,119 @@
+//! Holds a finished dictation in the overlay so it can be checked before it's
+//! pasted. The overlay counts down and accepts on its own unless the user
+//! edits the text or switches the prompt; Escape or the cancel button drops
+//! the dictation.
+
+use crate::actions;
+use crate::settings::get_settings;
+use crate::utils;
+use serde::Serialize;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+use tokio::sync::oneshot;
+
+/// The text the user accepted and the prompt that produced it.
+pub struct Reviewed {
+    pub text: String,
+    pub prompt_id: Option<String>,
+}
+
+struct Pending {
+    transcription: String,
+    prompt_id: Option<String>,
+    decision: oneshot::Sender<Reviewed>,
+}
+
+#[derive(Default)]
+pub struct PastePreview {
+    pending: Mutex<Option<Pending>>,
+}
+
+#[derive(Clone, Serialize)]
+pub struct PreviewPayload {
+    pub text: String,
+    pub prompt_id: Option<String>,
+    pub countdown_secs: u32,
+}
+
+impl PastePreview {
+    /// Pastes the previewed dictation as `text`, which may have been edited.
+    pub fn accept(&self, text: String) -> Result<(), String> {
+        let pending = self
+            .pending
+            .lock()
+            .unwrap()
+            .take()
+            .ok_or("No dictation is being previewed")?;
+        let prompt_id = pending.prompt_id;
+        // The dictation task may have been dropped meanwhile, nothing to do then
+        let _ = pending.decision.send(Reviewed { text, prompt_id });
+        Ok(())
+    }
+
+    /// Drops the previewed dictation, if any.
+    pub fn cancel(&self) -> bool {
+        self.pending.lock().unwrap().take().is_some()
+    }
+
+    fn transcription(&self) -> Option<String> {
+        self.pending
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map(|pending| pending.transcription.clone())
+    }
+}
+
+/// Shows `text` in the overlay and waits for the user. Returns `None` when the
+/// preview was cancelled.
+pub async fn review(
+    app: &AppHandle,
+    transcription: &str,
+    text: String,
+    prompt_id: Option<String>,
+) -> Option<Reviewed> {
+    let (decision, receiver) = oneshot::channel();
+    *app.state::<PastePreview>().pending.lock().unwrap() = Some(Pending {
+        transcription: transcription.to_string(),
+        prompt_id: prompt_id.clone(),
+        decision,
+    });
+
+    utils::show_preview_overlay(
+        app,
+        &PreviewPayload {
+            text,
+            prompt_id,
+            countdown_secs: get_settings(app).paste_preview_countdown_secs,
+        },
+    );
+
+    // A cancelled preview drops the sender
+    receiver.await.ok()
+}
+
+/// Post-processes the previewed transcription again with another prompt, or
+/// returns it unprocessed when `prompt_id` is `None`.
+pub async fn switch_prompt(app: &AppHandle, prompt_id: Option<String>) -> Result<String, String> {
+    let preview = app.state::<PastePreview>();
+    let transcription = preview
+        .transcription()
+        .ok_or("No dictation is being previewed")?;
+
+    let text = match &prompt_id {
+        Some(id) => {
+            let mut settings = get_settings(app);
+            settings.post_process_enabled = true;
+            settings.post_process_selected_prompt_id = Some(id.clone());
+            actions::maybe_post_process_transcription(app, &settings, &transcription)
+                .await
+                .ok_or("Post-processing with this prompt failed")?
+        }
+        None => transcription,
+    };
+
+    if let Some(pending) = preview.pending.lock().unwrap().as_mut() {
+        pending.prompt_id = prompt_id;
+    }
+    Ok(text)
+}
@@ -0,0 +1,134 @@
+use super::Shared;
+use objc2::rc::Retained;
+use objc2::runtime::ProtocolObject;
+use objc2::{define_class, msg_send, AllocAnyThread, DefinedClass};
+use objc2_app_kit::{
+    NSPasteboard, NSPasteboardItem, NSPasteboardItemDataProvider, NSPasteboardType,
+    NSPasteboardTypeString,
+};
+use objc2_foundation::{
+    NSArray, NSDate, NSDefaultRunLoopMode, NSObject, NSObjectProtocol, NSRunLoop, NSString,
+};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// How often the server checks whether the pasteboard changed hands.
+const POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+/// Marks the entry as sensitive; clipboard managers that see it skip it.
+const CONCEALED_TYPE: &str = "org.nspasteboard.ConcealedType";
+
+struct ProviderIvars {
+    text: String,
+    shared: Arc<Shared>,
+}
+
+define_class!(
+    /// Hands out the text when an application reads it from the pasteboard.
+    #[unsafe(super(NSObject))]
+    #[ivars = ProviderIvars]
+    struct TextProvider;
+
+    unsafe impl NSObjectProtocol for TextProvider {}
+
+    unsafe impl NSPasteboardItemDataProvider for TextProvider {
+        #[unsafe(method(pasteboard:item:provideDataForType:))]
+        fn provide_data(
+            &self,
+            _pasteboard: Option<&NSPasteboard>,
+            item: &NSPasteboardItem,
+            r#type: &NSPasteboardType,
+        ) {
+            let ivars = self.ivars();
+            // The pasteboard keeps whatever it got the first time, so leave
+            // reads from before the keystroke empty-handed and keep the
+            // promise for the paste
+            if !ivars.shared.is_armed() {
+                return;
+            }
+            if item.setString_forType(&NSString::from_str(&ivars.text), r#type) {
+                ivars.shared.fetched();
+            }
+        }
+    }
+);
+
+impl TextProvider {
+    fn new(text: &str, shared: Arc<Shared>) -> Retained<Self> {
+        let this = Self::alloc().set_ivars(ProviderIvars {
+            text: text.to_string(),
+            shared,
+        });
+        unsafe { msg_send![super(this), init] }
+    }
+}
+
+/// Puts a promise of the text on the general pasteboard. AppKit asks for the
+/// text when an application pastes it; a thread watches for anyone else
+/// writing to the pasteboard in the meantime.
+pub(super) fn serve(text: &str, shared: Arc<Shared>) -> Result<JoinHandle<()>, String> {
+    let text = text.to_string();
+    let provider = TextProvider::new(&text, shared.clone());
+    let item = NSPasteboardItem::new();
+    let text_type = text_type_string();
+    if !item.setDataProvider_forTypes(
+        ProtocolObject::from_ref(&*provider),
+        &NSArray::from_slice(&[text_type]),
+    ) {
+        return Err("failed to promise the text".to_string());
+    }
+    item.setString_forType(&NSString::new(), &NSString::from_str(CONCEALED_TYPE));
+
+    let pasteboard = NSPasteboard::generalPasteboard();
+    pasteboard.clearContents();
+    if !pasteboard.writeObjects(&NSArray::from_retained_slice(&[
+        ProtocolObject::from_retained(item),
+    ])) {
+        return Err("failed to write to the pasteboard".to_string());
+    }
+    let owned_count = pasteboard.changeCount();
+
+    Ok(thread::spawn(move || {
+        let pasteboard = NSPasteboard::generalPasteboard();
+        while !shared.is_stopped() {
+            if pasteboard.changeCount() != owned_count {
+                shared.update(|status| status.replaced = true);
+                break;
+            }
+            thread::sleep(POLL_INTERVAL);
+        }
+
+        // Don't leave a promise behind that nobody can keep; leave the text
+        if pasteboard.changeCount() == owned_count {
+            pasteboard.clearContents();
+            pasteboard.setString_forType(&NSString::from_str(&text), text_type_string());
+        }
+        drop(provider);
+    }))
+}
+
+fn text_type_string() -> &'static NSPasteboardType {
+    unsafe { NSPasteboardTypeString }
+}
+
+/// Keeps the current thread's run loop turning until `done` returns true or
+/// `timeout` passes, and returns the time left. AppKit asks for promised data
+/// on the run loop of the thread that wrote it, which is the one waiting.
+pub(super) fn run_until(timeout: Duration, done: impl Fn() -> bool) -> Duration {
+    let deadline = Instant::now() + timeout;
+    let run_loop = NSRunLoop::currentRunLoop();
+    while !done() {
+        let now = Instant::now();
+        if now >= deadline {
+            break;
+        }
+        let slice = (deadline - now).min(Duration::from_millis(10));
+        let limit = NSDate::dateWithTimeIntervalSinceNow(slice.as_secs_f64());
+        if !run_loop.runMode_beforeDate(unsafe { NSDefaultRunLoopMode }, &limit) {
+            // Nothing to run on this thread
+            thread::sleep(slice);
+        }
+    }
+    deadline.saturating_duration_since(Instant::now())
+}
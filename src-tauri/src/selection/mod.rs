@@ -0,0 +1,189 @@
+//! Serves a temporary clipboard entry that applications fetch from us when
+//! they paste it: from our own connection to the display server on Linux, and
+//! as a promised (delayed-rendered) entry on macOS and Windows. This tells us
+//! when the text was actually pasted and whether anyone else took over the
+//! clipboard in the meantime.
+
+#[cfg(target_os = "macos")]
+mod appkit;
+#[cfg(target_os = "linux")]
+mod wayland;
+#[cfg(target_os = "windows")]
+mod win32;
+#[cfg(target_os = "linux")]
+mod x11;
+
+#[cfg(target_os = "linux")]
+use log::debug;
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+/// Offered alongside the text; clipboard managers that see it skip the entry.
+#[cfg(target_os = "linux")]
+const PASSWORD_MANAGER_HINT: &str = "x-kde-passwordManagerHint";
+
+/// Names plain text goes by, as MIME types and as X11 targets.
+#[cfg(target_os = "linux")]
+const TEXT_TYPES: &[&str] = &[
+    "text/plain;charset=utf-8",
+    "text/plain",
+    "UTF8_STRING",
+    "STRING",
+    "TEXT",
+];
+
+#[derive(Default)]
+struct Status {
+    /// Set once the paste keystroke is about to be sent. Clipboard managers
+    /// and XWayland fetch a new selection right away, which isn't a paste.
+    armed: bool,
+    pasted: bool,
+    replaced: bool,
+    stopped: bool,
+}
+
+#[derive(Default)]
+struct Shared {
+    status: Mutex<Status>,
+    changed: Condvar,
+}
+
+impl Shared {
+    fn update(&self, f: impl FnOnce(&mut Status)) {
+        f(&mut self.status.lock().unwrap());
+        self.changed.notify_all();
+    }
+
+    /// Records that an application fetched the text.
+    fn fetched(&self) {
+        self.update(|status| status.pasted |= status.armed);
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn is_armed(&self) -> bool {
+        self.status.lock().unwrap().armed
+    }
+
+    fn is_stopped(&self) -> bool {
+        let status = self.status.lock().unwrap();
+        status.stopped || status.replaced
+    }
+}
+
+/// Owns the clipboard with a piece of text until dropped.
+pub struct TemporarySelection {
+    shared: Arc<Shared>,
+    server: Option<JoinHandle<()>>,
+}
+
+impl TemporarySelection {
+    /// Takes over the clipboard with `text`, preferring Wayland's data
+    /// control protocol and falling back to X11. Fails if neither is usable,
+    /// e.g. on GNOME's Wayland session without XWayland.
+    #[cfg(target_os = "linux")]
+    pub fn offer(text: &str) -> Result<Self, String> {
+        let shared = Arc::new(Shared::default());
+
+        let server = match wayland::serve(text, shared.clone()) {
+            Ok(server) => server,
+            Err(wayland_error) => {
+                debug!("Wayland clipboard unavailable: {}", wayland_error);
+                x11::serve(text, shared.clone()).map_err(|x11_error| {
+                    format!(
+                        "No clipboard to serve from (Wayland: {}, X11: {})",
+                        wayland_error, x11_error
+                    )
+                })?
+            }
+        };
+
+        Ok(Self {
+            shared,
+            server: Some(server),
+        })
+    }
+
+    /// Takes over the clipboard with a promise of `text`.
+    #[cfg(not(target_os = "linux"))]
+    pub fn offer(text: &str) -> Result<Self, String> {
+        let shared = Arc::new(Shared::default());
+
+        #[cfg(target_os = "macos")]
+        let server = appkit::serve(text, shared.clone())?;
+        #[cfg(target_os = "windows")]
+        let server = win32::serve(text, shared.clone())?;
+
+        Ok(Self {
+            shared,
+            server: Some(server),
+        })
+    }
+
+    /// Counts fetches from now on as the paste. Call right before sending the
+    /// paste keystroke.
+    pub fn arm(&self) {
+        self.shared.update(|status| status.armed = true);
+    }
+
+    /// Waits until an application has fetched the text since `arm`. Returns `false` if
+    /// none did within `timeout`.
+    pub fn wait_until_pasted(&self, timeout: Duration) -> bool {
+        #[cfg(target_os = "macos")]
+        let timeout = appkit::run_until(timeout, || {
+            let status = self.shared.status.lock().unwrap();
+            status.pasted || status.replaced
+        });
+
+        let status = self.shared.status.lock().unwrap();
+        let (status, _) = self
+            .shared
+            .changed
+            .wait_timeout_while(status, timeout, |s| !s.pasted && !s.replaced)
+            .unwrap();
+        status.pasted
+    }
+
+    /// Whether another application has put something on the clipboard since.
+    pub fn was_replaced(&self) -> bool {
+        self.shared.status.lock().unwrap().replaced
+    }
+}
+
+impl Drop for TemporarySelection {
+    fn drop(&mut self) {
+        self.shared.update(|status| status.stopped = true);
+        if let Some(server) = self.server.take() {
+            let _ = server.join();
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn is_text_type(mime_type: &str) -> bool {
+    TEXT_TYPES.contains(&mime_type)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn selection() -> TemporarySelection {
+        TemporarySelection {
+            shared: Arc::new(Shared::default()),
+            server: None,
+        }
+    }
+
+    #[test]
+    fn fetch_before_the_keystroke_is_not_a_paste() {
+        let selection = selection();
+        // A clipboard manager picking up the new entry
+        selection.shared.fetched();
+        assert!(!selection.wait_until_pasted(Duration::from_millis(10)));
+
+        selection.arm();
+        selection.shared.fetched();
+        assert!(selection.wait_until_pasted(Duration::from_millis(10)));
+    }
+}
@@ -0,0 +1,178 @@
+use super::{is_text_type, Shared, PASSWORD_MANAGER_HINT, TEXT_TYPES};
+use log::{debug, warn};
+use std::fs::File;
+use std::io::Write;
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+use wayland_client::globals::{registry_queue_init, GlobalListContents};
+use wayland_client::protocol::{wl_registry::WlRegistry, wl_seat::WlSeat};
+use wayland_client::{event_created_child, Connection, Dispatch, QueueHandle};
+use wayland_protocols_wlr::data_control::v1::client::{
+    zwlr_data_control_device_v1::{self, ZwlrDataControlDeviceV1},
+    zwlr_data_control_manager_v1::ZwlrDataControlManagerV1,
+    zwlr_data_control_offer_v1::ZwlrDataControlOfferV1,
+    zwlr_data_control_source_v1::{self, ZwlrDataControlSourceV1},
+};
+
+/// How often the server checks for requests and for being dropped.
+const POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+struct State {
+    text: Vec<u8>,
+    shared: Arc<Shared>,
+}
+
+/// Sets the clipboard through the data control protocol, which wlroots-based
+/// compositors and KDE offer to clients without keyboard focus.
+pub(super) fn serve(text: &str, shared: Arc<Shared>) -> Result<JoinHandle<()>, String> {
+    let conn = Connection::connect_to_env().map_err(|e| e.to_string())?;
+    let (globals, mut queue) = registry_queue_init::<State>(&conn).map_err(|e| e.to_string())?;
+    let qh = queue.handle();
+
+    let manager: ZwlrDataControlManagerV1 = globals
+        .bind(&qh, 1..=1, ())
+        .map_err(|e| format!("data control protocol not supported: {}", e))?;
+    let seat: WlSeat = globals
+        .bind(&qh, 1..=1, ())
+        .map_err(|e| format!("no seat: {}", e))?;
+
+    let device = manager.get_data_device(&seat, &qh, ());
+    let source = manager.create_data_source(&qh, ());
+    for mime_type in TEXT_TYPES {
+        source.offer(mime_type.to_string());
+    }
+    source.offer(PASSWORD_MANAGER_HINT.to_string());
+    device.set_selection(Some(&source));
+
+    let mut state = State {
+        text: text.as_bytes().to_vec(),
+        shared,
+    };
+    queue
+        .roundtrip(&mut state)
+        .map_err(|e| format!("failed to set the clipboard: {}", e))?;
+
+    Ok(thread::spawn(move || {
+        while !state.shared.is_stopped() {
+            if let Err(e) = queue.roundtrip(&mut state) {
+                warn!("Lost connection to the Wayland compositor: {}", e);
+                break;
+            }
+            thread::sleep(POLL_INTERVAL);
+        }
+
+        // Still holding the clipboard means nothing was restored, leave it empty
+        source.destroy();
+        device.destroy();
+        manager.destroy();
+        let _ = conn.flush();
+    }))
+}
+
+impl Dispatch<ZwlrDataControlSourceV1, ()> for State {
+    fn event(
+        state: &mut Self,
+        _: &ZwlrDataControlSourceV1,
+        event: zwlr_data_control_source_v1::Event,
+        _: &(),
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+        match event {
+            zwlr_data_control_source_v1::Event::Send { mime_type, fd } => {
+                let data: &[u8] = if mime_type == PASSWORD_MANAGER_HINT {
+                    b"secret"
+                } else if is_text_type(&mime_type) {
+                    &state.text
+                } else {
+                    return;
+                };
+                if let Err(e) = File::from(fd).write_all(data) {
+                    warn!("Failed to send clipboard contents: {}", e);
+                    return;
+                }
+                if is_text_type(&mime_type) {
+                    debug!("Clipboard text fetched as {}", mime_type);
+                    state.shared.fetched();
+                }
+            }
+            zwlr_data_control_source_v1::Event::Cancelled => {
+                state.shared.update(|status| status.replaced = true);
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Dispatch<ZwlrDataControlDeviceV1, ()> for State {
+    fn event(
+        _: &mut Self,
+        _: &ZwlrDataControlDeviceV1,
+        event: zwlr_data_control_device_v1::Event,
+        _: &(),
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+        // Offers for the current selection, ours included, aren't needed
+        match event {
+            zwlr_data_control_device_v1::Event::Selection { id: Some(offer) }
+            | zwlr_data_control_device_v1::Event::PrimarySelection { id: Some(offer) } => {
+                offer.destroy();
+            }
+            _ => {}
+        }
+    }
+
+    event_created_child!(State, ZwlrDataControlDeviceV1, [
+        zwlr_data_control_device_v1::EVT_DATA_OFFER_OPCODE => (ZwlrDataControlOfferV1, ()),
+    ]);
+}
+
+impl Dispatch<ZwlrDataControlOfferV1, ()> for State {
+    fn event(
+        _: &mut Self,
+        _: &ZwlrDataControlOfferV1,
+        _: <ZwlrDataControlOfferV1 as wayland_client::Proxy>::Event,
+        _: &(),
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+    }
+}
+
+impl Dispatch<ZwlrDataControlManagerV1, ()> for State {
+    fn event(
+        _: &mut Self,
+        _: &ZwlrDataControlManagerV1,
+        _: <ZwlrDataControlManagerV1 as wayland_client::Proxy>::Event,
+        _: &(),
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+    }
+}
+
+impl Dispatch<WlSeat, ()> for State {
+    fn event(
+        _: &mut Self,
+        _: &WlSeat,
+        _: <WlSeat as wayland_client::Proxy>::Event,
+        _: &(),
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+    }
+}
+
+impl Dispatch<WlRegistry, GlobalListContents> for State {
+    fn event(
+        _: &mut Self,
+        _: &WlRegistry,
+        _: <WlRegistry as wayland_client::Proxy>::Event,
+        _: &GlobalListContents,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+    }
+}
@@ -0,0 +1,232 @@
+use super::Shared;
+use log::warn;
+use std::cell::RefCell;
+use std::sync::{mpsc, Arc};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+use windows::core::{w, PCWSTR};
+use windows::Win32::Foundation::{GlobalFree, HANDLE, HWND, LPARAM, LRESULT, WPARAM};
+use windows::Win32::System::DataExchange::{
+    CloseClipboard, EmptyClipboard, GetClipboardOwner, OpenClipboard, RegisterClipboardFormatW,
+    SetClipboardData,
+};
+use windows::Win32::System::LibraryLoader::GetModuleHandleW;
+use windows::Win32::System::Memory::{GlobalAlloc, GlobalLock, GlobalUnlock, GMEM_MOVEABLE};
+use windows::Win32::System::Ole::CF_UNICODETEXT;
+use windows::Win32::UI::WindowsAndMessaging::{
+    CreateWindowExW, DefWindowProcW, DestroyWindow, DispatchMessageW, MsgWaitForMultipleObjects,
+    PeekMessageW, RegisterClassW, HWND_MESSAGE, MSG, PM_REMOVE, QS_ALLINPUT, WINDOW_EX_STYLE,
+    WINDOW_STYLE, WM_DESTROYCLIPBOARD, WM_RENDERALLFORMATS, WM_RENDERFORMAT, WNDCLASSW,
+};
+
+/// How often the server checks for being dropped.
+const POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+/// How long to wait for the server thread to take over the clipboard.
+const START_TIMEOUT: Duration = Duration::from_secs(1);
+
+const WINDOW_CLASS: PCWSTR = w!("AleFlowTemporarySelection");
+
+/// Registered formats that keep the entry out of clipboard history and away
+/// from clipboard monitors. Any data in them counts; zero is customary.
+const EXCLUSION_FORMATS: [PCWSTR; 2] = [
+    w!("CanIncludeInClipboardHistory"),
+    w!("ExcludeClipboardContentFromMonitorProcessing"),
+];
+
+/// What the window procedure hands out, on the server thread.
+struct Served {
+    text: Vec<u16>,
+    shared: Arc<Shared>,
+}
+
+thread_local! {
+    static SERVED: RefCell<Option<Served>> = const { RefCell::new(None) };
+}
+
+/// Takes over the clipboard with a promise of the text from a hidden window.
+/// Windows asks the window for the text when an application pastes it.
+pub(super) fn serve(text: &str, shared: Arc<Shared>) -> Result<JoinHandle<()>, String> {
+    let text: Vec<u16> = text.encode_utf16().chain(Some(0)).collect();
+    let (ready_tx, ready_rx) = mpsc::channel();
+
+    let server = thread::spawn(move || {
+        SERVED.with(|served| {
+            *served.borrow_mut() = Some(Served {
+                text,
+                shared: shared.clone(),
+            })
+        });
+        let window = match take_clipboard() {
+            Ok(window) => window,
+            Err(e) => {
+                let _ = ready_tx.send(Err(e));
+                return;
+            }
+        };
+        let _ = ready_tx.send(Ok(()));
+
+        while !shared.is_stopped() {
+            let mut msg = MSG::default();
+            unsafe {
+                while PeekMessageW(&mut msg, None, 0, 0, PM_REMOVE).as_bool() {
+                    DispatchMessageW(&msg);
+                }
+                MsgWaitForMultipleObjects(
+                    None,
+                    false,
+                    POLL_INTERVAL.as_millis() as u32,
+                    QS_ALLINPUT,
+                );
+            }
+        }
+
+        // Destroying the window gives up the clipboard if we still own it
+        unsafe {
+            let _ = DestroyWindow(window);
+        }
+    });
+
+    match ready_rx.recv_timeout(START_TIMEOUT) {
+        Ok(Ok(())) => Ok(server),
+        Ok(Err(e)) => Err(e),
+        Err(_) => Err("clipboard thread did not start".to_string()),
+    }
+}
+
+/// Creates the hidden window and puts a delayed-rendered text entry on the
+/// clipboard with it as the owner.
+fn take_clipboard() -> Result<HWND, String> {
+    unsafe {
+        let instance = GetModuleHandleW(None).map_err(|e| e.to_string())?;
+        let class = WNDCLASSW {
+            lpfnWndProc: Some(window_proc),
+            hInstance: instance.into(),
+            lpszClassName: WINDOW_CLASS,
+            ..Default::default()
+        };
+        // Fails harmlessly once the class exists from an earlier paste
+        RegisterClassW(&class);
+
+        let window = CreateWindowExW(
+            WINDOW_EX_STYLE::default(),
+            WINDOW_CLASS,
+            PCWSTR::null(),
+            WINDOW_STYLE::default(),
+            0,
+            0,
+            0,
+            0,
+            Some(HWND_MESSAGE),
+            None,
+            Some(instance.into()),
+            None,
+        )
+        .map_err(|e| e.to_string())?;
+
+        if let Err(e) = OpenClipboard(Some(window)) {
+            let _ = DestroyWindow(window);
+            return Err(e.to_string());
+        }
+        let result = EmptyClipboard()
+            .and_then(|()| SetClipboardData(CF_UNICODETEXT.0 as u32, None))
+            .map_err(|e| e.to_string());
+        if result.is_ok() {
+            for name in EXCLUSION_FORMATS {
+                let format = RegisterClipboardFormatW(name);
+                if format == 0 {
+                    continue;
+                }
+                if let Err(e) = set_data(format, &0u32.to_ne_bytes()) {
+                    warn!("Failed to exclude the text from clipboard history: {}", e);
+                }
+            }
+        }
+        let _ = CloseClipboard();
+
+        match result {
+            Ok(_) => Ok(window),
+            Err(e) => {
+                let _ = DestroyWindow(window);
+                Err(e)
+            }
+        }
+    }
+}
+
+unsafe extern "system" fn window_proc(
+    window: HWND,
+    message: u32,
+    wparam: WPARAM,
+    lparam: LPARAM,
+) -> LRESULT {
+    match message {
+        // An application is reading the text, with the clipboard already open
+        WM_RENDERFORMAT if wparam.0 == CF_UNICODETEXT.0 as usize => {
+            SERVED.with(|served| {
+                let served = served.borrow();
+                let Some(served) = served.as_ref() else {
+                    return;
+                };
+                // Windows only asks once, so leave reads from before the
+                // keystroke empty-handed and keep the promise for the paste
+                if !served.shared.is_armed() {
+                    return;
+                }
+                match render(&served.text) {
+                    Ok(()) => served.shared.fetched(),
+                    Err(e) => warn!("Failed to hand out the clipboard text: {}", e),
+                }
+            });
+            LRESULT(0)
+        }
+        // Going away while still owning the clipboard; leave the text on it
+        WM_RENDERALLFORMATS => {
+            if OpenClipboard(Some(window)).is_ok() {
+                if GetClipboardOwner().ok() == Some(window) {
+                    SERVED.with(|served| {
+                        if let Some(served) = served.borrow().as_ref() {
+                            let _ = render(&served.text);
+                        }
+                    });
+                }
+                let _ = CloseClipboard();
+            }
+            LRESULT(0)
+        }
+        // Someone else emptied the clipboard to put something on it
+        WM_DESTROYCLIPBOARD => {
+            SERVED.with(|served| {
+                if let Some(served) = served.borrow().as_ref() {
+                    served.shared.update(|status| status.replaced = true);
+                }
+            });
+            LRESULT(0)
+        }
+        _ => DefWindowProcW(window, message, wparam, lparam),
+    }
+}
+
+fn render(text: &[u16]) -> Result<(), String> {
+    let bytes: Vec<u8> = text.iter().flat_map(|unit| unit.to_ne_bytes()).collect();
+    unsafe { set_data(CF_UNICODETEXT.0 as u32, &bytes) }
+}
+
+/// Copies `bytes` into a global memory block owned by the open clipboard.
+unsafe fn set_data(format: u32, bytes: &[u8]) -> Result<(), String> {
+    let memory = GlobalAlloc(GMEM_MOVEABLE, bytes.len()).map_err(|e| e.to_string())?;
+    let target = GlobalLock(memory) as *mut u8;
+    if target.is_null() {
+        let _ = GlobalFree(Some(memory));
+        return Err("failed to lock clipboard memory".to_string());
+    }
+    std::ptr::copy_nonoverlapping(bytes.as_ptr(), target, bytes.len());
+    let _ = GlobalUnlock(memory);
+
+    // The clipboard owns the memory once it's set
+    if let Err(e) = SetClipboardData(format, Some(HANDLE(memory.0))) {
+        let _ = GlobalFree(Some(memory));
+        return Err(e.to_string());
+    }
+    Ok(())
+}
@@ -0,0 +1,192 @@
+use super::{Shared, PASSWORD_MANAGER_HINT, TEXT_TYPES};
+use log::warn;
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+use x11rb::connection::{Connection, RequestConnection};
+use x11rb::protocol::xproto::{
+    Atom, AtomEnum, ConnectionExt as _, CreateWindowAux, EventMask, PropMode, SelectionNotifyEvent,
+    SelectionRequestEvent, Window, WindowClass, SELECTION_NOTIFY_EVENT,
+};
+use x11rb::protocol::Event;
+use x11rb::rust_connection::RustConnection;
+use x11rb::wrapper::ConnectionExt as _;
+use x11rb::{COPY_DEPTH_FROM_PARENT, CURRENT_TIME, NONE};
+
+/// How often the server checks for requests and for being dropped.
+const POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+/// Property payload overhead of a ChangeProperty request.
+const CHANGE_PROPERTY_HEADER: usize = 24;
+
+struct Atoms {
+    clipboard: Atom,
+    targets: Atom,
+    utf8_string: Atom,
+    text: Atom,
+    hint: Atom,
+    text_targets: Vec<Atom>,
+}
+
+impl Atoms {
+    fn intern(conn: &RustConnection) -> Result<Self, String> {
+        let intern = |name: &str| -> Result<Atom, String> {
+            conn.intern_atom(false, name.as_bytes())
+                .map_err(|e| e.to_string())?
+                .reply()
+                .map(|reply| reply.atom)
+                .map_err(|e| e.to_string())
+        };
+
+        let text_targets = TEXT_TYPES
+            .iter()
+            .map(|name| intern(name))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            clipboard: intern("CLIPBOARD")?,
+            targets: intern("TARGETS")?,
+            utf8_string: intern("UTF8_STRING")?,
+            text: intern("TEXT")?,
+            hint: intern(PASSWORD_MANAGER_HINT)?,
+            text_targets,
+        })
+    }
+}
+
+/// Takes ownership of the CLIPBOARD selection with a hidden window and
+/// answers conversion requests for it.
+pub(super) fn serve(text: &str, shared: Arc<Shared>) -> Result<JoinHandle<()>, String> {
+    let (conn, screen_num) = x11rb::connect(None).map_err(|e| e.to_string())?;
+    let atoms = Atoms::intern(&conn)?;
+
+    // Larger text would need the INCR protocol, which the fallback handles
+    if text.len() + CHANGE_PROPERTY_HEADER > conn.maximum_request_bytes() {
+        return Err("text too large for a single property".to_string());
+    }
+
+    let root = conn.setup().roots[screen_num].root;
+    let window = conn.generate_id().map_err(|e| e.to_string())?;
+    conn.create_window(
+        COPY_DEPTH_FROM_PARENT,
+        window,
+        root,
+        0,
+        0,
+        1,
+        1,
+        0,
+        WindowClass::INPUT_ONLY,
+        x11rb::COPY_FROM_PARENT,
+        &CreateWindowAux::new(),
+    )
+    .map_err(|e| e.to_string())?;
+    conn.set_selection_owner(window, atoms.clipboard, CURRENT_TIME)
+        .map_err(|e| e.to_string())?;
+
+    let owner = conn
+        .get_selection_owner(atoms.clipboard)
+        .map_err(|e| e.to_string())?
+        .reply()
+        .map_err(|e| e.to_string())?
+        .owner;
+    if owner != window {
+        let _ = conn.destroy_window(window);
+        let _ = conn.flush();
+        return Err("failed to take ownership of the clipboard".to_string());
+    }
+
+    let text = text.as_bytes().to_vec();
+    Ok(thread::spawn(move || {
+        while !shared.is_stopped() {
+            match conn.poll_for_event() {
+                Ok(Some(Event::SelectionRequest(request))) => {
+                    if let Err(e) = answer(&conn, &atoms, &request, &text, &shared) {
+                        warn!("Failed to answer a clipboard request: {}", e);
+                    }
+                }
+                Ok(Some(Event::SelectionClear(event))) if event.selection == atoms.clipboard => {
+                    shared.update(|status| status.replaced = true);
+                }
+                Ok(Some(_)) => {}
+                Ok(None) => thread::sleep(POLL_INTERVAL),
+                Err(e) => {
+                    warn!("Lost connection to the X server: {}", e);
+                    break;
+                }
+            }
+        }
+
+        // Destroying the window gives up the selection if we still own it
+        let _ = conn.destroy_window(window);
+        let _ = conn.flush();
+    }))
+}
+
+fn answer(
+    conn: &RustConnection,
+    atoms: &Atoms,
+    request: &SelectionRequestEvent,
+    text: &[u8],
+    shared: &Shared,
+) -> Result<(), String> {
+    // Obsolete clients leave the property unset and expect the target's name
+    let property = if request.property == NONE {
+        request.target
+    } else {
+        request.property
+    };
+    let requestor: Window = request.requestor;
+
+    let stored = if request.selection != atoms.clipboard {
+        false
+    } else if request.target == atoms.targets {
+        let mut targets = vec![atoms.targets, atoms.hint];
+        targets.extend(&atoms.text_targets);
+        conn.change_property32(
+            PropMode::REPLACE,
+            requestor,
+            property,
+            AtomEnum::ATOM,
+            &targets,
+        )
+        .map_err(|e| e.to_string())?;
+        true
+    } else if request.target == atoms.hint {
+        conn.change_property8(
+            PropMode::REPLACE,
+            requestor,
+            property,
+            request.target,
+            b"secret",
+        )
+        .map_err(|e| e.to_string())?;
+        true
+    } else if atoms.text_targets.contains(&request.target) {
+        // TEXT lets the owner pick the encoding
+        let kind = if request.target == atoms.text {
+            atoms.utf8_string
+        } else {
+            request.target
+        };
+        conn.change_property8(PropMode::REPLACE, requestor, property, kind, text)
+            .map_err(|e| e.to_string())?;
+        shared.fetched();
+        true
+    } else {
+        false
+    };
+
+    let notify = SelectionNotifyEvent {
+        response_type: SELECTION_NOTIFY_EVENT,
+        sequence: 0,
+        time: request.time,
+        requestor,
+        selection: request.selection,
+        target: request.target,
+        property: if stored { property } else { NONE },
+    };
+    conn.send_event(false, requestor, EventMask::NO_EVENT, notify)
+        .map_err(|e| e.to_string())?;
+    conn.flush().map_err(|e| e.to_string())
+}
@@ -1,4 +1,5 @@
-use log::{debug, warn};
+use crate::audio_toolkit::LoopbackCapture;
+use log::{debug, info, warn};
 use serde::de::{self, Visitor};
 use serde::{Deserialize, Deserializer, Serialize};
 use specta::Type;
@@ -9,6 +10,18 @@ use tauri_plugin_store::StoreExt;
 pub const APPLE_INTELLIGENCE_PROVIDER_ID: &str = "apple_intelligence";
 pub const APPLE_INTELLIGENCE_DEFAULT_MODEL_ID: &str = "Apple Intelligence";
 
+/// Upper bound for VAD prefill and hangover, in 30 ms frames.
+pub const MAX_VAD_FRAMES: u32 = 100;
+
+pub const MAX_PRE_ROLL_MS: u32 = 2000;
+
+pub const MAX_TYPING_DELAY_MS: u32 = 100;
+pub const MAX_TYPING_CHUNK_SIZE: u32 = 500;
+pub const MAX_PASTE_PREVIEW_COUNTDOWN_SECS: u32 = 30;
+
+/// Key in `microphone_capture` for whichever device is the system default.
+pub const DEFAULT_MICROPHONE_KEY: &str = "default";
+
 #[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, Type)]
 #[serde(rename_all = "lowercase")]
 pub enum LogLevel {
@@ -100,11 +113,82 @@ pub struct PostProcessProvider {
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Type)]
-#[serde(rename_all = "lowercase")]
+#[serde(rename_all = "snake_case")]
 pub enum OverlayPosition {
     None,
     Top,
     Bottom,
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+    // Next to the text caret, or at the bottom when it can't be found
+    Caret,
+    // Wherever the overlay was dragged to, per monitor layout
+    Custom,
+    // Status shown in the tray icon's tooltip instead of a window
+    Tray,
+}
+
+// Top-left corner of the overlay in logical screen coordinates
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Type)]
+pub struct OverlayPoint {
+    pub x: f64,
+    pub y: f64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Type, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum VadEngine {
+    #[default]
+    Silero,
+    Energy,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Type, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum CaptureSource {
+    #[default]
+    Microphone,
+    SystemAudio,
+    Mixed,
+}
+
+/// How a microphone is captured. Empty or unset fields use the device defaults.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Type)]
+pub struct MicrophoneCaptureSettings {
+    #[serde(default)]
+    pub channel_mix: Vec<f32>,
+    #[serde(default)]
+    pub sample_rate: Option<u32>,
+    #[serde(default)]
+    pub buffer_size: Option<u32>,
+}
+
+impl MicrophoneCaptureSettings {
+    pub fn validate(&self) -> Result<(), String> {
+        if self
+            .channel_mix
+            .iter()
+            .any(|weight| !(0.0..=1.0).contains(weight))
+        {
+            return Err("Channel weights must be between 0 and 1".to_string());
+        }
+        if !self.channel_mix.is_empty() && self.channel_mix.iter().all(|&weight| weight == 0.0) {
+            return Err("At least one channel must be mixed in".to_string());
+        }
+        if let Some(rate) = self.sample_rate {
+            if !(8_000..=192_000).contains(&rate) {
+                return Err("Sample rate must be between 8000 and 192000 Hz".to_string());
+            }
+        }
+        if let Some(frames) = self.buffer_size {
+            if !(16..=8192).contains(&frames) {
+                return Err("Buffer size must be between 16 and 8192 frames".to_string());
+            }
+        }
+        Ok(())
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Type)]
@@ -137,6 +221,34 @@ pub enum ClipboardHandling {
     CopyToClipboard,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Type, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum FocusChangeBehavior {
+    PasteAnyway,
+    #[default]
+    Refocus,
+    Prompt,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Type)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum OutputSinkKind {
+    File { path: String },
+    Webhook { url: String },
+    Pipe { path: String },
+    Stdout,
+    Command { command: String },
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Type)]
+pub struct OutputSink {
+    pub id: String,
+    pub enabled: bool,
+    pub kind: OutputSinkKind,
+    #[serde(default)]
+    pub prompt_id: Option<String>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Type)]
 #[serde(rename_all = "snake_case")]
 pub enum RecordingRetentionPeriod {
@@ -222,6 +334,8 @@ impl SoundTheme {
 /* still useful for composing the initial JSON in the store ------------- */
 #[derive(Serialize, Deserialize, Debug, Clone, Type)]
 pub struct AppSettings {
+    #[serde(default)]
+    pub settings_version: u32,
     pub bindings: HashMap<String, ShortcutBinding>,
     pub push_to_talk: bool,
     pub audio_feedback: bool,
@@ -240,10 +354,50 @@ pub struct AppSettings {
     #[serde(default = "default_always_on_microphone")]
     pub always_on_microphone: bool,
     #[serde(default)]
+    pub hands_free_enabled: bool,
+    #[serde(default = "default_hands_free_silence_ms")]
+    pub hands_free_silence_ms: u32,
+    #[serde(default = "default_hands_free_max_duration_secs")]
+    pub hands_free_max_duration_secs: u32,
+    #[serde(default)]
+    pub wake_word_enabled: bool,
+    #[serde(default)]
+    pub vad_engine: VadEngine,
+    #[serde(default = "default_vad_threshold")]
+    pub vad_threshold: f32,
+    #[serde(default = "default_vad_prefill_frames")]
+    pub vad_prefill_frames: u32,
+    #[serde(default = "default_vad_hangover_frames")]
+    pub vad_hangover_frames: u32,
+    #[serde(default = "default_vad_onset_frames")]
+    pub vad_onset_frames: u32,
+    #[serde(default = "default_vad_trim_enabled")]
+    pub vad_trim_enabled: bool,
+    #[serde(default)]
+    pub dsp_high_pass_enabled: bool,
+    #[serde(default)]
+    pub dsp_denoise_enabled: bool,
+    #[serde(default)]
+    pub dsp_agc_enabled: bool,
+    #[serde(default = "default_dsp_clipping_detection_enabled")]
+    pub dsp_clipping_detection_enabled: bool,
+    #[serde(default)]
+    pub dsp_ab_capture_enabled: bool,
+    #[serde(default = "default_pre_roll_ms")]
+    pub pre_roll_ms: u32,
+    #[serde(default)]
     pub selected_microphone: Option<String>,
     #[serde(default)]
     pub clamshell_microphone: Option<String>,
     #[serde(default)]
+    pub microphone_capture: HashMap<String, MicrophoneCaptureSettings>,
+    #[serde(default)]
+    pub fallback_microphones: Vec<String>,
+    #[serde(default)]
+    pub capture_source: CaptureSource,
+    #[serde(default)]
+    pub diarization_enabled: bool,
+    #[serde(default)]
     pub selected_output_device: Option<String>,
     #[serde(default = "default_translate_to_english")]
     pub translate_to_english: bool,
@@ -251,6 +405,12 @@ pub struct AppSettings {
     pub selected_language: String,
     #[serde(default = "default_overlay_position")]
     pub overlay_position: OverlayPosition,
+    // Monitor the overlay is shown on by name; `None` follows the mouse
+    #[serde(default)]
+    pub overlay_monitor: Option<String>,
+    // Dragged overlay positions keyed by the monitor layout they were made in
+    #[serde(default)]
+    pub overlay_custom_positions: HashMap<String, OverlayPoint>,
     #[serde(default = "default_debug_mode")]
     pub debug_mode: bool,
     #[serde(default = "default_log_level")]
@@ -268,13 +428,28 @@ pub struct AppSettings {
     #[serde(default)]
     pub paste_method: PasteMethod,
     #[serde(default)]
+    pub typing_delay_ms: u32,
+    #[serde(default = "default_typing_chunk_size")]
+    pub typing_chunk_size: u32,
+    #[serde(default)]
     pub clipboard_handling: ClipboardHandling,
+    #[serde(default)]
+    pub focus_change_behavior: FocusChangeBehavior,
+    #[serde(default)]
+    pub output_sinks: Vec<OutputSink>,
+    #[serde(default)]
+    pub paste_preview_enabled: bool,
+    // Seconds before a preview pastes on its own; 0 waits for Enter
+    #[serde(default = "default_paste_preview_countdown_secs")]
+    pub paste_preview_countdown_secs: u32,
     #[serde(default = "default_post_process_enabled")]
     pub post_process_enabled: bool,
     #[serde(default = "default_post_process_provider_id")]
     pub post_process_provider_id: String,
     #[serde(default = "default_post_process_providers")]
     pub post_process_providers: Vec<PostProcessProvider>,
+    // API keys are kept by `SecretsManager`. This map only holds keys from older
+    // versions until they are migrated, and placeholders when sent to the frontend.
     #[serde(default = "default_post_process_api_keys")]
     pub post_process_api_keys: HashMap<String, String>,
     #[serde(default = "default_post_process_models")]
@@ -299,6 +474,50 @@ fn default_always_on_microphone() -> bool {
     false
 }
 
+fn default_hands_free_silence_ms() -> u32 {
+    1200
+}
+
+fn default_hands_free_max_duration_secs() -> u32 {
+    60
+}
+
+fn default_vad_threshold() -> f32 {
+    0.3
+}
+
+fn default_vad_prefill_frames() -> u32 {
+    15
+}
+
+fn default_vad_hangover_frames() -> u32 {
+    15
+}
+
+fn default_vad_onset_frames() -> u32 {
+    2
+}
+
+fn default_vad_trim_enabled() -> bool {
+    true
+}
+
+fn default_dsp_clipping_detection_enabled() -> bool {
+    true
+}
+
+fn default_pre_roll_ms() -> u32 {
+    300
+}
+
+fn default_typing_chunk_size() -> u32 {
+    50
+}
+
+fn default_paste_preview_countdown_secs() -> u32 {
+    3
+}
+
 fn default_translate_to_english() -> bool {
     false
 }
@@ -450,8 +669,59 @@ fn default_post_process_prompts() -> Vec<LLMPrompt> {
     }]
 }
 
-fn ensure_post_process_defaults(settings: &mut AppSettings) -> bool {
-    let mut changed = false;
+/// Settings migrations. Entry `i` upgrades settings from version `i` to `i + 1`,
+/// so new migrations must only ever be appended. Settings stored before the
+/// version field existed deserialize as version 0 and run every migration.
+static SETTINGS_MIGRATIONS: &[fn(&mut AppSettings)] = &[
+    add_missing_default_bindings,
+    ensure_post_process_defaults,
+    // Picks up the meeting and undo shortcuts added since
+    add_missing_default_bindings,
+    // Picks up the re-paste and history picker shortcuts
+    add_missing_default_bindings,
+];
+
+pub fn current_settings_version() -> u32 {
+    SETTINGS_MIGRATIONS.len() as u32
+}
+
+/// Brings `settings` up to the current version. Returns true if anything ran.
+pub fn migrate_settings(settings: &mut AppSettings) -> bool {
+    let version_before = settings.settings_version;
+    let latest = current_settings_version();
+
+    if version_before >= latest {
+        if version_before > latest {
+            warn!(
+                "Settings version {} is newer than this build supports ({})",
+                version_before, latest
+            );
+        }
+        return false;
+    }
+
+    for migration in &SETTINGS_MIGRATIONS[version_before as usize..] {
+        migration(settings);
+    }
+    settings.settings_version = latest;
+
+    info!(
+        "Settings migrated from version {} to {}",
+        version_before, latest
+    );
+    true
+}
+
+fn add_missing_default_bindings(settings: &mut AppSettings) {
+    for (key, value) in get_default_settings().bindings {
+        if !settings.bindings.contains_key(&key) {
+            debug!("Adding missing binding: {}", key);
+            settings.bindings.insert(key, value);
+        }
+    }
+}
+
+fn ensure_post_process_defaults(settings: &mut AppSettings) {
     for provider in default_post_process_providers() {
         if settings
             .post_process_providers
@@ -459,34 +729,27 @@ fn ensure_post_process_defaults(settings: &mut AppSettings) -> bool {
             .all(|existing| existing.id != provider.id)
         {
             settings.post_process_providers.push(provider.clone());
-            changed = true;
         }
 
-        if !settings.post_process_api_keys.contains_key(&provider.id) {
-            settings
-                .post_process_api_keys
-                .insert(provider.id.clone(), String::new());
-            changed = true;
-        }
+        settings
+            .post_process_api_keys
+            .entry(provider.id.clone())
+            .or_default();
 
         let default_model = default_model_for_provider(&provider.id);
         match settings.post_process_models.get_mut(&provider.id) {
             Some(existing) => {
                 if existing.is_empty() && !default_model.is_empty() {
                     *existing = default_model.clone();
-                    changed = true;
                 }
             }
             None => {
                 settings
                     .post_process_models
                     .insert(provider.id.clone(), default_model);
-                changed = true;
             }
         }
     }
-
-    changed
 }
 
 pub const SETTINGS_STORE_PATH: &str = "settings_store.json";
@@ -512,6 +775,62 @@ pub fn get_default_settings() -> AppSettings {
             current_binding: default_shortcut.to_string(),
         },
     );
+    #[cfg(target_os = "macos")]
+    let default_meeting_shortcut = "option+shift+m";
+    #[cfg(not(target_os = "macos"))]
+    let default_meeting_shortcut = "ctrl+alt+m";
+    bindings.insert(
+        "meeting".to_string(),
+        ShortcutBinding {
+            id: "meeting".to_string(),
+            name: "Meeting".to_string(),
+            description: "Records and transcribes a meeting until pressed again.".to_string(),
+            default_binding: default_meeting_shortcut.to_string(),
+            current_binding: default_meeting_shortcut.to_string(),
+        },
+    );
+    #[cfg(target_os = "macos")]
+    let default_undo_shortcut = "option+shift+z";
+    #[cfg(not(target_os = "macos"))]
+    let default_undo_shortcut = "ctrl+alt+z";
+    bindings.insert(
+        "undo_last_dictation".to_string(),
+        ShortcutBinding {
+            id: "undo_last_dictation".to_string(),
+            name: "Undo Last Dictation".to_string(),
+            description: "Removes the text of the last dictation again.".to_string(),
+            default_binding: default_undo_shortcut.to_string(),
+            current_binding: default_undo_shortcut.to_string(),
+        },
+    );
+    #[cfg(target_os = "macos")]
+    let default_repaste_shortcut = "option+shift+v";
+    #[cfg(not(target_os = "macos"))]
+    let default_repaste_shortcut = "ctrl+alt+v";
+    bindings.insert(
+        "repaste_last".to_string(),
+        ShortcutBinding {
+            id: "repaste_last".to_string(),
+            name: "Re-paste Last Dictation".to_string(),
+            description: "Pastes the most recent dictation again.".to_string(),
+            default_binding: default_repaste_shortcut.to_string(),
+            current_binding: default_repaste_shortcut.to_string(),
+        },
+    );
+    #[cfg(target_os = "macos")]
+    let default_picker_shortcut = "option+shift+h";
+    #[cfg(not(target_os = "macos"))]
+    let default_picker_shortcut = "ctrl+alt+h";
+    bindings.insert(
+        "history_picker".to_string(),
+        ShortcutBinding {
+            id: "history_picker".to_string(),
+            name: "History Picker".to_string(),
+            description: "Opens a searchable list of recent dictations to paste.".to_string(),
+            default_binding: default_picker_shortcut.to_string(),
+            current_binding: default_picker_shortcut.to_string(),
+        },
+    );
     bindings.insert(
         "cancel".to_string(),
         ShortcutBinding {
@@ -524,6 +843,7 @@ pub fn get_default_settings() -> AppSettings {
     );
 
     AppSettings {
+        settings_version: current_settings_version(),
         bindings,
         push_to_talk: true,
         audio_feedback: false,
@@ -534,12 +854,34 @@ pub fn get_default_settings() -> AppSettings {
         update_checks_enabled: default_update_checks_enabled(),
         selected_model: "".to_string(),
         always_on_microphone: false,
+        hands_free_enabled: false,
+        hands_free_silence_ms: default_hands_free_silence_ms(),
+        hands_free_max_duration_secs: default_hands_free_max_duration_secs(),
+        wake_word_enabled: false,
+        vad_engine: VadEngine::default(),
+        vad_threshold: default_vad_threshold(),
+        vad_prefill_frames: default_vad_prefill_frames(),
+        vad_hangover_frames: default_vad_hangover_frames(),
+        vad_onset_frames: default_vad_onset_frames(),
+        vad_trim_enabled: default_vad_trim_enabled(),
+        dsp_high_pass_enabled: false,
+        dsp_denoise_enabled: false,
+        dsp_agc_enabled: false,
+        dsp_clipping_detection_enabled: default_dsp_clipping_detection_enabled(),
+        dsp_ab_capture_enabled: false,
+        pre_roll_ms: default_pre_roll_ms(),
         selected_microphone: None,
         clamshell_microphone: None,
+        microphone_capture: HashMap::new(),
+        fallback_microphones: Vec::new(),
+        capture_source: CaptureSource::default(),
+        diarization_enabled: false,
         selected_output_device: None,
         translate_to_english: false,
         selected_language: "auto".to_string(),
         overlay_position: default_overlay_position(),
+        overlay_monitor: None,
+        overlay_custom_positions: HashMap::new(),
         debug_mode: false,
         log_level: default_log_level(),
         custom_words: Vec::new(),
@@ -548,7 +890,13 @@ pub fn get_default_settings() -> AppSettings {
         history_limit: default_history_limit(),
         recording_retention_period: default_recording_retention_period(),
         paste_method: PasteMethod::default(),
+        typing_delay_ms: 0,
+        typing_chunk_size: default_typing_chunk_size(),
         clipboard_handling: ClipboardHandling::default(),
+        focus_change_behavior: FocusChangeBehavior::default(),
+        output_sinks: Vec::new(),
+        paste_preview_enabled: false,
+        paste_preview_countdown_secs: default_paste_preview_countdown_secs(),
         post_process_enabled: default_post_process_enabled(),
         post_process_provider_id: default_post_process_provider_id(),
         post_process_providers: default_post_process_providers(),
@@ -594,25 +942,8 @@ pub fn load_or_create_app_settings(app: &AppHandle) -> AppSettings {
     let mut settings = if let Some(settings_value) = store.get("settings") {
         // Parse the entire settings object
         match serde_json::from_value::<AppSettings>(settings_value) {
-            Ok(mut settings) => {
+            Ok(settings) => {
                 debug!("Found existing settings: {:?}", settings);
-                let default_settings = get_default_settings();
-                let mut updated = false;
-
-                // Merge default bindings into existing settings
-                for (key, value) in default_settings.bindings {
-                    if !settings.bindings.contains_key(&key) {
-                        debug!("Adding missing binding: {}", key);
-                        settings.bindings.insert(key, value);
-                        updated = true;
-                    }
-                }
-
-                if updated {
-                    debug!("Settings updated with new bindings");
-                    store.set("settings", serde_json::to_value(&settings).unwrap());
-                }
-
                 settings
             }
             Err(e) => {
@@ -629,7 +960,7 @@ pub fn load_or_create_app_settings(app: &AppHandle) -> AppSettings {
         default_settings
     };
 
-    if ensure_post_process_defaults(&mut settings) {
+    if migrate_settings(&mut settings) {
         store.set("settings", serde_json::to_value(&settings).unwrap());
     }
 
@@ -653,7 +984,7 @@ pub fn get_settings(app: &AppHandle) -> AppSettings {
         default_settings
     };
 
-    if ensure_post_process_defaults(&mut settings) {
+    if migrate_settings(&mut settings) {
         store.set("settings", serde_json::to_value(&settings).unwrap());
     }
 
@@ -691,3 +1022,278 @@ pub fn get_recording_retention_period(app: &AppHandle) -> RecordingRetentionPeri
     let settings = get_settings(app);
     settings.recording_retention_period
 }
+
+/* portable settings files ---------------------------------------------- */
+
+/// Identifies files written by `export_settings`.
+pub const SETTINGS_EXPORT_FORMAT: &str = "aleflow-settings";
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SettingsExport {
+    pub format: String,
+    pub app_version: String,
+    pub settings: AppSettings,
+}
+
+impl SettingsExport {
+    /// Wraps `settings` for sharing, dropping API keys, audio devices and output
+    /// sinks since those are specific to the machine they were configured on.
+    pub fn new(settings: &AppSettings) -> Self {
+        let mut settings = settings.clone();
+        settings.selected_microphone = None;
+        settings.clamshell_microphone = None;
+        settings.microphone_capture.clear();
+        settings.fallback_microphones.clear();
+        settings.selected_output_device = None;
+        settings.output_sinks.clear();
+        for api_key in settings.post_process_api_keys.values_mut() {
+            api_key.clear();
+        }
+
+        Self {
+            format: SETTINGS_EXPORT_FORMAT.to_string(),
+            app_version: env!("CARGO_PKG_VERSION").to_string(),
+            settings,
+        }
+    }
+
+    /// Parses an exported file, migrating it to the current settings version
+    /// and rejecting anything this build could not apply safely.
+    pub fn parse(contents: &str) -> Result<AppSettings, String> {
+        let export: SettingsExport = serde_json::from_str(contents)
+            .map_err(|e| format!("Not a valid settings file: {}", e))?;
+
+        if export.format != SETTINGS_EXPORT_FORMAT {
+            return Err(format!("Unsupported settings format '{}'", export.format));
+        }
+
+        let mut settings = export.settings;
+        if settings.settings_version > current_settings_version() {
+            return Err(format!(
+                "Settings were exported from a newer version ({}) and cannot be imported",
+                export.app_version
+            ));
+        }
+        migrate_settings(&mut settings);
+        validate_settings(&settings)?;
+
+        Ok(settings)
+    }
+    /// Carries over from `current` the values `new` leaves out of an export, so
+    /// importing doesn't reset this machine's devices, API keys and sinks.
+    pub fn keep_machine_settings(imported: &mut AppSettings, current: &AppSettings) {
+        imported.selected_microphone = current.selected_microphone.clone();
+        imported.clamshell_microphone = current.clamshell_microphone.clone();
+        imported.microphone_capture = current.microphone_capture.clone();
+        imported.fallback_microphones = current.fallback_microphones.clone();
+        imported.selected_output_device = current.selected_output_device.clone();
+        imported.post_process_api_keys = current.post_process_api_keys.clone();
+        // Sinks can run commands and write files, which a shared file must not set up
+        imported.output_sinks = current.output_sinks.clone();
+    }
+}
+
+/// Checks the parts of imported settings that the UI would otherwise never
+/// let through. Shortcut syntax is checked when the bindings are registered.
+pub fn validate_settings(settings: &AppSettings) -> Result<(), String> {
+    let known_bindings = get_default_settings().bindings;
+    for (id, binding) in &settings.bindings {
+        if !known_bindings.contains_key(id) {
+            return Err(format!("Unknown shortcut binding '{}'", id));
+        }
+        if binding.id != *id {
+            return Err(format!(
+                "Shortcut binding '{}' is stored under '{}'",
+                binding.id, id
+            ));
+        }
+        if binding.current_binding.trim().is_empty() {
+            return Err(format!("Shortcut binding '{}' is empty", id));
+        }
+    }
+
+    let mut prompt_ids = std::collections::HashSet::new();
+    for prompt in &settings.post_process_prompts {
+        if prompt.id.is_empty() || prompt.name.trim().is_empty() {
+            return Err("Post-processing prompts need an id and a name".to_string());
+        }
+        if !prompt_ids.insert(prompt.id.as_str()) {
+            return Err(format!(
+                "Duplicate post-processing prompt id '{}'",
+                prompt.id
+            ));
+        }
+    }
+    if let Some(selected) = &settings.post_process_selected_prompt_id {
+        if !prompt_ids.contains(selected.as_str()) {
+            return Err(format!("Selected prompt '{}' does not exist", selected));
+        }
+    }
+
+    crate::output::validate_sinks(&settings.output_sinks)?;
+
+    if settings
+        .post_process_provider(&settings.post_process_provider_id)
+        .is_none()
+    {
+        return Err(format!(
+            "Unknown post-processing provider '{}'",
+            settings.post_process_provider_id
+        ));
+    }
+
+    if !(0.0..=1.0).contains(&settings.word_correction_threshold) {
+        return Err("Word correction threshold must be between 0 and 1".to_string());
+    }
+    if !(0.0..=1.0).contains(&settings.audio_feedback_volume) {
+        return Err("Audio feedback volume must be between 0 and 1".to_string());
+    }
+    if !(0.0..=1.0).contains(&settings.vad_threshold) {
+        return Err("VAD threshold must be between 0 and 1".to_string());
+    }
+    if settings.vad_onset_frames == 0 {
+        return Err("VAD onset must be at least one frame".to_string());
+    }
+    if settings.vad_prefill_frames > MAX_VAD_FRAMES || settings.vad_hangover_frames > MAX_VAD_FRAMES
+    {
+        return Err(format!(
+            "VAD prefill and hangover must be at most {} frames",
+            MAX_VAD_FRAMES
+        ));
+    }
+    if settings.pre_roll_ms > MAX_PRE_ROLL_MS {
+        return Err(format!("Pre-roll must be at most {} ms", MAX_PRE_ROLL_MS));
+    }
+    if settings.typing_delay_ms > MAX_TYPING_DELAY_MS {
+        return Err(format!(
+            "Typing delay must be at most {} ms",
+            MAX_TYPING_DELAY_MS
+        ));
+    }
+    if !(1..=MAX_TYPING_CHUNK_SIZE).contains(&settings.typing_chunk_size) {
+        return Err(format!(
+            "Typing chunk size must be between 1 and {}",
+            MAX_TYPING_CHUNK_SIZE
+        ));
+    }
+    if settings.paste_preview_countdown_secs > MAX_PASTE_PREVIEW_COUNTDOWN_SECS {
+        return Err(format!(
+            "Preview countdown must be at most {} seconds",
+            MAX_PASTE_PREVIEW_COUNTDOWN_SECS
+        ));
+    }
+    if settings.capture_source != CaptureSource::Microphone && !LoopbackCapture::is_supported() {
+        return Err("System audio capture is not supported on this platform".to_string());
+    }
+    for (device, capture) in &settings.microphone_capture {
+        capture
+            .validate()
+            .map_err(|e| format!("Microphone '{}': {}", device, e))?;
+    }
+    if settings.hands_free_silence_ms == 0 || settings.hands_free_max_duration_secs == 0 {
+        return Err("Hands-free silence and maximum duration must be positive".to_string());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unversioned_settings_run_all_migrations() {
+        let mut settings = get_default_settings();
+        settings.settings_version = 0;
+        settings.bindings.remove("cancel");
+        settings.post_process_providers.clear();
+        settings.post_process_models.clear();
+
+        assert!(migrate_settings(&mut settings));
+        assert_eq!(settings.settings_version, current_settings_version());
+        assert!(settings.bindings.contains_key("cancel"));
+        assert_eq!(
+            settings.post_process_providers.len(),
+            default_post_process_providers().len()
+        );
+
+        // Already up to date, nothing to do
+        assert!(!migrate_settings(&mut settings));
+    }
+
+    #[test]
+    fn test_export_round_trip_strips_machine_specific_values() {
+        let mut settings = get_default_settings();
+        settings.selected_microphone = Some("USB Mic".to_string());
+        settings.microphone_capture.insert(
+            "USB Mic".to_string(),
+            MicrophoneCaptureSettings {
+                channel_mix: vec![1.0, 0.0],
+                ..Default::default()
+            },
+        );
+        settings
+            .post_process_api_keys
+            .insert("openai".to_string(), "sk-test".to_string());
+        settings.custom_words = vec!["AleFlow".to_string()];
+        settings.output_sinks.push(OutputSink {
+            id: "sink_1".to_string(),
+            enabled: true,
+            kind: OutputSinkKind::Command {
+                command: "notify-send dictated".to_string(),
+            },
+            prompt_id: None,
+        });
+
+        let json = serde_json::to_string(&SettingsExport::new(&settings)).unwrap();
+        assert!(!json.contains("sk-test"));
+        assert!(!json.contains("notify-send"));
+
+        let imported = SettingsExport::parse(&json).unwrap();
+        assert_eq!(imported.selected_microphone, None);
+        assert!(imported.microphone_capture.is_empty());
+        assert_eq!(imported.custom_words, vec!["AleFlow".to_string()]);
+    }
+
+    #[test]
+    fn test_import_keeps_machine_specific_values() {
+        let mut current = get_default_settings();
+        current.selected_microphone = Some("USB Mic".to_string());
+        current.microphone_capture.insert(
+            "USB Mic".to_string(),
+            MicrophoneCaptureSettings {
+                channel_mix: vec![1.0, 0.0],
+                sample_rate: Some(48_000),
+                buffer_size: None,
+            },
+        );
+        current.fallback_microphones = vec!["Built-in Microphone".to_string()];
+        current.custom_words = vec!["AleFlow".to_string()];
+
+        let mut shared = current.clone();
+        shared.custom_words = vec!["Tauri".to_string()];
+        let json = serde_json::to_string(&SettingsExport::new(&shared)).unwrap();
+
+        let mut imported = SettingsExport::parse(&json).unwrap();
+        SettingsExport::keep_machine_settings(&mut imported, &current);
+        assert_eq!(imported.selected_microphone, current.selected_microphone);
+        assert_eq!(imported.microphone_capture, current.microphone_capture);
+        assert_eq!(imported.fallback_microphones, current.fallback_microphones);
+        assert_eq!(imported.custom_words, vec!["Tauri".to_string()]);
+    }
+
+    #[test]
+    fn test_import_rejects_unknown_binding_and_newer_version() {
+        let mut settings = get_default_settings();
+        let mut binding = settings.bindings["transcribe"].clone();
+        binding.id = "launch_rockets".to_string();
+        settings.bindings.insert(binding.id.clone(), binding);
+        let json = serde_json::to_string(&SettingsExport::new(&settings)).unwrap();
+        assert!(SettingsExport::parse(&json).is_err());
+
+        let mut settings = get_default_settings();
+        settings.settings_version = current_settings_version() + 1;
+        let json = serde_json::to_string(&SettingsExport::new(&settings)).unwrap();
+        assert!(SettingsExport::parse(&json).is_err());
+    }
+}
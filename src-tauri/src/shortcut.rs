@@ -1,17 +1,25 @@
 use log::{error, warn};
 use serde::Serialize;
 use specta::Type;
+use std::collections::HashMap;
 use std::sync::Arc;
 use tauri::{AppHandle, Emitter, Manager};
 use tauri_plugin_autostart::ManagerExt;
 use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutState};
 
-use crate::actions::ACTION_MAP;
-use crate::managers::audio::AudioRecordingManager;
+use crate::actions::{ACTION_MAP, HISTORY_PICKER_BINDING_ID, REPASTE_BINDING_ID, UNDO_BINDING_ID};
+use crate::managers::audio::{AudioRecordingManager, MicrophoneMode};
+use crate::managers::meeting::MEETING_BINDING_ID;
+use crate::managers::model::ModelManager;
+use crate::managers::secrets::{SecretsManager, REDACTED_API_KEY};
+use crate::managers::transcription::TranscriptionManager;
+use crate::output;
 use crate::settings::ShortcutBinding;
 use crate::settings::{
-    self, get_settings, ClipboardHandling, LLMPrompt, OverlayPosition, PasteMethod, SoundTheme,
-    APPLE_INTELLIGENCE_DEFAULT_MODEL_ID, APPLE_INTELLIGENCE_PROVIDER_ID,
+    self, get_settings, AppSettings, ClipboardHandling, FocusChangeBehavior, LLMPrompt, OutputSink,
+    OverlayPosition, PasteMethod, SoundTheme, APPLE_INTELLIGENCE_DEFAULT_MODEL_ID,
+    APPLE_INTELLIGENCE_PROVIDER_ID, MAX_PASTE_PREVIEW_COUNTDOWN_SECS, MAX_TYPING_CHUNK_SIZE,
+    MAX_TYPING_DELAY_MS,
 };
 use crate::tray;
 use crate::ManagedToggleState;
@@ -37,6 +45,161 @@ pub fn init_shortcuts(app: &AppHandle) {
     }
 }
 
+/// Swaps every registered shortcut from `old` to `new`, e.g. after importing
+/// settings. All of `new` is validated up front, and if registration still
+/// fails the previous shortcuts are restored.
+pub fn replace_bindings(
+    app: &AppHandle,
+    old: &HashMap<String, ShortcutBinding>,
+    new: &HashMap<String, ShortcutBinding>,
+) -> Result<(), String> {
+    for binding in new.values() {
+        validate_shortcut_string(&binding.current_binding)?;
+        binding
+            .current_binding
+            .parse::<Shortcut>()
+            .map_err(|e| format!("Invalid shortcut '{}': {}", binding.current_binding, e))?;
+    }
+
+    // The cancel shortcut is registered dynamically while recording
+    let registered = |bindings: &HashMap<String, ShortcutBinding>| -> Vec<ShortcutBinding> {
+        bindings
+            .values()
+            .filter(|binding| binding.id != "cancel")
+            .cloned()
+            .collect()
+    };
+
+    for binding in registered(old) {
+        let _ = unregister_shortcut(app, binding);
+    }
+
+    let mut newly_registered = Vec::new();
+    for binding in registered(new) {
+        if let Err(e) = register_shortcut(app, binding.clone()) {
+            for registered_binding in newly_registered {
+                let _ = unregister_shortcut(app, registered_binding);
+            }
+            for binding in registered(old) {
+                let _ = register_shortcut(app, binding);
+            }
+            return Err(e);
+        }
+        newly_registered.push(binding);
+    }
+
+    Ok(())
+}
+
+/// Writes `new` over `current` and re-applies everything that the individual
+/// `change_*` commands would otherwise apply one at a time. Used when settings
+/// are replaced wholesale, e.g. by an import or the user config file.
+pub fn apply_settings(
+    app: &AppHandle,
+    current: &AppSettings,
+    mut new: AppSettings,
+) -> Result<(), String> {
+    let model_changed = new.selected_model != current.selected_model;
+    if model_changed {
+        let model_available = app
+            .state::<Arc<ModelManager>>()
+            .get_model_info(&new.selected_model)
+            .is_some_and(|model| model.is_downloaded);
+        if !model_available {
+            warn!(
+                "Model '{}' is not downloaded, keeping '{}'",
+                new.selected_model, current.selected_model
+            );
+            new.selected_model = current.selected_model.clone();
+        }
+    }
+
+    replace_bindings(app, &current.bindings, &new.bindings)?;
+    settings::write_settings(app, new.clone());
+
+    crate::commands::apply_log_level(new.log_level);
+
+    let autostart_manager = app.autolaunch();
+    let _ = if new.autostart_enabled {
+        autostart_manager.enable()
+    } else {
+        autostart_manager.disable()
+    };
+
+    if new.always_on_microphone != current.always_on_microphone {
+        let mode = MicrophoneMode::from_settings(&new);
+        if let Err(e) = app.state::<Arc<AudioRecordingManager>>().update_mode(mode) {
+            warn!("Failed to apply microphone mode: {}", e);
+        }
+    }
+
+    if new.hands_free_enabled != current.hands_free_enabled
+        || new.hands_free_silence_ms != current.hands_free_silence_ms
+        || new.hands_free_max_duration_secs != current.hands_free_max_duration_secs
+        || new.wake_word_enabled != current.wake_word_enabled
+    {
+        if let Err(e) = app
+            .state::<Arc<AudioRecordingManager>>()
+            .update_hands_free(&new)
+        {
+            warn!("Failed to apply hands-free mode: {}", e);
+        }
+    }
+
+    if new.vad_engine != current.vad_engine
+        || new.vad_threshold != current.vad_threshold
+        || new.vad_prefill_frames != current.vad_prefill_frames
+        || new.vad_hangover_frames != current.vad_hangover_frames
+        || new.vad_onset_frames != current.vad_onset_frames
+        || new.vad_trim_enabled != current.vad_trim_enabled
+    {
+        if let Err(e) = app.state::<Arc<AudioRecordingManager>>().update_vad(&new) {
+            warn!("Failed to apply voice activity detection settings: {}", e);
+        }
+    }
+
+    if new.dsp_high_pass_enabled != current.dsp_high_pass_enabled
+        || new.dsp_denoise_enabled != current.dsp_denoise_enabled
+        || new.dsp_agc_enabled != current.dsp_agc_enabled
+        || new.dsp_clipping_detection_enabled != current.dsp_clipping_detection_enabled
+        || new.dsp_ab_capture_enabled != current.dsp_ab_capture_enabled
+    {
+        if let Err(e) = app.state::<Arc<AudioRecordingManager>>().update_dsp(&new) {
+            warn!("Failed to apply audio processing settings: {}", e);
+        }
+    }
+
+    if new.pre_roll_ms != current.pre_roll_ms {
+        if let Err(e) = app
+            .state::<Arc<AudioRecordingManager>>()
+            .update_pre_roll(&new)
+        {
+            warn!("Failed to apply pre-roll: {}", e);
+        }
+    }
+
+    if new.selected_model != current.selected_model {
+        let tm = app.state::<Arc<TranscriptionManager>>().inner().clone();
+        let model_id = new.selected_model.clone();
+        std::thread::spawn(move || {
+            if let Err(e) = tm.load_model(&model_id) {
+                error!("Failed to load model '{}': {}", model_id, e);
+            }
+        });
+    }
+
+    tray::update_tray_menu(app, &tray::TrayIconState::Idle, Some(&new.app_language));
+
+    let _ = app.emit(
+        "settings-changed",
+        serde_json::json!({
+            "setting": "all"
+        }),
+    );
+
+    Ok(())
+}
+
 #[derive(Serialize, Type)]
 pub struct BindingResponse {
     success: bool,
@@ -140,6 +303,7 @@ pub fn change_ptt_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
     settings.push_to_talk = enabled;
 
     settings::write_settings(&app, settings);
+    tray::refresh_tray_menu(&app);
 
     Ok(())
 }
@@ -206,6 +370,13 @@ pub fn change_overlay_position_setting(app: AppHandle, position: String) -> Resu
         "none" => OverlayPosition::None,
         "top" => OverlayPosition::Top,
         "bottom" => OverlayPosition::Bottom,
+        "top_left" => OverlayPosition::TopLeft,
+        "top_right" => OverlayPosition::TopRight,
+        "bottom_left" => OverlayPosition::BottomLeft,
+        "bottom_right" => OverlayPosition::BottomRight,
+        "caret" => OverlayPosition::Caret,
+        "custom" => OverlayPosition::Custom,
+        "tray" => OverlayPosition::Tray,
         other => {
             warn!("Invalid overlay position '{}', defaulting to bottom", other);
             OverlayPosition::Bottom
@@ -220,6 +391,21 @@ pub fn change_overlay_position_setting(app: AppHandle, position: String) -> Resu
     Ok(())
 }
 
+#[tauri::command]
+#[specta::specta]
+pub fn change_overlay_monitor_setting(
+    app: AppHandle,
+    monitor: Option<String>,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.overlay_monitor = monitor;
+    settings::write_settings(&app, settings);
+
+    crate::utils::update_overlay_position(&app);
+
+    Ok(())
+}
+
 #[tauri::command]
 #[specta::specta]
 pub fn change_debug_mode_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
@@ -344,6 +530,38 @@ pub fn change_paste_method_setting(app: AppHandle, method: String) -> Result<(),
     Ok(())
 }
 
+#[tauri::command]
+#[specta::specta]
+pub fn change_typing_delay_setting(app: AppHandle, delay_ms: u32) -> Result<(), String> {
+    if delay_ms > MAX_TYPING_DELAY_MS {
+        return Err(format!(
+            "Typing delay must be at most {} ms",
+            MAX_TYPING_DELAY_MS
+        ));
+    }
+
+    let mut settings = settings::get_settings(&app);
+    settings.typing_delay_ms = delay_ms;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_typing_chunk_size_setting(app: AppHandle, chunk_size: u32) -> Result<(), String> {
+    if !(1..=MAX_TYPING_CHUNK_SIZE).contains(&chunk_size) {
+        return Err(format!(
+            "Typing chunk size must be between 1 and {}",
+            MAX_TYPING_CHUNK_SIZE
+        ));
+    }
+
+    let mut settings = settings::get_settings(&app);
+    settings.typing_chunk_size = chunk_size;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
 #[tauri::command]
 #[specta::specta]
 pub fn change_clipboard_handling_setting(app: AppHandle, handling: String) -> Result<(), String> {
@@ -364,12 +582,73 @@ pub fn change_clipboard_handling_setting(app: AppHandle, handling: String) -> Re
     Ok(())
 }
 
+#[tauri::command]
+#[specta::specta]
+pub fn change_focus_change_behavior_setting(
+    app: AppHandle,
+    behavior: String,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    let parsed = match behavior.as_str() {
+        "paste_anyway" => FocusChangeBehavior::PasteAnyway,
+        "refocus" => FocusChangeBehavior::Refocus,
+        "prompt" => FocusChangeBehavior::Prompt,
+        other => {
+            warn!(
+                "Invalid focus change behavior '{}', defaulting to refocus",
+                other
+            );
+            FocusChangeBehavior::Refocus
+        }
+    };
+    settings.focus_change_behavior = parsed;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_output_sinks_setting(app: AppHandle, sinks: Vec<OutputSink>) -> Result<(), String> {
+    output::validate_sinks(&sinks)?;
+
+    let mut settings = settings::get_settings(&app);
+    settings.output_sinks = sinks;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_paste_preview_enabled_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.paste_preview_enabled = enabled;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_paste_preview_countdown_setting(app: AppHandle, secs: u32) -> Result<(), String> {
+    if secs > MAX_PASTE_PREVIEW_COUNTDOWN_SECS {
+        return Err(format!(
+            "Preview countdown must be at most {} seconds",
+            MAX_PASTE_PREVIEW_COUNTDOWN_SECS
+        ));
+    }
+
+    let mut settings = settings::get_settings(&app);
+    settings.paste_preview_countdown_secs = secs;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
 #[tauri::command]
 #[specta::specta]
 pub fn change_post_process_enabled_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
     let mut settings = settings::get_settings(&app);
     settings.post_process_enabled = enabled;
     settings::write_settings(&app, settings);
+    tray::refresh_tray_menu(&app);
     Ok(())
 }
 
@@ -424,11 +703,17 @@ pub fn change_post_process_api_key_setting(
     provider_id: String,
     api_key: String,
 ) -> Result<(), String> {
-    let mut settings = settings::get_settings(&app);
+    let settings = settings::get_settings(&app);
     validate_provider_exists(&settings, &provider_id)?;
-    settings.post_process_api_keys.insert(provider_id, api_key);
-    settings::write_settings(&app, settings);
-    Ok(())
+
+    // The frontend only ever sees the placeholder, so echoing it back means "unchanged"
+    if api_key == REDACTED_API_KEY {
+        return Ok(());
+    }
+
+    app.state::<Arc<SecretsManager>>()
+        .set_api_key(&provider_id, &api_key)
+        .map_err(|e| format!("Failed to store API key: {}", e))
 }
 
 #[tauri::command]
@@ -475,6 +760,7 @@ pub fn add_post_process_prompt(
 
     settings.post_process_prompts.push(new_prompt.clone());
     settings::write_settings(&app, settings);
+    tray::refresh_tray_menu(&app);
 
     Ok(new_prompt)
 }
@@ -497,6 +783,7 @@ pub fn update_post_process_prompt(
         existing_prompt.name = name;
         existing_prompt.prompt = prompt;
         settings::write_settings(&app, settings);
+        tray::refresh_tray_menu(&app);
         Ok(())
     } else {
         Err(format!("Prompt with id '{}' not found", id))
@@ -528,6 +815,7 @@ pub fn delete_post_process_prompt(app: AppHandle, id: String) -> Result<(), Stri
     }
 
     settings::write_settings(&app, settings);
+    tray::refresh_tray_menu(&app);
     Ok(())
 }
 
@@ -559,11 +847,7 @@ pub async fn fetch_post_process_models(
     }
 
     // Get API key
-    let api_key = settings
-        .post_process_api_keys
-        .get(&provider_id)
-        .cloned()
-        .unwrap_or_default();
+    let api_key = app.state::<Arc<SecretsManager>>().get_api_key(&provider_id);
 
     // Skip fetching if no API key for providers that typically need one
     if api_key.trim().is_empty() && provider.id != "custom" {
@@ -588,6 +872,7 @@ pub fn set_post_process_selected_prompt(app: AppHandle, id: String) -> Result<()
 
     settings.post_process_selected_prompt_id = Some(id);
     settings::write_settings(&app, settings);
+    tray::refresh_tray_menu(&app);
     Ok(())
 }
 
@@ -756,6 +1041,23 @@ pub fn register_shortcut(app: &AppHandle, binding: ShortcutBinding) -> Result<()
                             action.start(ah, &binding_id_for_closure, &shortcut_string);
                         }
                         return;
+                    } else if binding_id_for_closure == UNDO_BINDING_ID
+                        || binding_id_for_closure == REPASTE_BINDING_ID
+                    {
+                        // Undo and re-paste on release; they also wait for
+                        // the shortcut's modifiers to be let go before
+                        // sending keys of their own
+                        if event.state == ShortcutState::Released {
+                            action.start(ah, &binding_id_for_closure, &shortcut_string);
+                        }
+                    } else if binding_id_for_closure == MEETING_BINDING_ID
+                        || binding_id_for_closure == HISTORY_PICKER_BINDING_ID
+                    {
+                        // Meetings are too long to hold a key for and the
+                        // picker stays open, so these act on press in every mode
+                        if event.state == ShortcutState::Pressed {
+                            action.start(ah, &binding_id_for_closure, &shortcut_string);
+                        }
                     } else if settings.push_to_talk {
                         if event.state == ShortcutState::Pressed {
                             action.start(ah, &binding_id_for_closure, &shortcut_string);
@@ -0,0 +1,194 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::HashMap;
+use std::fmt::Write;
+
+/// A timed piece of a transcript and, when diarization ran, who said it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TranscriptSegment {
+    pub start_ms: u64,
+    pub end_ms: u64,
+    pub speaker: Option<u32>,
+    pub text: String,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "lowercase")]
+pub enum TranscriptFormat {
+    Srt,
+    Vtt,
+    Markdown,
+}
+
+impl TranscriptFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            TranscriptFormat::Srt => "srt",
+            TranscriptFormat::Vtt => "vtt",
+            TranscriptFormat::Markdown => "md",
+        }
+    }
+}
+
+/// The name the user gave a speaker, or "Speaker N" counting from one.
+pub fn speaker_name(speaker: u32, names: &HashMap<u32, String>) -> String {
+    names
+        .get(&speaker)
+        .cloned()
+        .unwrap_or_else(|| format!("Speaker {}", speaker + 1))
+}
+
+/// One line per segment, prefixed with its offset and speaker, as stored in
+/// history.
+pub fn format_plain(segments: &[TranscriptSegment], names: &HashMap<u32, String>) -> String {
+    segments
+        .iter()
+        .map(|segment| {
+            let label = segment
+                .speaker
+                .map(|speaker| format!("{}: ", speaker_name(speaker, names)))
+                .unwrap_or_default();
+            format!(
+                "[{}] {}{}",
+                format_offset(segment.start_ms),
+                label,
+                segment.text
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+pub fn format_transcript(
+    segments: &[TranscriptSegment],
+    names: &HashMap<u32, String>,
+    format: TranscriptFormat,
+    title: &str,
+) -> String {
+    let mut out = String::new();
+    match format {
+        TranscriptFormat::Srt => {
+            for (index, segment) in segments.iter().enumerate() {
+                let label = segment
+                    .speaker
+                    .map(|speaker| format!("{}: ", speaker_name(speaker, names)))
+                    .unwrap_or_default();
+                let _ = write!(
+                    out,
+                    "{}\n{} --> {}\n{}{}\n\n",
+                    index + 1,
+                    format_cue_time(segment.start_ms, ','),
+                    format_cue_time(segment.end_ms, ','),
+                    label,
+                    segment.text
+                );
+            }
+        }
+        TranscriptFormat::Vtt => {
+            out.push_str("WEBVTT\n\n");
+            for segment in segments {
+                let label = segment
+                    .speaker
+                    .map(|speaker| format!("<v {}>", speaker_name(speaker, names)))
+                    .unwrap_or_default();
+                let _ = write!(
+                    out,
+                    "{} --> {}\n{}{}\n\n",
+                    format_cue_time(segment.start_ms, '.'),
+                    format_cue_time(segment.end_ms, '.'),
+                    label,
+                    segment.text
+                );
+            }
+        }
+        TranscriptFormat::Markdown => {
+            let _ = write!(out, "# {}\n\n", title);
+            for segment in segments {
+                let label = segment
+                    .speaker
+                    .map(|speaker| format!(" {}:", speaker_name(speaker, names)))
+                    .unwrap_or_default();
+                let _ = write!(
+                    out,
+                    "**[{}]{}** {}\n\n",
+                    format_offset(segment.start_ms),
+                    label,
+                    segment.text
+                );
+            }
+        }
+    }
+    out
+}
+
+pub fn format_offset(ms: u64) -> String {
+    let secs = ms / 1000;
+    format!("{:02}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+}
+
+fn format_cue_time(ms: u64, separator: char) -> String {
+    format!("{}{}{:03}", format_offset(ms), separator, ms % 1000)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segments() -> Vec<TranscriptSegment> {
+        vec![
+            TranscriptSegment {
+                start_ms: 1_500,
+                end_ms: 4_250,
+                speaker: Some(0),
+                text: "Hello everyone.".to_string(),
+            },
+            TranscriptSegment {
+                start_ms: 3_725_000,
+                end_ms: 3_727_040,
+                speaker: Some(1),
+                text: "Let's wrap up.".to_string(),
+            },
+        ]
+    }
+
+    #[test]
+    fn plain_lines_are_timestamped_and_labelled() {
+        let names = HashMap::from([(1, "Ana".to_string())]);
+        assert_eq!(
+            format_plain(&segments(), &names),
+            "[00:00:01] Speaker 1: Hello everyone.\n[01:02:05] Ana: Let's wrap up."
+        );
+
+        let mut unlabelled = segments();
+        unlabelled[0].speaker = None;
+        assert!(format_plain(&unlabelled, &names).starts_with("[00:00:01] Hello everyone.\n"));
+    }
+
+    #[test]
+    fn subtitles_use_their_own_time_formats() {
+        let names = HashMap::new();
+        assert_eq!(
+            format_transcript(&segments(), &names, TranscriptFormat::Srt, ""),
+            "1\n00:00:01,500 --> 00:00:04,250\nSpeaker 1: Hello everyone.\n\n\
+             2\n01:02:05,000 --> 01:02:07,040\nSpeaker 2: Let's wrap up.\n\n"
+        );
+        assert_eq!(
+            format_transcript(&segments(), &names, TranscriptFormat::Vtt, ""),
+            "WEBVTT\n\n00:00:01.500 --> 00:00:04.250\n<v Speaker 1>Hello everyone.\n\n\
+             01:02:05.000 --> 01:02:07.040\n<v Speaker 2>Let's wrap up.\n\n"
+        );
+    }
+
+    #[test]
+    fn markdown_has_a_title_and_bold_timestamps() {
+        assert_eq!(
+            format_transcript(
+                &segments()[..1],
+                &HashMap::new(),
+                TranscriptFormat::Markdown,
+                "Standup"
+            ),
+            "# Standup\n\n**[00:00:01] Speaker 1:** Hello everyone.\n\n"
+        );
+    }
+}
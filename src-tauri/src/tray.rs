@@ -1,9 +1,21 @@
+use crate::commands::models::set_active_model;
+use crate::managers::history::HistoryManager;
+use crate::managers::model::ModelManager;
+use crate::managers::transcription::TranscriptionManager;
 use crate::settings;
+use crate::shortcut::set_post_process_selected_prompt;
 use crate::tray_i18n::get_tray_translations;
+use log::error;
+use std::sync::{Arc, Mutex};
 use tauri::image::Image;
-use tauri::menu::{Menu, MenuItem, PredefinedMenuItem};
+use tauri::menu::{CheckMenuItem, Menu, MenuItem, PredefinedMenuItem, Submenu};
 use tauri::tray::TrayIcon;
-use tauri::{AppHandle, Manager, Theme};
+use tauri::{AppHandle, Emitter, Manager, Theme};
+use tauri_plugin_clipboard_manager::ClipboardExt;
+
+const MODEL_ID_PREFIX: &str = "model:";
+const PROMPT_ID_PREFIX: &str = "prompt:";
+const RECENT_ID_PREFIX: &str = "recent:";
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum TrayIconState {
@@ -75,7 +87,39 @@ pub fn change_tray_icon(app: &AppHandle, icon: TrayIconState) {
     update_tray_menu(app, &icon, None);
 }
 
+/// Shows the recording state on the tray icon, for when the overlay is set to
+/// live in the tray. `None` clears it.
+pub fn set_tray_status(app: &AppHandle, state: Option<TrayIconState>) {
+    let settings = settings::get_settings(app);
+    let strings = get_tray_translations(Some(settings.app_language));
+    let text = match state {
+        Some(TrayIconState::Recording) => Some(strings.recording),
+        Some(TrayIconState::Transcribing) => Some(strings.transcribing),
+        Some(TrayIconState::Idle) | None => None,
+    };
+
+    let tray = app.state::<TrayIcon>();
+    let _ = tray.set_tooltip(text.as_deref());
+    // Tray icons on Linux have no tooltips but can show a label instead
+    #[cfg(target_os = "linux")]
+    let _ = tray.set_title(text.as_deref());
+}
+
+/// The state the tray menu was last built for, so it can be rebuilt when a
+/// setting or the history changes without resetting a recording's menu.
+static MENU_STATE: Mutex<TrayIconState> = Mutex::new(TrayIconState::Idle);
+
+const RECENT_TRANSCRIPTION_COUNT: usize = 5;
+const RECENT_LABEL_CHARS: usize = 40;
+
+/// Rebuilds the tray menu for its current state.
+pub fn refresh_tray_menu(app: &AppHandle) {
+    let state = MENU_STATE.lock().unwrap().clone();
+    update_tray_menu(app, &state, None);
+}
+
 pub fn update_tray_menu(app: &AppHandle, state: &TrayIconState, locale: Option<&str>) {
+    *MENU_STATE.lock().unwrap() = state.clone();
     let settings = settings::get_settings(app);
 
     let locale = locale.unwrap_or(&settings.app_language);
@@ -115,41 +159,205 @@ pub fn update_tray_menu(app: &AppHandle, state: &TrayIconState, locale: Option<&
         .expect("failed to create quit item");
     let separator = || PredefinedMenuItem::separator(app).expect("failed to create separator");
 
-    let menu = match state {
-        TrayIconState::Recording | TrayIconState::Transcribing => {
-            let cancel_i = MenuItem::with_id(app, "cancel", &strings.cancel, true, None::<&str>)
-                .expect("failed to create cancel item");
-            Menu::with_items(
-                app,
-                &[
-                    &version_i,
-                    &separator(),
-                    &cancel_i,
-                    &separator(),
-                    &settings_i,
-                    &check_updates_i,
-                    &separator(),
-                    &quit_i,
-                ],
-            )
-            .expect("failed to create menu")
-        }
-        TrayIconState::Idle => Menu::with_items(
+    let model_menu =
+        Submenu::with_id(app, "model", &strings.model, true).expect("failed to create model menu");
+    let mut models: Vec<_> = app
+        .state::<Arc<ModelManager>>()
+        .get_available_models()
+        .into_iter()
+        .filter(|model| model.is_downloaded)
+        .collect();
+    models.sort_by(|a, b| a.name.cmp(&b.name));
+    for model in models {
+        let item = CheckMenuItem::with_id(
+            app,
+            format!("{MODEL_ID_PREFIX}{}", model.id),
+            &model.name,
+            true,
+            model.id == settings.selected_model,
+            None::<&str>,
+        )
+        .expect("failed to create model item");
+        let _ = model_menu.append(&item);
+    }
+
+    let prompt_menu = Submenu::with_id(app, "prompt", &strings.prompt, true)
+        .expect("failed to create prompt menu");
+    for prompt in &settings.post_process_prompts {
+        let item = CheckMenuItem::with_id(
             app,
-            &[
-                &version_i,
-                &separator(),
-                &settings_i,
-                &check_updates_i,
-                &separator(),
-                &quit_i,
-            ],
+            format!("{PROMPT_ID_PREFIX}{}", prompt.id),
+            &prompt.name,
+            true,
+            settings.post_process_selected_prompt_id.as_ref() == Some(&prompt.id),
+            None::<&str>,
         )
-        .expect("failed to create menu"),
+        .expect("failed to create prompt item");
+        let _ = prompt_menu.append(&item);
+    }
+
+    let toggle = |id: &str, label: &str, checked: bool| {
+        CheckMenuItem::with_id(app, id, label, true, checked, None::<&str>)
+            .expect("failed to create toggle item")
     };
+    let push_to_talk_i = toggle("push_to_talk", &strings.push_to_talk, settings.push_to_talk);
+    let always_on_mic_i = toggle(
+        "always_on_mic",
+        &strings.always_on_mic,
+        settings.always_on_microphone,
+    );
+    let post_process_i = toggle(
+        "post_process",
+        &strings.post_processing,
+        settings.post_process_enabled,
+    );
+
+    let recent_menu = Submenu::with_id(app, "recent", &strings.recent_transcriptions, true)
+        .expect("failed to create recent transcriptions menu");
+    let recent = app
+        .state::<Arc<HistoryManager>>()
+        .recent_entries(RECENT_TRANSCRIPTION_COUNT)
+        .unwrap_or_else(|e| {
+            error!("Failed to load recent transcriptions for the tray: {}", e);
+            Vec::new()
+        });
+    if recent.is_empty() {
+        let empty_i = MenuItem::with_id(
+            app,
+            "recent_empty",
+            &strings.no_recent_transcriptions,
+            false,
+            None::<&str>,
+        )
+        .expect("failed to create empty recent item");
+        let _ = recent_menu.append(&empty_i);
+    }
+    for entry in recent {
+        let item = MenuItem::with_id(
+            app,
+            format!("{RECENT_ID_PREFIX}{}", entry.id),
+            recent_label(&entry.pasted_text()),
+            true,
+            None::<&str>,
+        )
+        .expect("failed to create recent item");
+        let _ = recent_menu.append(&item);
+    }
+
+    let menu = Menu::new(app).expect("failed to create menu");
+    let _ = menu.append(&version_i);
+    let _ = menu.append(&separator());
+    if let TrayIconState::Recording | TrayIconState::Transcribing = state {
+        let cancel_i = MenuItem::with_id(app, "cancel", &strings.cancel, true, None::<&str>)
+            .expect("failed to create cancel item");
+        let _ = menu.append(&cancel_i);
+        let _ = menu.append(&separator());
+    }
+    let _ = menu.append(&model_menu);
+    let _ = menu.append(&prompt_menu);
+    let _ = menu.append(&push_to_talk_i);
+    let _ = menu.append(&always_on_mic_i);
+    let _ = menu.append(&post_process_i);
+    let _ = menu.append(&separator());
+    let _ = menu.append(&recent_menu);
+    let _ = menu.append(&separator());
+    let _ = menu.append(&settings_i);
+    let _ = menu.append(&check_updates_i);
+    let _ = menu.append(&separator());
+    let _ = menu.append(&quit_i);
 
     let tray = app.state::<TrayIcon>();
     let _ = tray.set_menu(Some(menu));
     // Changed to use color icon and disable template mode to prove control
     let _ = tray.set_icon_as_template(false);
 }
+
+/// The first line of a transcription, shortened to fit in the menu.
+fn recent_label(text: &str) -> String {
+    let line = text.lines().next().unwrap_or_default().trim();
+    if line.chars().count() > RECENT_LABEL_CHARS {
+        let short: String = line.chars().take(RECENT_LABEL_CHARS).collect();
+        format!("{}…", short.trim_end())
+    } else {
+        line.to_string()
+    }
+}
+
+/// Handles the tray menu items that switch the model or prompt, or copy a
+/// recent transcription. Other ids are ignored.
+pub fn handle_menu_selection(app: &AppHandle, id: &str) {
+    if let Some(model_id) = id.strip_prefix(MODEL_ID_PREFIX) {
+        let app = app.clone();
+        let model_id = model_id.to_string();
+        tauri::async_runtime::spawn(async move {
+            if let Err(e) = set_active_model(
+                app.clone(),
+                app.state::<Arc<ModelManager>>(),
+                app.state::<Arc<TranscriptionManager>>(),
+                model_id,
+            )
+            .await
+            {
+                error!("Failed to switch model from the tray: {}", e);
+                // Put the check mark back on the model that's still active
+                refresh_tray_menu(&app);
+            }
+            notify_settings_changed(&app, "selected_model");
+        });
+    } else if let Some(prompt_id) = id.strip_prefix(PROMPT_ID_PREFIX) {
+        if let Err(e) = set_post_process_selected_prompt(app.clone(), prompt_id.to_string()) {
+            error!("Failed to select prompt from the tray: {}", e);
+        }
+        notify_settings_changed(app, "post_process_selected_prompt_id");
+    } else if let Some(entry_id) = id.strip_prefix(RECENT_ID_PREFIX) {
+        let Ok(entry_id) = entry_id.parse::<i64>() else {
+            return;
+        };
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move {
+            let history_manager = app.state::<Arc<HistoryManager>>();
+            match history_manager.get_entry_by_id(entry_id).await {
+                Ok(Some(entry)) => {
+                    if let Err(e) = app.clipboard().write_text(entry.pasted_text()) {
+                        error!("Failed to copy transcription from the tray: {}", e);
+                    }
+                }
+                Ok(None) => {}
+                Err(e) => error!("Failed to load transcription {}: {}", entry_id, e),
+            }
+        });
+    }
+}
+
+/// Lets the settings window pick up a change made from the tray.
+pub fn notify_settings_changed(app: &AppHandle, setting: &str) {
+    let _ = app.emit(
+        "settings-changed",
+        serde_json::json!({
+            "setting": setting
+        }),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recent_label_uses_the_first_line() {
+        assert_eq!(recent_label("  hello there \nsecond line"), "hello there");
+        assert_eq!(recent_label(""), "");
+    }
+
+    #[test]
+    fn recent_label_truncates_long_text() {
+        let text = format!("{} tail", "a".repeat(RECENT_LABEL_CHARS - 1));
+        assert_eq!(
+            recent_label(&text),
+            format!("{}…", "a".repeat(RECENT_LABEL_CHARS - 1))
+        );
+
+        let exact = "é".repeat(RECENT_LABEL_CHARS);
+        assert_eq!(recent_label(&exact), exact);
+    }
+}
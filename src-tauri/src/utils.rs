@@ -1,5 +1,8 @@
 use crate::managers::audio::AudioRecordingManager;
+use crate::managers::meeting::MeetingManager;
 use crate::managers::transcription::TranscriptionManager;
+use crate::paste_target::PasteTarget;
+use crate::preview::PastePreview;
 use crate::shortcut;
 use crate::ManagedToggleState;
 use log::{info, warn};
@@ -29,6 +32,13 @@ pub fn cancel_current_operation(app: &AppHandle) {
         warn!("Failed to lock toggle state manager during cancellation");
     }
 
+    // Text waiting for a click to insert or in preview is dropped
+    app.state::<PasteTarget>().discard_pending();
+    app.state::<PastePreview>().cancel();
+
+    // A meeting is cancelled along with its chunks
+    app.state::<Arc<MeetingManager>>().cancel();
+
     // Cancel any ongoing recording
     let audio_manager = app.state::<Arc<AudioRecordingManager>>();
     audio_manager.cancel_recording();
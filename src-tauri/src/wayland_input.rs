@@ -0,0 +1,320 @@
+//! Types text and sends key combinations from our own Wayland connection
+//! instead of shelling out to wtype or dotool. Text is committed through the
+//! input method protocol when the focused field supports text-input-v3, and
+//! typed on a virtual keyboard with a keymap made for it otherwise, so any
+//! script types the same regardless of the user's keyboard layout.
+
+use crate::settings::PasteMethod;
+use log::debug;
+use std::io::Write;
+use std::os::fd::AsFd;
+use std::time::Instant;
+use wayland_client::globals::{registry_queue_init, GlobalList, GlobalListContents};
+use wayland_client::protocol::wl_keyboard::{KeyState, KeymapFormat};
+use wayland_client::protocol::{wl_registry::WlRegistry, wl_seat::WlSeat};
+use wayland_client::{delegate_noop, Connection, Dispatch, EventQueue, QueueHandle};
+use wayland_protocols_misc::zwp_input_method_v2::client::{
+    zwp_input_method_manager_v2::ZwpInputMethodManagerV2,
+    zwp_input_method_v2::{self, ZwpInputMethodV2},
+};
+use wayland_protocols_misc::zwp_virtual_keyboard_v1::client::{
+    zwp_virtual_keyboard_manager_v1::ZwpVirtualKeyboardManagerV1,
+    zwp_virtual_keyboard_v1::ZwpVirtualKeyboardV1,
+};
+
+/// X11 clients under XWayland only see keycodes up to 255, and keymap
+/// keycodes start at 8 with the first one left unused.
+const MAX_KEYS: usize = 247;
+
+/// Characters typed between round trips, so a long text doesn't overflow the
+/// connection's buffer before the compositor reads it.
+const SYNC_INTERVAL: usize = 64;
+
+const SHIFT_MASK: u32 = 1 << 0;
+const CONTROL_MASK: u32 = 1 << 2;
+
+#[derive(Default)]
+struct State {
+    active: bool,
+    unavailable: bool,
+    done_count: u32,
+}
+
+struct Session {
+    globals: GlobalList,
+    queue: EventQueue<State>,
+    seat: WlSeat,
+    state: State,
+}
+
+impl Session {
+    fn connect() -> Result<Self, String> {
+        let conn = Connection::connect_to_env().map_err(|e| e.to_string())?;
+        let (globals, queue) = registry_queue_init::<State>(&conn).map_err(|e| e.to_string())?;
+        let seat = globals
+            .bind(&queue.handle(), 1..=1, ())
+            .map_err(|e| format!("no seat: {}", e))?;
+        Ok(Self {
+            globals,
+            queue,
+            seat,
+            state: State::default(),
+        })
+    }
+
+    fn roundtrip(&mut self) -> Result<(), String> {
+        self.queue
+            .roundtrip(&mut self.state)
+            .map(|_| ())
+            .map_err(|e| format!("Wayland connection failed: {}", e))
+    }
+
+    /// Commits `text` to the focused text field as an input method. Returns
+    /// `false` when the compositor doesn't support it, another input method
+    /// is running, or the focused window doesn't use text-input-v3.
+    fn commit_string(&mut self, text: &str) -> Result<bool, String> {
+        let qh = self.queue.handle();
+        let Ok(manager) = self
+            .globals
+            .bind::<ZwpInputMethodManagerV2, _, _>(&qh, 1..=1, ())
+        else {
+            return Ok(false);
+        };
+        let input_method = manager.get_input_method(&self.seat, &qh, ());
+
+        // The compositor activates a new input method right away if a text
+        // field has focus
+        self.roundtrip()?;
+        let committed = self.state.active && !self.state.unavailable;
+        if committed {
+            input_method.commit_string(text.to_string());
+            input_method.commit(self.state.done_count);
+        }
+
+        input_method.destroy();
+        manager.destroy();
+        self.roundtrip()?;
+        Ok(committed)
+    }
+
+    fn virtual_keyboard(&mut self) -> Result<VirtualKeyboard, String> {
+        let qh = self.queue.handle();
+        let manager: ZwpVirtualKeyboardManagerV1 = self
+            .globals
+            .bind(&qh, 1..=1, ())
+            .map_err(|e| format!("virtual keyboard protocol not supported: {}", e))?;
+        let keyboard = manager.create_virtual_keyboard(&self.seat, &qh, ());
+        Ok(VirtualKeyboard {
+            keyboard,
+            started: Instant::now(),
+        })
+    }
+}
+
+struct VirtualKeyboard {
+    keyboard: ZwpVirtualKeyboardV1,
+    started: Instant,
+}
+
+impl VirtualKeyboard {
+    fn set_keymap(&self, keysyms: &[String]) -> Result<(), String> {
+        let mut keymap = build_keymap(keysyms).into_bytes();
+        keymap.push(0);
+
+        let mut file = tempfile::tempfile().map_err(|e| e.to_string())?;
+        file.write_all(&keymap).map_err(|e| e.to_string())?;
+        self.keyboard.keymap(
+            KeymapFormat::XkbV1.into(),
+            file.as_fd(),
+            keymap.len() as u32,
+        );
+        Ok(())
+    }
+
+    /// Presses and releases the key at `index` in the current keymap.
+    fn tap(&self, index: usize) {
+        let key = index as u32 + 1;
+        let time = self.started.elapsed().as_millis() as u32;
+        self.keyboard.key(time, key, KeyState::Pressed.into());
+        self.keyboard.key(time, key, KeyState::Released.into());
+    }
+
+    fn destroy(self) {
+        self.keyboard.destroy();
+    }
+}
+
+/// Types `text` into the focused window.
+pub fn type_text(text: &str) -> Result<(), String> {
+    let mut session = Session::connect()?;
+    if session.commit_string(text)? {
+        debug!("Committed text through the input method protocol");
+        return Ok(());
+    }
+
+    let keyboard = session.virtual_keyboard()?;
+    let chars: Vec<char> = text.chars().filter(|c| keysym_name(*c).is_some()).collect();
+
+    // Each keymap holds as many distinct characters as fit, in typing order
+    let mut typed = 0;
+    let mut start = 0;
+    while start < chars.len() {
+        let mut keys: Vec<char> = Vec::new();
+        let mut indices = Vec::new();
+        for c in &chars[start..] {
+            let index = match keys.iter().position(|k| k == c) {
+                Some(index) => index,
+                None if keys.len() < MAX_KEYS => {
+                    keys.push(*c);
+                    keys.len() - 1
+                }
+                None => break,
+            };
+            indices.push(index);
+        }
+
+        let keysyms: Vec<String> = keys.iter().filter_map(|c| keysym_name(*c)).collect();
+        keyboard.set_keymap(&keysyms)?;
+        for index in &indices {
+            keyboard.tap(*index);
+            typed += 1;
+            if typed % SYNC_INTERVAL == 0 {
+                session.roundtrip()?;
+            }
+        }
+        start += indices.len();
+    }
+
+    keyboard.destroy();
+    session.roundtrip()
+}
+
+/// Sends the paste shortcut for `paste_method` to the focused window.
+pub fn send_key_combo(paste_method: &PasteMethod) -> Result<(), String> {
+    let (modifiers, key) = match paste_method {
+        PasteMethod::CtrlV => (CONTROL_MASK, "v"),
+        PasteMethod::CtrlShiftV => (CONTROL_MASK | SHIFT_MASK, "v"),
+        PasteMethod::ShiftInsert => (SHIFT_MASK, "Insert"),
+        _ => return Err("Invalid paste method for key combo".into()),
+    };
+    tap_key(modifiers, key, 1)
+}
+
+/// Sends Ctrl+Z to the focused window.
+pub fn send_undo() -> Result<(), String> {
+    tap_key(CONTROL_MASK, "z", 1)
+}
+
+/// Presses Backspace `count` times in the focused window.
+pub fn send_backspaces(count: usize) -> Result<(), String> {
+    tap_key(0, "BackSpace", count)
+}
+
+/// Taps `key` `count` times while `modifiers` are held.
+fn tap_key(modifiers: u32, key: &str, count: usize) -> Result<(), String> {
+    let mut session = Session::connect()?;
+    let keyboard = session.virtual_keyboard()?;
+    keyboard.set_keymap(&[key.to_string()])?;
+    keyboard.keyboard.modifiers(modifiers, 0, 0, 0);
+    for sent in 1..=count {
+        keyboard.tap(0);
+        if sent % SYNC_INTERVAL == 0 {
+            session.roundtrip()?;
+        }
+    }
+    keyboard.keyboard.modifiers(0, 0, 0, 0);
+
+    keyboard.destroy();
+    session.roundtrip()
+}
+
+/// The keysym that types `c`, or `None` for control characters other than
+/// newline and tab.
+fn keysym_name(c: char) -> Option<String> {
+    match c {
+        '\n' => Some("Return".to_string()),
+        '\t' => Some("Tab".to_string()),
+        c if c.is_control() => None,
+        c => Some(format!("U{:04X}", c as u32)),
+    }
+}
+
+/// An XKB keymap with one key per keysym, each on its own keycode from 9.
+fn build_keymap(keysyms: &[String]) -> String {
+    let mut keycodes = String::new();
+    let mut symbols = String::new();
+    for (index, keysym) in keysyms.iter().enumerate() {
+        keycodes.push_str(&format!("<K{}> = {};\n", index + 1, index + 9));
+        symbols.push_str(&format!("key <K{}> {{ [ {} ] }};\n", index + 1, keysym));
+    }
+
+    format!(
+        "xkb_keymap {{\n\
+         xkb_keycodes \"aleflow\" {{\nminimum = 8;\nmaximum = {};\n{}}};\n\
+         xkb_types \"aleflow\" {{ include \"complete\" }};\n\
+         xkb_compatibility \"aleflow\" {{ include \"complete\" }};\n\
+         xkb_symbols \"aleflow\" {{\n{}}};\n\
+         }};\n",
+        keysyms.len().max(1) + 8,
+        keycodes,
+        symbols
+    )
+}
+
+impl Dispatch<ZwpInputMethodV2, ()> for State {
+    fn event(
+        state: &mut Self,
+        _: &ZwpInputMethodV2,
+        event: zwp_input_method_v2::Event,
+        _: &(),
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+        match event {
+            zwp_input_method_v2::Event::Activate => state.active = true,
+            zwp_input_method_v2::Event::Deactivate => state.active = false,
+            zwp_input_method_v2::Event::Done => state.done_count += 1,
+            zwp_input_method_v2::Event::Unavailable => state.unavailable = true,
+            _ => {}
+        }
+    }
+}
+
+impl Dispatch<WlRegistry, GlobalListContents> for State {
+    fn event(
+        _: &mut Self,
+        _: &WlRegistry,
+        _: <WlRegistry as wayland_client::Proxy>::Event,
+        _: &GlobalListContents,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+    }
+}
+
+delegate_noop!(State: ignore WlSeat);
+delegate_noop!(State: ZwpInputMethodManagerV2);
+delegate_noop!(State: ZwpVirtualKeyboardManagerV1);
+delegate_noop!(State: ZwpVirtualKeyboardV1);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn characters_map_to_unicode_keysyms() {
+        assert_eq!(keysym_name('a').as_deref(), Some("U0061"));
+        assert_eq!(keysym_name('ש').as_deref(), Some("U05E9"));
+        assert_eq!(keysym_name('😀').as_deref(), Some("U1F600"));
+        assert_eq!(keysym_name('\n').as_deref(), Some("Return"));
+        assert_eq!(keysym_name('\r'), None);
+    }
+
+    #[test]
+    fn keymap_numbers_keys_from_nine() {
+        let keymap = build_keymap(&["U05E9".to_string(), "Return".to_string()]);
+        assert!(keymap.contains("maximum = 10;"));
+        assert!(keymap.contains("<K1> = 9;\n<K2> = 10;\n"));
+        assert!(keymap.contains("key <K2> { [ Return ] };"));
+    }
+}
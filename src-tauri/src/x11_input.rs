@@ -0,0 +1,295 @@
+//! Types text on X11 through the XTEST extension instead of forking xdotool.
+//! Characters the current layout can't produce are typed by temporarily
+//! binding them to keycodes the layout leaves unused.
+
+use log::debug;
+use std::collections::HashMap;
+use std::ops::Range;
+use std::thread;
+use std::time::Duration;
+use x11rb::connection::{Connection, RequestConnection};
+use x11rb::protocol::xkb::{self, ConnectionExt as _};
+use x11rb::protocol::xproto::{
+    ConnectionExt as _, KeyButMask, Keycode, Keysym, Window, KEY_PRESS_EVENT, KEY_RELEASE_EVENT,
+};
+use x11rb::protocol::xtest::{self, ConnectionExt as _};
+use x11rb::rust_connection::RustConnection;
+use x11rb::{CURRENT_TIME, NONE};
+
+const XK_SHIFT_L: Keysym = 0xffe1;
+const XK_TAB: Keysym = 0xff09;
+const XK_RETURN: Keysym = 0xff0d;
+
+/// Lets applications finish reading the last characters typed on borrowed
+/// keycodes before their binding goes away.
+const UNBIND_SETTLE: Duration = Duration::from_millis(50);
+
+/// Keysyms for characters outside Latin-1 are the code point plus this.
+const UNICODE_KEYSYM_OFFSET: u32 = 0x0100_0000;
+
+struct Keyboard {
+    conn: RustConnection,
+    root: Window,
+    min_keycode: Keycode,
+    keysyms_per_keycode: u8,
+    mapping: Vec<Keysym>,
+    /// Columns of `mapping` holding the base and shifted level of the active
+    /// layout group. `None` when the group can't be told from the core
+    /// mapping, so every character is typed through a spare keycode.
+    group_columns: Option<Range<usize>>,
+    shift: Option<Keycode>,
+    /// Keycodes without symbols, handed out in turn so a character's binding
+    /// isn't replaced while the application might still be looking it up.
+    spare: Vec<Keycode>,
+    next_spare: usize,
+    bound: HashMap<Keysym, Keycode>,
+}
+
+impl Keyboard {
+    fn connect() -> Result<Self, String> {
+        let (conn, screen_num) = x11rb::connect(None).map_err(|e| e.to_string())?;
+        if conn
+            .extension_information(xtest::X11_EXTENSION_NAME)
+            .map_err(|e| e.to_string())?
+            .is_none()
+        {
+            return Err("XTEST extension not available".to_string());
+        }
+
+        let root = conn.setup().roots[screen_num].root;
+        let min_keycode = conn.setup().min_keycode;
+        let count = conn.setup().max_keycode - min_keycode + 1;
+        let reply = conn
+            .get_keyboard_mapping(min_keycode, count)
+            .map_err(|e| e.to_string())?
+            .reply()
+            .map_err(|e| e.to_string())?;
+
+        let group_columns = group_columns(active_group(&conn), reply.keysyms_per_keycode);
+        let mut keyboard = Self {
+            conn,
+            root,
+            min_keycode,
+            keysyms_per_keycode: reply.keysyms_per_keycode,
+            mapping: reply.keysyms,
+            group_columns,
+            shift: None,
+            spare: Vec::new(),
+            next_spare: 0,
+            bound: HashMap::new(),
+        };
+        keyboard.shift = keyboard.find(XK_SHIFT_L).map(|(keycode, _)| keycode);
+        keyboard.spare = keyboard
+            .keycodes()
+            .filter(|(_, keysyms)| keysyms.iter().all(|&k| k == NONE))
+            .map(|(keycode, _)| keycode)
+            .collect();
+        Ok(keyboard)
+    }
+
+    fn keycodes(&self) -> impl Iterator<Item = (Keycode, &[Keysym])> {
+        self.mapping
+            .chunks(self.keysyms_per_keycode.max(1) as usize)
+            .enumerate()
+            .map(|(i, keysyms)| (self.min_keycode + i as u8, keysyms))
+    }
+
+    /// The keycode that produces `keysym` on the base or shifted level of the
+    /// active group.
+    fn find(&self, keysym: Keysym) -> Option<(Keycode, bool)> {
+        let columns = self.group_columns.clone()?;
+        self.keycodes().find_map(|(keycode, keysyms)| {
+            keysyms
+                .get(columns.clone())?
+                .iter()
+                .position(|&k| k == keysym)
+                .map(|level| (keycode, level == 1))
+        })
+    }
+
+    /// Binds `keysym` to a spare keycode on every level.
+    fn bind(&mut self, keysym: Keysym) -> Result<Keycode, String> {
+        if let Some(&keycode) = self.bound.get(&keysym) {
+            return Ok(keycode);
+        }
+        if self.spare.is_empty() {
+            return Err("no spare keycode to type unmapped characters".to_string());
+        }
+
+        let keycode = self.spare[self.next_spare];
+        self.next_spare = (self.next_spare + 1) % self.spare.len();
+        self.bound.retain(|_, &mut bound| bound != keycode);
+        self.bound.insert(keysym, keycode);
+
+        let keysyms = vec![keysym; self.keysyms_per_keycode as usize];
+        self.conn
+            .change_keyboard_mapping(1, keycode, self.keysyms_per_keycode, &keysyms)
+            .map_err(|e| e.to_string())?;
+        self.sync()?;
+        Ok(keycode)
+    }
+
+    fn fake_key(&self, event: u8, keycode: Keycode) -> Result<(), String> {
+        self.conn
+            .xtest_fake_input(event, keycode, CURRENT_TIME, self.root, 0, 0, 0)
+            .map_err(|e| e.to_string())?;
+        Ok(())
+    }
+
+    fn type_keysym(&mut self, keysym: Keysym) -> Result<(), String> {
+        let (keycode, shifted) = match self.find(keysym) {
+            Some((_, true)) if self.shift.is_none() => (self.bind(keysym)?, false),
+            Some(found) => found,
+            None => (self.bind(keysym)?, false),
+        };
+
+        let shift = self.shift.filter(|_| shifted);
+        if let Some(shift) = shift {
+            self.fake_key(KEY_PRESS_EVENT, shift)?;
+        }
+        self.fake_key(KEY_PRESS_EVENT, keycode)?;
+        self.fake_key(KEY_RELEASE_EVENT, keycode)?;
+        if let Some(shift) = shift {
+            self.fake_key(KEY_RELEASE_EVENT, shift)?;
+        }
+        self.conn.flush().map_err(|e| e.to_string())
+    }
+
+    /// Waits until the server has handled everything sent so far.
+    fn sync(&self) -> Result<(), String> {
+        self.conn
+            .get_input_focus()
+            .map_err(|e| e.to_string())?
+            .reply()
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    }
+
+    /// Gives the borrowed keycodes back their empty mapping.
+    fn unbind_all(&mut self) -> Result<(), String> {
+        if self.bound.is_empty() {
+            return Ok(());
+        }
+        thread::sleep(UNBIND_SETTLE);
+
+        let empty = vec![NONE; self.keysyms_per_keycode as usize];
+        for (_, keycode) in self.bound.drain() {
+            self.conn
+                .change_keyboard_mapping(1, keycode, self.keysyms_per_keycode, &empty)
+                .map_err(|e| e.to_string())?;
+        }
+        self.sync()
+    }
+}
+
+/// Types `text` into the focused window, pausing `delay` after each character
+/// and waiting for the X server to catch up every `chunk_size` characters.
+pub fn type_text(text: &str, delay: Duration, chunk_size: usize) -> Result<(), String> {
+    let mut keyboard = Keyboard::connect()?;
+    debug!(
+        "Typing through XTEST with {} spare keycodes",
+        keyboard.spare.len()
+    );
+
+    let keysyms: Vec<Keysym> = text.chars().filter_map(keysym_for).collect();
+    let mut result = Ok(());
+    for chunk in keysyms.chunks(chunk_size.max(1)) {
+        result = chunk.iter().try_for_each(|&keysym| {
+            keyboard.type_keysym(keysym)?;
+            if !delay.is_zero() {
+                thread::sleep(delay);
+            }
+            Ok(())
+        });
+        if result.is_ok() {
+            result = keyboard.sync();
+        }
+        if result.is_err() {
+            break;
+        }
+    }
+
+    let restored = keyboard.unbind_all();
+    result.and(restored)
+}
+
+/// Whether Shift, Control, Alt or Super is held on the core keyboard.
+pub fn modifiers_held() -> Result<bool, String> {
+    let (conn, screen_num) = x11rb::connect(None).map_err(|e| e.to_string())?;
+    let root = conn.setup().roots[screen_num].root;
+    let pointer = conn
+        .query_pointer(root)
+        .map_err(|e| e.to_string())?
+        .reply()
+        .map_err(|e| e.to_string())?;
+    Ok(pointer
+        .mask
+        .intersects(KeyButMask::SHIFT | KeyButMask::CONTROL | KeyButMask::MOD1 | KeyButMask::MOD4))
+}
+
+/// The layout group currently in use, or the first one if XKB isn't there to
+/// ask.
+fn active_group(conn: &RustConnection) -> u8 {
+    let state = conn
+        .xkb_use_extension(1, 0)
+        .ok()
+        .and_then(|cookie| cookie.reply().ok())
+        .filter(|reply| reply.supported)
+        .and_then(|_| conn.xkb_get_state(xkb::ID::USE_CORE_KBD.into()).ok())
+        .and_then(|cookie| cookie.reply().ok());
+    match state {
+        Some(state) => u8::from(state.group),
+        None => {
+            debug!("XKB unavailable, assuming the first layout group");
+            0
+        }
+    }
+}
+
+/// Where the core keyboard mapping keeps `group`'s first two levels. XKB
+/// lists the first two groups there side by side (repeating the first for
+/// keys with only one); later groups don't have a fixed place.
+fn group_columns(group: u8, keysyms_per_keycode: u8) -> Option<Range<usize>> {
+    let start = match group {
+        0 => 0,
+        1 => 2,
+        _ => return None,
+    };
+    (start + 2 <= keysyms_per_keycode as usize).then_some(start..start + 2)
+}
+
+/// The keysym that types `c`, or `None` for control characters other than
+/// newline and tab.
+pub(crate) fn keysym_for(c: char) -> Option<Keysym> {
+    match c {
+        '\n' => Some(XK_RETURN),
+        '\t' => Some(XK_TAB),
+        c if c.is_control() => None,
+        // Latin-1 keysyms are the code points themselves
+        c if (c as u32) < 0x100 => Some(c as u32),
+        c => Some(c as u32 + UNICODE_KEYSYM_OFFSET),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn characters_map_to_keysyms() {
+        assert_eq!(keysym_for('a'), Some(0x61));
+        assert_eq!(keysym_for('é'), Some(0xe9));
+        assert_eq!(keysym_for('ש'), Some(0x10005e9));
+        assert_eq!(keysym_for('\n'), Some(XK_RETURN));
+        assert_eq!(keysym_for('\u{7f}'), None);
+    }
+
+    #[test]
+    fn groups_map_to_core_mapping_columns() {
+        assert_eq!(group_columns(0, 4), Some(0..2));
+        // A Hebrew+US setup with US active reads the second group
+        assert_eq!(group_columns(1, 7), Some(2..4));
+        assert_eq!(group_columns(1, 2), None);
+        assert_eq!(group_columns(2, 8), None);
+    }
+}
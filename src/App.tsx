@@ -1,12 +1,14 @@
 import { useEffect, useState } from "react";
-import { Toaster } from "sonner";
+import { listen } from "@tauri-apps/api/event";
+import { useTranslation } from "react-i18next";
+import { Toaster, toast } from "sonner";
 import "./App.css";
 import AccessibilityPermissions from "./components/AccessibilityPermissions";
 import Footer from "./components/footer";
 import Onboarding from "./components/onboarding";
 import { Sidebar, SidebarSection, SECTIONS_CONFIG } from "./components/Sidebar";
 import { useSettings } from "./hooks/useSettings";
-import { commands } from "@/bindings";
+import { commands, type ConfigFileStatus } from "@/bindings";
 
 const renderSettingsContent = (section: SidebarSection) => {
   const ActiveComponent =
@@ -18,12 +20,65 @@ function App() {
   const [showOnboarding, setShowOnboarding] = useState<boolean | null>(null);
   const [currentSection, setCurrentSection] =
     useState<SidebarSection>("general");
-  const { settings, updateSetting } = useSettings();
+  const { t } = useTranslation();
+  const { settings, updateSetting, refreshSettings, refreshAudioDevices } =
+    useSettings();
 
   useEffect(() => {
     checkOnboardingStatus();
   }, []);
 
+  // Settings can change outside the UI, e.g. from an import or the config file
+  useEffect(() => {
+    const showConfigFileError = (status: ConfigFileStatus) => {
+      if (status.error) {
+        toast.error(
+          t("errors.configFile", { path: status.path, error: status.error }),
+        );
+      }
+    };
+
+    commands.getConfigFileStatus().then((result) => {
+      if (result.status === "ok") {
+        showConfigFileError(result.data);
+      }
+    });
+
+    const configErrorUnlisten = listen<ConfigFileStatus>(
+      "config-file-error",
+      (event) => showConfigFileError(event.payload),
+    );
+    const settingsChangedUnlisten = listen("settings-changed", () => {
+      refreshSettings();
+    });
+    const clippingUnlisten = listen("audio-clipping", () => {
+      toast.warning(t("errors.audioClipping"));
+    });
+    const devicesChangedUnlisten = listen("audio-devices-changed", () => {
+      refreshAudioDevices();
+    });
+    const microphoneChangedUnlisten = listen<string | null>(
+      "microphone-changed",
+      (event) => {
+        if (event.payload) {
+          toast.info(
+            t("settings.sound.microphone.changed", { name: event.payload }),
+          );
+        } else {
+          toast.error(t("errors.microphoneUnavailable"));
+        }
+      },
+    );
+
+    return () => {
+      configErrorUnlisten.then((fn) => fn());
+      settingsChangedUnlisten.then((fn) => fn());
+      clippingUnlisten.then((fn) => fn());
+      devicesChangedUnlisten.then((fn) => fn());
+      microphoneChangedUnlisten.then((fn) => fn());
+    };
+  }, [t, refreshSettings, refreshAudioDevices]);
+
   // Handle keyboard shortcuts for debug mode toggle
   useEffect(() => {
     const handleKeyDown = (event: KeyboardEvent) => {
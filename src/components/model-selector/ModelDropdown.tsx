@@ -1,6 +1,6 @@
 import React from "react";
 import { useTranslation } from "react-i18next";
-import type { ModelInfo } from "@/bindings";
+import type { ModelBenchmark, ModelInfo } from "@/bindings";
 import { formatModelSize } from "../../lib/utils/format";
 import {
   getTranslatedModelName,
@@ -19,9 +19,13 @@ interface ModelDropdownProps {
   models: ModelInfo[];
   currentModelId: string;
   downloadProgress: Map<string, DownloadProgress>;
+  benchmarks: ModelBenchmark[];
+  recommendedModelId: string;
+  isBenchmarking: boolean;
   onModelSelect: (modelId: string) => void;
   onModelDownload: (modelId: string) => void;
   onModelDelete: (modelId: string) => Promise<void>;
+  onRunBenchmark: () => void;
   onError?: (error: string) => void;
 }
 
@@ -29,9 +33,13 @@ const ModelDropdown: React.FC<ModelDropdownProps> = ({
   models,
   currentModelId,
   downloadProgress,
+  benchmarks,
+  recommendedModelId,
+  isBenchmarking,
   onModelSelect,
   onModelDownload,
   onModelDelete,
+  onRunBenchmark,
   onError,
 }) => {
   const { t } = useTranslation();
@@ -39,6 +47,17 @@ const ModelDropdown: React.FC<ModelDropdownProps> = ({
   const downloadableModels = models.filter((m) => !m.is_downloaded);
   const isFirstRun = availableModels.length === 0 && models.length > 0;
 
+  const getBenchmarkText = (modelId: string) => {
+    const benchmark = benchmarks.find((b) => b.model_id === modelId);
+    if (!benchmark) {
+      return null;
+    }
+    return t("modelSelector.benchmark.measured", {
+      rtf: benchmark.real_time_factor.toFixed(2),
+      wer: (benchmark.word_error_rate * 100).toFixed(1),
+    });
+  };
+
   const handleDeleteClick = async (e: React.MouseEvent, modelId: string) => {
     e.preventDefault();
     e.stopPropagation();
@@ -111,6 +130,11 @@ const ModelDropdown: React.FC<ModelDropdownProps> = ({
                   <div className="text-xs text-text/40 italic pr-4">
                     {getTranslatedModelDescription(model, t)}
                   </div>
+                  {getBenchmarkText(model.id) && (
+                    <div className="mt-1 text-xs text-text/50 tabular-nums">
+                      {getBenchmarkText(model.id)}
+                    </div>
+                  )}
                 </div>
                 <div className="flex items-center gap-2">
                   {currentModelId === model.id && (
@@ -143,6 +167,15 @@ const ModelDropdown: React.FC<ModelDropdownProps> = ({
               </div>
             </div>
           ))}
+          <button
+            onClick={onRunBenchmark}
+            disabled={isBenchmarking}
+            className="w-full px-3 py-1.5 text-left text-xs text-logo-primary hover:bg-mid-gray/10 transition-colors disabled:opacity-50 disabled:cursor-not-allowed"
+          >
+            {isBenchmarking
+              ? t("modelSelector.benchmark.running")
+              : t("modelSelector.benchmark.run")}
+          </button>
         </div>
       )}
 
@@ -184,7 +217,7 @@ const ModelDropdown: React.FC<ModelDropdownProps> = ({
                   <div>
                     <div className="text-sm">
                       {getTranslatedModelName(model, t)}
-                      {model.id === "parakeet-tdt-0.6b-v3" && isFirstRun && (
+                      {model.id === recommendedModelId && isFirstRun && (
                         <span className="ml-2 text-xs bg-logo-primary/20 text-logo-primary px-1.5 py-0.5 rounded">
                           {t("onboarding.recommended")}
                         </span>
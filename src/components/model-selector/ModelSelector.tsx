@@ -1,7 +1,7 @@
 import React, { useState, useRef, useEffect } from "react";
 import { useTranslation } from "react-i18next";
 import { listen } from "@tauri-apps/api/event";
-import { commands, type ModelInfo } from "@/bindings";
+import { commands, type ModelBenchmark, type ModelInfo } from "@/bindings";
 import { getTranslatedModelName } from "../../lib/utils/modelTranslation";
 import ModelStatusButton from "./ModelStatusButton";
 import ModelDropdown from "./ModelDropdown";
@@ -57,12 +57,16 @@ const ModelSelector: React.FC<ModelSelectorProps> = ({ onError }) => {
   const [extractingModels, setExtractingModels] = useState<Set<string>>(
     new Set(),
   );
+  const [benchmarks, setBenchmarks] = useState<ModelBenchmark[]>([]);
+  const [recommendedModelId, setRecommendedModelId] = useState<string>("");
+  const [isBenchmarking, setIsBenchmarking] = useState(false);
 
   const dropdownRef = useRef<HTMLDivElement>(null);
 
   useEffect(() => {
     loadModels();
     loadCurrentModel();
+    loadBenchmarks();
 
     // Listen for model state changes
     const modelStateUnlisten = listen<ModelStateEvent>(
@@ -257,6 +261,36 @@ const ModelSelector: React.FC<ModelSelectorProps> = ({ onError }) => {
     }
   };
 
+  const loadBenchmarks = async () => {
+    try {
+      const [benchmarkResult, recommendedResult] = await Promise.all([
+        commands.getModelBenchmarks(),
+        commands.getRecommendedFirstModel(),
+      ]);
+      if (benchmarkResult.status === "ok") {
+        setBenchmarks(benchmarkResult.data);
+      }
+      if (recommendedResult.status === "ok") {
+        setRecommendedModelId(recommendedResult.data);
+      }
+    } catch (err) {
+      console.error("Failed to load benchmarks:", err);
+    }
+  };
+
+  const handleRunBenchmark = async () => {
+    setIsBenchmarking(true);
+    try {
+      const result = await commands.runModelBenchmark();
+      if (result.status === "error") {
+        onError?.(result.error);
+      }
+      await loadBenchmarks();
+    } finally {
+      setIsBenchmarking(false);
+    }
+  };
+
   const loadCurrentModel = async () => {
     try {
       const result = await commands.getCurrentModel();
@@ -418,9 +452,13 @@ const ModelSelector: React.FC<ModelSelectorProps> = ({ onError }) => {
             models={models}
             currentModelId={currentModelId}
             downloadProgress={modelDownloadProgress}
+            benchmarks={benchmarks}
+            recommendedModelId={recommendedModelId}
+            isBenchmarking={isBenchmarking}
             onModelSelect={handleModelSelect}
             onModelDownload={handleModelDownload}
             onModelDelete={handleModelDelete}
+            onRunBenchmark={handleRunBenchmark}
             onError={onError}
           />
         )}
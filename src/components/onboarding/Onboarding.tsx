@@ -12,9 +12,15 @@ const Onboarding: React.FC<OnboardingProps> = ({ onModelSelected }) => {
   const [availableModels, setAvailableModels] = useState<ModelInfo[]>([]);
   const [downloading, setDownloading] = useState(false);
   const [error, setError] = useState<string | null>(null);
+  const [recommendedModelId, setRecommendedModelId] = useState<string>("");
 
   useEffect(() => {
     loadModels();
+    commands.getRecommendedFirstModel().then((result) => {
+      if (result.status === "ok") {
+        setRecommendedModelId(result.data);
+      }
+    });
   }, []);
 
   const loadModels = async () => {
@@ -54,7 +60,7 @@ const Onboarding: React.FC<OnboardingProps> = ({ onModelSelected }) => {
   };
 
   const getRecommendedBadge = (modelId: string): boolean => {
-    return modelId === "parakeet-tdt-0.6b-v3";
+    return modelId === recommendedModelId;
   };
 
   return (
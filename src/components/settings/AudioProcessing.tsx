@@ -0,0 +1,102 @@
+import React from "react";
+import { useTranslation } from "react-i18next";
+import { toast } from "sonner";
+import { commands } from "@/bindings";
+import { ToggleSwitch } from "../ui/ToggleSwitch";
+import { SettingContainer } from "../ui/SettingContainer";
+import { Button } from "../ui/Button";
+import { useSettings } from "../../hooks/useSettings";
+
+interface AudioProcessingProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const AudioProcessing: React.FC<AudioProcessingProps> = ({
+  descriptionMode = "tooltip",
+  grouped = false,
+}) => {
+  const { t } = useTranslation();
+  const { getSetting, updateSetting, isUpdating } = useSettings();
+
+  const abCaptureEnabled = getSetting("dsp_ab_capture_enabled") || false;
+
+  const handleOpenComparisons = async () => {
+    const result = await commands.openDspComparisonFolder();
+    if (result.status === "error") {
+      toast.error(result.error);
+    }
+  };
+
+  return (
+    <>
+      <ToggleSwitch
+        checked={getSetting("dsp_high_pass_enabled") || false}
+        onChange={(value) => updateSetting("dsp_high_pass_enabled", value)}
+        isUpdating={isUpdating("dsp_high_pass_enabled")}
+        label={t("settings.advanced.audioProcessing.highPass.label")}
+        description={t(
+          "settings.advanced.audioProcessing.highPass.description",
+        )}
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+      />
+      <ToggleSwitch
+        checked={getSetting("dsp_denoise_enabled") || false}
+        onChange={(value) => updateSetting("dsp_denoise_enabled", value)}
+        isUpdating={isUpdating("dsp_denoise_enabled")}
+        label={t("settings.advanced.audioProcessing.denoise.label")}
+        description={t("settings.advanced.audioProcessing.denoise.description")}
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+      />
+      <ToggleSwitch
+        checked={getSetting("dsp_agc_enabled") || false}
+        onChange={(value) => updateSetting("dsp_agc_enabled", value)}
+        isUpdating={isUpdating("dsp_agc_enabled")}
+        label={t("settings.advanced.audioProcessing.agc.label")}
+        description={t("settings.advanced.audioProcessing.agc.description")}
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+      />
+      <ToggleSwitch
+        checked={getSetting("dsp_clipping_detection_enabled") ?? true}
+        onChange={(value) =>
+          updateSetting("dsp_clipping_detection_enabled", value)
+        }
+        isUpdating={isUpdating("dsp_clipping_detection_enabled")}
+        label={t("settings.advanced.audioProcessing.clippingDetection.label")}
+        description={t(
+          "settings.advanced.audioProcessing.clippingDetection.description",
+        )}
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+      />
+      <ToggleSwitch
+        checked={abCaptureEnabled}
+        onChange={(value) => updateSetting("dsp_ab_capture_enabled", value)}
+        isUpdating={isUpdating("dsp_ab_capture_enabled")}
+        label={t("settings.advanced.audioProcessing.abCapture.label")}
+        description={t(
+          "settings.advanced.audioProcessing.abCapture.description",
+        )}
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+      />
+      {abCaptureEnabled && (
+        <SettingContainer
+          title={t("settings.advanced.audioProcessing.comparisons.title")}
+          description={t(
+            "settings.advanced.audioProcessing.comparisons.description",
+          )}
+          descriptionMode={descriptionMode}
+          grouped={grouped}
+        >
+          <Button onClick={handleOpenComparisons} variant="secondary" size="sm">
+            {t("common.open")}
+          </Button>
+        </SettingContainer>
+      )}
+    </>
+  );
+};
@@ -0,0 +1,64 @@
+import React, { useEffect, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { type as getOsType } from "@tauri-apps/plugin-os";
+import { Dropdown } from "../ui/Dropdown";
+import { SettingContainer } from "../ui/SettingContainer";
+import { useSettings } from "../../hooks/useSettings";
+import type { CaptureSource } from "@/bindings";
+
+interface CaptureSourceSelectorProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const CaptureSourceSelector: React.FC<CaptureSourceSelectorProps> =
+  React.memo(({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting, isUpdating } = useSettings();
+    const [osType, setOsType] = useState<string>("unknown");
+
+    useEffect(() => {
+      setOsType(getOsType());
+    }, []);
+
+    // System audio is only captured on Linux so far
+    if (osType !== "linux") {
+      return null;
+    }
+
+    const selectedSource = (getSetting("capture_source") ||
+      "microphone") as CaptureSource;
+
+    const sourceOptions = [
+      {
+        value: "microphone",
+        label: t("settings.sound.captureSource.options.microphone"),
+      },
+      {
+        value: "system_audio",
+        label: t("settings.sound.captureSource.options.systemAudio"),
+      },
+      {
+        value: "mixed",
+        label: t("settings.sound.captureSource.options.mixed"),
+      },
+    ];
+
+    return (
+      <SettingContainer
+        title={t("settings.sound.captureSource.title")}
+        description={t("settings.sound.captureSource.description")}
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+      >
+        <Dropdown
+          options={sourceOptions}
+          selectedValue={selectedSource}
+          onSelect={(value) =>
+            updateSetting("capture_source", value as CaptureSource)
+          }
+          disabled={isUpdating("capture_source")}
+        />
+      </SettingContainer>
+    );
+  });
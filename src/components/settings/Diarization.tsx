@@ -0,0 +1,30 @@
+import React from "react";
+import { useTranslation } from "react-i18next";
+import { ToggleSwitch } from "../ui/ToggleSwitch";
+import { useSettings } from "../../hooks/useSettings";
+
+interface DiarizationProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const Diarization: React.FC<DiarizationProps> = React.memo(
+  ({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting, isUpdating } = useSettings();
+
+    const enabled = getSetting("diarization_enabled") ?? false;
+
+    return (
+      <ToggleSwitch
+        checked={enabled}
+        onChange={(enabled) => updateSetting("diarization_enabled", enabled)}
+        isUpdating={isUpdating("diarization_enabled")}
+        label={t("settings.general.diarization.label")}
+        description={t("settings.general.diarization.description")}
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+      />
+    );
+  },
+);
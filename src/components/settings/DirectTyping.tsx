@@ -0,0 +1,61 @@
+import React, { useEffect, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { type as getOsType } from "@tauri-apps/plugin-os";
+import { Slider } from "../ui/Slider";
+import { useSettings } from "../../hooks/useSettings";
+
+interface DirectTypingProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const DirectTyping: React.FC<DirectTypingProps> = React.memo(
+  ({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting } = useSettings();
+    const [osType, setOsType] = useState<string>("unknown");
+
+    useEffect(() => {
+      setOsType(getOsType());
+    }, []);
+
+    // Only the built-in X11 typing backend paces itself
+    if (osType !== "linux" || getSetting("paste_method") !== "direct") {
+      return null;
+    }
+
+    const delayMs = getSetting("typing_delay_ms") ?? 0;
+    const chunkSize = getSetting("typing_chunk_size") ?? 50;
+
+    return (
+      <>
+        <Slider
+          value={delayMs}
+          onChange={(value) => updateSetting("typing_delay_ms", value)}
+          min={0}
+          max={100}
+          step={1}
+          label={t("settings.advanced.directTyping.delay.title")}
+          description={t("settings.advanced.directTyping.delay.description")}
+          descriptionMode={descriptionMode}
+          grouped={grouped}
+          formatValue={(value) => `${value} ms`}
+        />
+        <Slider
+          value={chunkSize}
+          onChange={(value) => updateSetting("typing_chunk_size", value)}
+          min={10}
+          max={500}
+          step={10}
+          label={t("settings.advanced.directTyping.chunkSize.title")}
+          description={t(
+            "settings.advanced.directTyping.chunkSize.description",
+          )}
+          descriptionMode={descriptionMode}
+          grouped={grouped}
+          formatValue={(value) => `${value}`}
+        />
+      </>
+    );
+  },
+);
@@ -0,0 +1,109 @@
+import React from "react";
+import { useTranslation } from "react-i18next";
+import { useSettings } from "../../hooks/useSettings";
+import { Button } from "../ui/Button";
+import { Dropdown } from "../ui/Dropdown";
+import { SettingContainer } from "../ui/SettingContainer";
+
+interface FallbackMicrophonesProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const FallbackMicrophones: React.FC<FallbackMicrophonesProps> =
+  React.memo(({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting, isUpdating, audioDevices } =
+      useSettings();
+    const fallbacks = getSetting("fallback_microphones") || [];
+    const selected = getSetting("selected_microphone");
+    const updating = isUpdating("fallback_microphones");
+
+    const connected = new Set(audioDevices.map((device) => device.name));
+    const addOptions = audioDevices
+      .filter(
+        (device) =>
+          device.index !== "default" &&
+          device.name !== selected &&
+          !fallbacks.includes(device.name),
+      )
+      .map((device) => ({ value: device.name, label: device.name }));
+
+    const handleAdd = (name: string) => {
+      updateSetting("fallback_microphones", [...fallbacks, name]);
+    };
+
+    const handleRemove = (name: string) => {
+      updateSetting(
+        "fallback_microphones",
+        fallbacks.filter((fallback) => fallback !== name),
+      );
+    };
+
+    const handleMoveUp = (index: number) => {
+      const reordered = [...fallbacks];
+      [reordered[index - 1], reordered[index]] = [
+        reordered[index],
+        reordered[index - 1],
+      ];
+      updateSetting("fallback_microphones", reordered);
+    };
+
+    return (
+      <>
+        <SettingContainer
+          title={t("settings.sound.fallbackMicrophones.title")}
+          description={t("settings.sound.fallbackMicrophones.description")}
+          descriptionMode={descriptionMode}
+          grouped={grouped}
+        >
+          <Dropdown
+            options={addOptions}
+            selectedValue={null}
+            onSelect={handleAdd}
+            placeholder={t("settings.sound.fallbackMicrophones.add")}
+            disabled={updating || addOptions.length === 0}
+          />
+        </SettingContainer>
+        {fallbacks.length > 0 && (
+          <div
+            className={`px-4 p-2 ${grouped ? "" : "rounded-lg border border-mid-gray/20"} flex flex-col gap-1`}
+          >
+            {fallbacks.map((name, index) => (
+              <div key={name} className="flex items-center gap-2 text-sm">
+                <span className="w-5 text-mid-gray">{index + 1}.</span>
+                <span className="flex-1 truncate">{name}</span>
+                {!connected.has(name) && (
+                  <span className="text-xs text-mid-gray">
+                    {t("settings.sound.fallbackMicrophones.disconnected")}
+                  </span>
+                )}
+                <Button
+                  onClick={() => handleMoveUp(index)}
+                  disabled={index === 0 || updating}
+                  variant="secondary"
+                  size="sm"
+                  aria-label={t("settings.sound.fallbackMicrophones.moveUp", {
+                    name,
+                  })}
+                >
+                  ↑
+                </Button>
+                <Button
+                  onClick={() => handleRemove(name)}
+                  disabled={updating}
+                  variant="secondary"
+                  size="sm"
+                  aria-label={t("settings.sound.fallbackMicrophones.remove", {
+                    name,
+                  })}
+                >
+                  ✕
+                </Button>
+              </div>
+            ))}
+          </div>
+        )}
+      </>
+    );
+  });
@@ -0,0 +1,56 @@
+import React from "react";
+import { useTranslation } from "react-i18next";
+import { Dropdown } from "../ui/Dropdown";
+import { SettingContainer } from "../ui/SettingContainer";
+import { useSettings } from "../../hooks/useSettings";
+import type { FocusChangeBehavior } from "@/bindings";
+
+interface FocusChangeBehaviorProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const FocusChangeBehaviorSetting: React.FC<FocusChangeBehaviorProps> =
+  React.memo(({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting, isUpdating } = useSettings();
+
+    const focusChangeOptions = [
+      {
+        value: "refocus",
+        label: t("settings.advanced.focusChange.options.refocus"),
+      },
+      {
+        value: "prompt",
+        label: t("settings.advanced.focusChange.options.prompt"),
+      },
+      {
+        value: "paste_anyway",
+        label: t("settings.advanced.focusChange.options.pasteAnyway"),
+      },
+    ];
+
+    const selectedBehavior = (getSetting("focus_change_behavior") ||
+      "refocus") as FocusChangeBehavior;
+
+    return (
+      <SettingContainer
+        title={t("settings.advanced.focusChange.title")}
+        description={t("settings.advanced.focusChange.description")}
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+      >
+        <Dropdown
+          options={focusChangeOptions}
+          selectedValue={selectedBehavior}
+          onSelect={(value) =>
+            updateSetting(
+              "focus_change_behavior",
+              value as FocusChangeBehavior,
+            )
+          }
+          disabled={isUpdating("focus_change_behavior")}
+        />
+      </SettingContainer>
+    );
+  });
@@ -0,0 +1,63 @@
+import React from "react";
+import { useTranslation } from "react-i18next";
+import { ToggleSwitch } from "../ui/ToggleSwitch";
+import { Slider } from "../ui/Slider";
+import { useSettings } from "../../hooks/useSettings";
+
+interface HandsFreeModeProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const HandsFreeMode: React.FC<HandsFreeModeProps> = React.memo(
+  ({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting, isUpdating } = useSettings();
+
+    const enabled = getSetting("hands_free_enabled") || false;
+    const silenceMs = getSetting("hands_free_silence_ms") ?? 1200;
+    const maxDurationSecs = getSetting("hands_free_max_duration_secs") ?? 60;
+
+    return (
+      <>
+        <ToggleSwitch
+          checked={enabled}
+          onChange={(value) => updateSetting("hands_free_enabled", value)}
+          isUpdating={isUpdating("hands_free_enabled")}
+          label={t("settings.general.handsFree.label")}
+          description={t("settings.general.handsFree.description")}
+          descriptionMode={descriptionMode}
+          grouped={grouped}
+        />
+        <Slider
+          value={silenceMs}
+          onChange={(value) => updateSetting("hands_free_silence_ms", value)}
+          min={300}
+          max={5000}
+          step={100}
+          label={t("settings.general.handsFree.silence.title")}
+          description={t("settings.general.handsFree.silence.description")}
+          descriptionMode={descriptionMode}
+          grouped={grouped}
+          formatValue={(value) => `${(value / 1000).toFixed(1)} s`}
+          disabled={!enabled}
+        />
+        <Slider
+          value={maxDurationSecs}
+          onChange={(value) =>
+            updateSetting("hands_free_max_duration_secs", value)
+          }
+          min={5}
+          max={300}
+          step={5}
+          label={t("settings.general.handsFree.maxDuration.title")}
+          description={t("settings.general.handsFree.maxDuration.description")}
+          descriptionMode={descriptionMode}
+          grouped={grouped}
+          formatValue={(value) => `${value} s`}
+          disabled={!enabled}
+        />
+      </>
+    );
+  },
+);
@@ -0,0 +1,235 @@
+import React, { useEffect, useRef, useState } from "react";
+import { useTranslation } from "react-i18next";
+import {
+  commands,
+  type MicrophoneCapabilities,
+  type MicrophoneCaptureSettings,
+} from "@/bindings";
+import { Dropdown } from "../ui/Dropdown";
+import { SettingContainer } from "../ui/SettingContainer";
+import { Slider } from "../ui/Slider";
+import { useSettings } from "../../hooks/useSettings";
+
+// Changing the capture reopens the microphone, so slider drags are batched
+const MIX_DEBOUNCE_MS = 400;
+
+const AUTO = "auto";
+const AVERAGE = "average";
+const CUSTOM = "custom";
+
+const BUFFER_SIZES = [64, 128, 256, 512, 1024, 2048, 4096];
+
+interface MicrophoneCaptureProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const MicrophoneCapture: React.FC<MicrophoneCaptureProps> = ({
+  descriptionMode = "tooltip",
+  grouped = false,
+}) => {
+  const { t } = useTranslation();
+  const { getSetting, updateMicrophoneCapture, isUpdating } = useSettings();
+  const [capabilities, setCapabilities] =
+    useState<MicrophoneCapabilities | null>(null);
+  const [customMix, setCustomMix] = useState<number[] | null>(null);
+  const mixTimer = useRef<ReturnType<typeof setTimeout> | null>(null);
+
+  const selected = getSetting("selected_microphone");
+  const deviceName = !selected || selected === "Default" ? "default" : selected;
+  const capture: MicrophoneCaptureSettings =
+    getSetting("microphone_capture")?.[deviceName] ?? {};
+  const channelMix = capture.channel_mix ?? [];
+  const updating = isUpdating(`microphone_capture:${deviceName}`);
+
+  useEffect(() => {
+    setCapabilities(null);
+    setCustomMix(null);
+    commands.getMicrophoneCapabilities(deviceName).then((result) => {
+      if (result.status === "ok") {
+        setCapabilities(result.data);
+      }
+    });
+  }, [deviceName]);
+
+  useEffect(
+    () => () => {
+      if (mixTimer.current) clearTimeout(mixTimer.current);
+    },
+    [],
+  );
+
+  if (!capabilities) {
+    return null;
+  }
+
+  const channels = capabilities.channels;
+  const update = (changes: MicrophoneCaptureSettings) =>
+    updateMicrophoneCapture(deviceName, { ...capture, ...changes });
+
+  // A mix with a single full-weight channel is shown as that channel
+  const soloChannel =
+    channelMix.length === channels &&
+    channelMix.filter((weight) => weight === 1).length === 1 &&
+    channelMix.every((weight) => weight === 0 || weight === 1)
+      ? channelMix.indexOf(1)
+      : -1;
+  const isCustom =
+    customMix !== null ||
+    (channelMix.length === channels && soloChannel === -1);
+  const channelMode = isCustom
+    ? CUSTOM
+    : soloChannel >= 0
+      ? String(soloChannel)
+      : AVERAGE;
+  const weights =
+    customMix ??
+    (channelMix.length === channels
+      ? channelMix
+      : Array(channels).fill(1 / channels));
+
+  const handleChannelMode = (value: string) => {
+    if (value === CUSTOM) {
+      setCustomMix(weights);
+      return;
+    }
+    setCustomMix(null);
+    if (value === AVERAGE) {
+      update({ channel_mix: [] });
+    } else {
+      const solo = Array(channels).fill(0);
+      solo[Number(value)] = 1;
+      update({ channel_mix: solo });
+    }
+  };
+
+  const handleWeight = (channel: number, weight: number) => {
+    const next = weights.map((w, i) => (i === channel ? weight : w));
+    setCustomMix(next);
+    if (mixTimer.current) clearTimeout(mixTimer.current);
+    // Leave the stored mix alone while every channel is muted
+    if (next.every((w) => w === 0)) return;
+    mixTimer.current = setTimeout(
+      () => update({ channel_mix: next }),
+      MIX_DEBOUNCE_MS,
+    );
+  };
+
+  const channelOptions = [
+    {
+      value: AVERAGE,
+      label: t("settings.advanced.microphoneCapture.channels.average"),
+    },
+    ...Array.from({ length: channels }, (_, i) => ({
+      value: String(i),
+      label: t("settings.advanced.microphoneCapture.channels.single", {
+        channel: i + 1,
+      }),
+    })),
+    {
+      value: CUSTOM,
+      label: t("settings.advanced.microphoneCapture.channels.custom"),
+    },
+  ];
+
+  const sampleRateOptions = [
+    { value: AUTO, label: t("settings.advanced.microphoneCapture.auto") },
+    ...capabilities.sample_rates.map((rate) => ({
+      value: String(rate),
+      label: `${rate} Hz`,
+    })),
+  ];
+
+  const minBuffer = capabilities.min_buffer_size ?? 0;
+  const maxBuffer = capabilities.max_buffer_size ?? Number.MAX_SAFE_INTEGER;
+  const bufferOptions = [
+    { value: AUTO, label: t("settings.advanced.microphoneCapture.auto") },
+    ...BUFFER_SIZES.filter(
+      (frames) => frames >= minBuffer && frames <= maxBuffer,
+    ).map((frames) => ({
+      value: String(frames),
+      label: t("settings.advanced.microphoneCapture.bufferSize.frames", {
+        frames,
+      }),
+    })),
+  ];
+
+  const parseOption = (value: string) =>
+    value === AUTO ? null : Number(value);
+
+  return (
+    <>
+      {channels > 1 && (
+        <SettingContainer
+          title={t("settings.advanced.microphoneCapture.channels.title")}
+          description={t(
+            "settings.advanced.microphoneCapture.channels.description",
+          )}
+          descriptionMode={descriptionMode}
+          grouped={grouped}
+        >
+          <Dropdown
+            options={channelOptions}
+            selectedValue={channelMode}
+            onSelect={handleChannelMode}
+            disabled={updating}
+          />
+        </SettingContainer>
+      )}
+      {channels > 1 &&
+        channelMode === CUSTOM &&
+        weights.map((weight, channel) => (
+          <Slider
+            key={channel}
+            value={weight}
+            onChange={(value) => handleWeight(channel, value)}
+            min={0}
+            max={1}
+            step={0.05}
+            label={t("settings.advanced.microphoneCapture.channels.single", {
+              channel: channel + 1,
+            })}
+            description={t(
+              "settings.advanced.microphoneCapture.channels.weight",
+            )}
+            descriptionMode={descriptionMode}
+            grouped={grouped}
+          />
+        ))}
+      <SettingContainer
+        title={t("settings.advanced.microphoneCapture.sampleRate.title")}
+        description={t(
+          "settings.advanced.microphoneCapture.sampleRate.description",
+        )}
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+      >
+        <Dropdown
+          options={sampleRateOptions}
+          selectedValue={
+            capture.sample_rate ? String(capture.sample_rate) : AUTO
+          }
+          onSelect={(value) => update({ sample_rate: parseOption(value) })}
+          disabled={updating}
+        />
+      </SettingContainer>
+      <SettingContainer
+        title={t("settings.advanced.microphoneCapture.bufferSize.title")}
+        description={t(
+          "settings.advanced.microphoneCapture.bufferSize.description",
+        )}
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+      >
+        <Dropdown
+          options={bufferOptions}
+          selectedValue={
+            capture.buffer_size ? String(capture.buffer_size) : AUTO
+          }
+          onSelect={(value) => update({ buffer_size: parseOption(value) })}
+          disabled={updating}
+        />
+      </SettingContainer>
+    </>
+  );
+};
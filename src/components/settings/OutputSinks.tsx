@@ -0,0 +1,188 @@
+import React, { useState } from "react";
+import { useTranslation } from "react-i18next";
+import { commands, type OutputSink, type OutputSinkKind } from "@/bindings";
+import { useSettings } from "../../hooks/useSettings";
+import { Button } from "../ui/Button";
+import { Dropdown } from "../ui/Dropdown";
+import { Input } from "../ui/Input";
+import { SettingContainer } from "../ui/SettingContainer";
+
+interface OutputSinksProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+type SinkType = OutputSinkKind["type"];
+
+const SINK_TYPES: SinkType[] = ["file", "webhook", "pipe", "stdout", "command"];
+
+// New sinks start disabled with an example target to edit
+const exampleKind = (type: SinkType): OutputSinkKind => {
+  switch (type) {
+    case "file":
+      return { type, path: "~/Documents/Journal/%Y-%m-%d.md" };
+    case "webhook":
+      return { type, url: "http://localhost:8080/dictation" };
+    case "pipe":
+      return { type, path: "/tmp/aleflow.fifo" };
+    case "stdout":
+      return { type };
+    case "command":
+      return { type, command: "cat >> ~/dictations.txt" };
+  }
+};
+
+const targetOf = (kind: OutputSinkKind): string | null => {
+  switch (kind.type) {
+    case "file":
+    case "pipe":
+      return kind.path;
+    case "webhook":
+      return kind.url;
+    case "command":
+      return kind.command;
+    case "stdout":
+      return null;
+  }
+};
+
+const withTarget = (kind: OutputSinkKind, target: string): OutputSinkKind => {
+  switch (kind.type) {
+    case "file":
+    case "pipe":
+      return { ...kind, path: target };
+    case "webhook":
+      return { ...kind, url: target };
+    case "command":
+      return { ...kind, command: target };
+    case "stdout":
+      return kind;
+  }
+};
+
+export const OutputSinks: React.FC<OutputSinksProps> = React.memo(
+  ({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, refreshSettings } = useSettings();
+    const [error, setError] = useState<string | null>(null);
+    const [saving, setSaving] = useState(false);
+    const sinks = getSetting("output_sinks") || [];
+    const prompts = getSetting("post_process_prompts") || [];
+
+    const save = async (next: OutputSink[]) => {
+      setSaving(true);
+      const result = await commands.changeOutputSinksSetting(next);
+      setError(result.status === "error" ? result.error : null);
+      await refreshSettings();
+      setSaving(false);
+    };
+
+    const update = (id: string, changes: Partial<OutputSink>) =>
+      save(
+        sinks.map((sink) => (sink.id === id ? { ...sink, ...changes } : sink)),
+      );
+
+    const handleAdd = (type: string) =>
+      save([
+        ...sinks,
+        {
+          id: `sink_${Date.now()}`,
+          enabled: false,
+          kind: exampleKind(type as SinkType),
+          prompt_id: null,
+        },
+      ]);
+
+    const typeOptions = SINK_TYPES.map((type) => ({
+      value: type,
+      label: t(`settings.advanced.outputSinks.types.${type}`),
+    }));
+    const promptOptions = [
+      { value: "", label: t("settings.advanced.outputSinks.everyDictation") },
+      ...prompts.map((prompt) => ({ value: prompt.id, label: prompt.name })),
+    ];
+
+    return (
+      <>
+        <SettingContainer
+          title={t("settings.advanced.outputSinks.title")}
+          description={t("settings.advanced.outputSinks.description")}
+          descriptionMode={descriptionMode}
+          grouped={grouped}
+        >
+          <Dropdown
+            options={typeOptions}
+            selectedValue={null}
+            onSelect={handleAdd}
+            placeholder={t("settings.advanced.outputSinks.add")}
+            disabled={saving}
+          />
+        </SettingContainer>
+        {(sinks.length > 0 || error) && (
+          <div
+            className={`px-4 p-2 ${grouped ? "" : "rounded-lg border border-mid-gray/20"} flex flex-col gap-2`}
+          >
+            {sinks.map((sink) => {
+              const target = targetOf(sink.kind);
+              return (
+                <div key={sink.id} className="flex items-center gap-2 text-sm">
+                  <input
+                    type="checkbox"
+                    checked={sink.enabled}
+                    onChange={(e) =>
+                      update(sink.id, { enabled: e.target.checked })
+                    }
+                    disabled={saving}
+                    aria-label={t("settings.advanced.outputSinks.enabled")}
+                  />
+                  <span className="w-20 shrink-0">
+                    {t(`settings.advanced.outputSinks.types.${sink.kind.type}`)}
+                  </span>
+                  {target === null ? (
+                    <span className="flex-1" />
+                  ) : (
+                    <Input
+                      key={target}
+                      type="text"
+                      defaultValue={target}
+                      onBlur={(e) => {
+                        if (e.target.value !== target) {
+                          update(sink.id, {
+                            kind: withTarget(sink.kind, e.target.value),
+                          });
+                        }
+                      }}
+                      disabled={saving}
+                      variant="compact"
+                      className="flex-1 min-w-0"
+                    />
+                  )}
+                  <Dropdown
+                    options={promptOptions}
+                    selectedValue={sink.prompt_id ?? ""}
+                    onSelect={(value) =>
+                      update(sink.id, { prompt_id: value || null })
+                    }
+                    disabled={saving}
+                  />
+                  <Button
+                    onClick={() =>
+                      save(sinks.filter((other) => other.id !== sink.id))
+                    }
+                    disabled={saving}
+                    variant="secondary"
+                    size="sm"
+                    aria-label={t("settings.advanced.outputSinks.remove")}
+                  >
+                    ✕
+                  </Button>
+                </div>
+              );
+            })}
+            {error && <p className="text-red-400 text-xs">{error}</p>}
+          </div>
+        )}
+      </>
+    );
+  },
+);
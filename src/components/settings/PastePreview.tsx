@@ -0,0 +1,56 @@
+import React from "react";
+import { useTranslation } from "react-i18next";
+import { Slider } from "../ui/Slider";
+import { ToggleSwitch } from "../ui/ToggleSwitch";
+import { useSettings } from "../../hooks/useSettings";
+
+interface PastePreviewProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const PastePreview: React.FC<PastePreviewProps> = React.memo(
+  ({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting, isUpdating } = useSettings();
+
+    const enabled = getSetting("paste_preview_enabled") ?? false;
+    const countdownSecs = getSetting("paste_preview_countdown_secs") ?? 3;
+
+    return (
+      <>
+        <ToggleSwitch
+          checked={enabled}
+          onChange={(value) => updateSetting("paste_preview_enabled", value)}
+          isUpdating={isUpdating("paste_preview_enabled")}
+          label={t("settings.advanced.pastePreview.label")}
+          description={t("settings.advanced.pastePreview.description")}
+          descriptionMode={descriptionMode}
+          grouped={grouped}
+        />
+        {enabled && (
+          <Slider
+            value={countdownSecs}
+            onChange={(value) =>
+              updateSetting("paste_preview_countdown_secs", value)
+            }
+            min={0}
+            max={30}
+            step={1}
+            label={t("settings.advanced.pastePreview.countdown.title")}
+            description={t(
+              "settings.advanced.pastePreview.countdown.description",
+            )}
+            descriptionMode={descriptionMode}
+            grouped={grouped}
+            formatValue={(value) =>
+              value === 0
+                ? t("settings.advanced.pastePreview.countdown.off")
+                : `${value} s`
+            }
+          />
+        )}
+      </>
+    );
+  },
+);
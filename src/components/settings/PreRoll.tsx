@@ -0,0 +1,33 @@
+import React from "react";
+import { useTranslation } from "react-i18next";
+import { Slider } from "../ui/Slider";
+import { useSettings } from "../../hooks/useSettings";
+
+interface PreRollProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const PreRoll: React.FC<PreRollProps> = React.memo(
+  ({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting } = useSettings();
+
+    const preRollMs = getSetting("pre_roll_ms") ?? 300;
+
+    return (
+      <Slider
+        value={preRollMs}
+        onChange={(value) => updateSetting("pre_roll_ms", value)}
+        min={0}
+        max={2000}
+        step={100}
+        label={t("settings.advanced.preRoll.title")}
+        description={t("settings.advanced.preRoll.description")}
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+        formatValue={(value) => `${value} ms`}
+      />
+    );
+  },
+);
@@ -1,45 +1,101 @@
-import React from "react";
+import React, { useEffect, useState } from "react";
 import { useTranslation } from "react-i18next";
 import { Dropdown } from "../ui/Dropdown";
 import { SettingContainer } from "../ui/SettingContainer";
 import { useSettings } from "../../hooks/useSettings";
-import type { OverlayPosition } from "@/bindings";
+import { commands, type OverlayPosition } from "@/bindings";
 
 interface ShowOverlayProps {
   descriptionMode?: "inline" | "tooltip";
   grouped?: boolean;
 }
 
+const POSITIONS: OverlayPosition[] = [
+  "none",
+  "bottom",
+  "top",
+  "bottom_left",
+  "bottom_right",
+  "top_left",
+  "top_right",
+  "caret",
+  "custom",
+  "tray",
+];
+
+// Positions placed on a monitor rather than by the caret, a drag or the tray
+const MONITOR_POSITIONS: OverlayPosition[] = [
+  "bottom",
+  "top",
+  "bottom_left",
+  "bottom_right",
+  "top_left",
+  "top_right",
+];
+
 export const ShowOverlay: React.FC<ShowOverlayProps> = React.memo(
   ({ descriptionMode = "tooltip", grouped = false }) => {
     const { t } = useTranslation();
     const { getSetting, updateSetting, isUpdating } = useSettings();
+    const [monitors, setMonitors] = useState<string[]>([]);
+
+    useEffect(() => {
+      commands.getMonitorNames().then((result) => {
+        if (result.status === "ok") {
+          setMonitors(result.data);
+        }
+      });
+    }, []);
 
-    const overlayOptions = [
-      { value: "none", label: t("settings.advanced.overlay.options.none") },
-      { value: "bottom", label: t("settings.advanced.overlay.options.bottom") },
-      { value: "top", label: t("settings.advanced.overlay.options.top") },
+    const overlayOptions = POSITIONS.map((position) => ({
+      value: position,
+      label: t(`settings.advanced.overlay.options.${position}`),
+    }));
+    const monitorOptions = [
+      { value: "", label: t("settings.advanced.overlay.monitor.followMouse") },
+      ...monitors.map((name) => ({ value: name, label: name })),
     ];
 
     const selectedPosition = (getSetting("overlay_position") ||
       "bottom") as OverlayPosition;
+    const selectedMonitor = getSetting("overlay_monitor") ?? "";
 
     return (
-      <SettingContainer
-        title={t("settings.advanced.overlay.title")}
-        description={t("settings.advanced.overlay.description")}
-        descriptionMode={descriptionMode}
-        grouped={grouped}
-      >
-        <Dropdown
-          options={overlayOptions}
-          selectedValue={selectedPosition}
-          onSelect={(value) =>
-            updateSetting("overlay_position", value as OverlayPosition)
-          }
-          disabled={isUpdating("overlay_position")}
-        />
-      </SettingContainer>
+      <>
+        <SettingContainer
+          title={t("settings.advanced.overlay.title")}
+          description={t("settings.advanced.overlay.description")}
+          descriptionMode={descriptionMode}
+          grouped={grouped}
+        >
+          <Dropdown
+            options={overlayOptions}
+            selectedValue={selectedPosition}
+            onSelect={(value) =>
+              updateSetting("overlay_position", value as OverlayPosition)
+            }
+            disabled={isUpdating("overlay_position")}
+          />
+        </SettingContainer>
+        {MONITOR_POSITIONS.includes(selectedPosition) &&
+          monitors.length > 1 && (
+            <SettingContainer
+              title={t("settings.advanced.overlay.monitor.title")}
+              description={t("settings.advanced.overlay.monitor.description")}
+              descriptionMode={descriptionMode}
+              grouped={grouped}
+            >
+              <Dropdown
+                options={monitorOptions}
+                selectedValue={selectedMonitor}
+                onSelect={(value) =>
+                  updateSetting("overlay_monitor", value || null)
+                }
+                disabled={isUpdating("overlay_monitor")}
+              />
+            </SettingContainer>
+          )}
+      </>
     );
   },
 );
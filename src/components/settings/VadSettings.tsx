@@ -0,0 +1,154 @@
+import React, { useEffect, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { listen } from "@tauri-apps/api/event";
+import { commands, type VadEngine } from "@/bindings";
+import { Dropdown } from "../ui/Dropdown";
+import { SettingContainer } from "../ui/SettingContainer";
+import { Slider } from "../ui/Slider";
+import { ToggleSwitch } from "../ui/ToggleSwitch";
+import { useSettings } from "../../hooks/useSettings";
+
+// Each VAD frame is 30 ms of audio
+const FRAME_MS = 30;
+
+interface VadSettingsProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const VadSettings: React.FC<VadSettingsProps> = ({
+  descriptionMode = "tooltip",
+  grouped = false,
+}) => {
+  const { t } = useTranslation();
+  const { getSetting, updateSetting, isUpdating } = useSettings();
+  const [isSpeech, setIsSpeech] = useState(false);
+
+  const engine = (getSetting("vad_engine") || "silero") as VadEngine;
+  const threshold = getSetting("vad_threshold") ?? 0.3;
+  const prefillFrames = getSetting("vad_prefill_frames") ?? 15;
+  const hangoverFrames = getSetting("vad_hangover_frames") ?? 15;
+  const onsetFrames = getSetting("vad_onset_frames") ?? 2;
+  const trimEnabled = getSetting("vad_trim_enabled") ?? true;
+
+  // Keep the microphone classified for as long as these settings are visible
+  useEffect(() => {
+    const unlisten = listen<boolean>("vad-preview", (event) => {
+      setIsSpeech(event.payload);
+    });
+    commands.startVadPreview();
+
+    return () => {
+      unlisten.then((fn) => fn());
+      commands.stopVadPreview();
+    };
+  }, []);
+
+  const engineOptions = [
+    {
+      value: "silero",
+      label: t("settings.advanced.vad.engine.options.silero"),
+    },
+    {
+      value: "energy",
+      label: t("settings.advanced.vad.engine.options.energy"),
+    },
+  ];
+
+  const formatFrames = (value: number) => `${value * FRAME_MS} ms`;
+
+  return (
+    <>
+      <SettingContainer
+        title={t("settings.advanced.vad.preview.title")}
+        description={t("settings.advanced.vad.preview.description")}
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+      >
+        <div className="flex items-center gap-2">
+          <span
+            className={`w-3 h-3 rounded-full transition-colors ${
+              isSpeech ? "bg-logo-primary" : "bg-mid-gray/40"
+            }`}
+          />
+          <span className="text-sm">
+            {isSpeech
+              ? t("settings.advanced.vad.preview.speech")
+              : t("settings.advanced.vad.preview.silence")}
+          </span>
+        </div>
+      </SettingContainer>
+      <SettingContainer
+        title={t("settings.advanced.vad.engine.title")}
+        description={t("settings.advanced.vad.engine.description")}
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+      >
+        <Dropdown
+          options={engineOptions}
+          selectedValue={engine}
+          onSelect={(value) =>
+            updateSetting("vad_engine", value as VadEngine)
+          }
+          disabled={isUpdating("vad_engine")}
+        />
+      </SettingContainer>
+      <Slider
+        value={threshold}
+        onChange={(value) => updateSetting("vad_threshold", value)}
+        min={0.05}
+        max={0.95}
+        step={0.05}
+        label={t("settings.advanced.vad.threshold.title")}
+        description={t("settings.advanced.vad.threshold.description")}
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+      />
+      <Slider
+        value={onsetFrames}
+        onChange={(value) => updateSetting("vad_onset_frames", value)}
+        min={1}
+        max={10}
+        step={1}
+        label={t("settings.advanced.vad.onset.title")}
+        description={t("settings.advanced.vad.onset.description")}
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+        formatValue={formatFrames}
+      />
+      <Slider
+        value={prefillFrames}
+        onChange={(value) => updateSetting("vad_prefill_frames", value)}
+        min={0}
+        max={50}
+        step={1}
+        label={t("settings.advanced.vad.prefill.title")}
+        description={t("settings.advanced.vad.prefill.description")}
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+        formatValue={formatFrames}
+      />
+      <Slider
+        value={hangoverFrames}
+        onChange={(value) => updateSetting("vad_hangover_frames", value)}
+        min={0}
+        max={50}
+        step={1}
+        label={t("settings.advanced.vad.hangover.title")}
+        description={t("settings.advanced.vad.hangover.description")}
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+        formatValue={formatFrames}
+      />
+      <ToggleSwitch
+        checked={trimEnabled}
+        onChange={(value) => updateSetting("vad_trim_enabled", value)}
+        isUpdating={isUpdating("vad_trim_enabled")}
+        label={t("settings.advanced.vad.trim.label")}
+        description={t("settings.advanced.vad.trim.description")}
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+      />
+    </>
+  );
+};
@@ -0,0 +1,111 @@
+import React, { useEffect, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { toast } from "sonner";
+import { commands } from "@/bindings";
+import { ToggleSwitch } from "../ui/ToggleSwitch";
+import { SettingContainer } from "../ui/SettingContainer";
+import { Button } from "../ui/Button";
+import { useSettings } from "../../hooks/useSettings";
+
+// Keep in sync with MIN_WAKE_WORD_SAMPLES in the wake word detector
+const MIN_SAMPLES = 2;
+
+interface WakeWordProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const WakeWord: React.FC<WakeWordProps> = ({
+  descriptionMode = "tooltip",
+  grouped = false,
+}) => {
+  const { t } = useTranslation();
+  const { getSetting, updateSetting, isUpdating } = useSettings();
+  const [sampleCount, setSampleCount] = useState(0);
+  const [isRecording, setIsRecording] = useState(false);
+
+  const handsFreeEnabled = getSetting("hands_free_enabled") || false;
+  const enabled = getSetting("wake_word_enabled") || false;
+
+  useEffect(() => {
+    commands.getWakeWordSampleCount().then((result) => {
+      if (result.status === "ok") {
+        setSampleCount(result.data);
+      }
+    });
+  }, []);
+
+  const handleRecord = async () => {
+    setIsRecording(true);
+    try {
+      const result = await commands.recordWakeWordSample();
+      if (result.status === "ok") {
+        setSampleCount(result.data);
+      } else {
+        toast.error(result.error);
+      }
+    } finally {
+      setIsRecording(false);
+    }
+  };
+
+  const handleClear = async () => {
+    const result = await commands.clearWakeWordSamples();
+    if (result.status === "ok") {
+      setSampleCount(0);
+      updateSetting("wake_word_enabled", false);
+    } else {
+      toast.error(result.error);
+    }
+  };
+
+  return (
+    <>
+      <ToggleSwitch
+        checked={enabled}
+        onChange={(value) => updateSetting("wake_word_enabled", value)}
+        isUpdating={isUpdating("wake_word_enabled")}
+        disabled={!handsFreeEnabled || (!enabled && sampleCount < MIN_SAMPLES)}
+        label={t("settings.general.wakeWord.label")}
+        description={t("settings.general.wakeWord.description")}
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+      />
+      <SettingContainer
+        title={t("settings.general.wakeWord.samples.title")}
+        description={t("settings.general.wakeWord.samples.description", {
+          count: MIN_SAMPLES,
+        })}
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+        disabled={!handsFreeEnabled}
+      >
+        <div className="flex items-center gap-2">
+          <span className="text-sm text-text/70 tabular-nums">
+            {t("settings.general.wakeWord.samples.count", {
+              count: sampleCount,
+            })}
+          </span>
+          <Button
+            variant="secondary"
+            size="sm"
+            onClick={handleRecord}
+            disabled={!handsFreeEnabled || isRecording}
+          >
+            {isRecording
+              ? t("settings.general.wakeWord.samples.recording")
+              : t("settings.general.wakeWord.samples.record")}
+          </Button>
+          <Button
+            variant="ghost"
+            size="sm"
+            onClick={handleClear}
+            disabled={sampleCount === 0 || isRecording}
+          >
+            {t("settings.general.wakeWord.samples.clear")}
+          </Button>
+        </div>
+      </SettingContainer>
+    </>
+  );
+};
@@ -8,7 +8,15 @@ import { SettingsGroup } from "../../ui/SettingsGroup";
 import { StartHidden } from "../StartHidden";
 import { AutostartToggle } from "../AutostartToggle";
 import { PasteMethodSetting } from "../PasteMethod";
+import { DirectTyping } from "../DirectTyping";
 import { ClipboardHandlingSetting } from "../ClipboardHandling";
+import { FocusChangeBehaviorSetting } from "../FocusChangeBehavior";
+import { OutputSinks } from "../OutputSinks";
+import { PastePreview } from "../PastePreview";
+import { VadSettings } from "../VadSettings";
+import { AudioProcessing } from "../AudioProcessing";
+import { MicrophoneCapture } from "../MicrophoneCapture";
+import { PreRoll } from "../PreRoll";
 
 export const AdvancedSettings: React.FC = () => {
   const { t } = useTranslation();
@@ -19,11 +27,23 @@ export const AdvancedSettings: React.FC = () => {
         <AutostartToggle descriptionMode="tooltip" grouped={true} />
         <ShowOverlay descriptionMode="tooltip" grouped={true} />
         <PasteMethodSetting descriptionMode="tooltip" grouped={true} />
+        <DirectTyping descriptionMode="tooltip" grouped={true} />
         <ClipboardHandlingSetting descriptionMode="tooltip" grouped={true} />
+        <FocusChangeBehaviorSetting descriptionMode="tooltip" grouped={true} />
+        <PastePreview descriptionMode="tooltip" grouped={true} />
+        <OutputSinks descriptionMode="tooltip" grouped={true} />
         <TranslateToEnglish descriptionMode="tooltip" grouped={true} />
         <ModelUnloadTimeoutSetting descriptionMode="tooltip" grouped={true} />
         <CustomWords descriptionMode="tooltip" grouped />
       </SettingsGroup>
+      <SettingsGroup title={t("settings.advanced.audioProcessing.title")}>
+        <PreRoll descriptionMode="tooltip" grouped={true} />
+        <AudioProcessing descriptionMode="tooltip" grouped={true} />
+        <MicrophoneCapture descriptionMode="tooltip" grouped={true} />
+      </SettingsGroup>
+      <SettingsGroup title={t("settings.advanced.vad.title")}>
+        <VadSettings descriptionMode="tooltip" grouped={true} />
+      </SettingsGroup>
     </div>
   );
 };
@@ -4,6 +4,7 @@ import { type } from "@tauri-apps/plugin-os";
 import { WordCorrectionThreshold } from "./WordCorrectionThreshold";
 import { LogDirectory } from "./LogDirectory";
 import { LogLevelSelector } from "./LogLevelSelector";
+import { TranscriptionMetrics } from "./TranscriptionMetrics";
 import { SettingsGroup } from "../../ui/SettingsGroup";
 import { HistoryLimit } from "../HistoryLimit";
 import { AlwaysOnMicrophone } from "../AlwaysOnMicrophone";
@@ -53,6 +54,7 @@ export const DebugSettings: React.FC = () => {
           />
         )}
       </SettingsGroup>
+      <TranscriptionMetrics />
     </div>
   );
 };
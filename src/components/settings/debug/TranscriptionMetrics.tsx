@@ -0,0 +1,109 @@
+import React, { useEffect, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { listen } from "@tauri-apps/api/event";
+import { commands, type MetricsSummary } from "@/bindings";
+import { SettingsGroup } from "../../ui/SettingsGroup";
+
+const METRICS_DAYS = 30;
+
+const formatMs = (value: number | null) =>
+  value === null ? "–" : `${Math.round(value)} ms`;
+
+export const TranscriptionMetrics: React.FC = () => {
+  const { t } = useTranslation();
+  const [summaries, setSummaries] = useState<MetricsSummary[]>([]);
+
+  useEffect(() => {
+    const loadMetrics = async () => {
+      const result = await commands.getTranscriptionMetrics(METRICS_DAYS);
+      if (result.status === "ok") {
+        setSummaries(result.data);
+      }
+    };
+
+    loadMetrics();
+
+    // A new history entry means a dictation just finished
+    const historyUnlisten = listen("history-updated", () => {
+      loadMetrics();
+    });
+
+    return () => {
+      historyUnlisten.then((fn) => fn());
+    };
+  }, []);
+
+  return (
+    <SettingsGroup
+      title={t("settings.debug.metrics.title")}
+      description={t("settings.debug.metrics.description", {
+        days: METRICS_DAYS,
+      })}
+    >
+      {summaries.length === 0 ? (
+        <p className="px-4 py-3 text-sm text-mid-gray">
+          {t("settings.debug.metrics.empty")}
+        </p>
+      ) : (
+        <div className="overflow-x-auto">
+          <table className="w-full text-xs">
+            <thead className="text-mid-gray text-left">
+              <tr>
+                <th className="px-4 py-2 font-medium">
+                  {t("settings.debug.metrics.day")}
+                </th>
+                <th className="px-2 py-2 font-medium">
+                  {t("settings.debug.metrics.model")}
+                </th>
+                <th className="px-2 py-2 font-medium text-right">
+                  {t("settings.debug.metrics.count")}
+                </th>
+                <th className="px-2 py-2 font-medium text-right">
+                  {t("settings.debug.metrics.load")}
+                </th>
+                <th className="px-2 py-2 font-medium text-right">
+                  {t("settings.debug.metrics.inference")}
+                </th>
+                <th className="px-2 py-2 font-medium text-right">
+                  {t("settings.debug.metrics.postProcess")}
+                </th>
+                <th className="px-2 py-2 font-medium text-right">
+                  {t("settings.debug.metrics.paste")}
+                </th>
+                <th className="px-4 py-2 font-medium text-right">
+                  {t("settings.debug.metrics.realTimeFactor")}
+                </th>
+              </tr>
+            </thead>
+            <tbody className="divide-y divide-mid-gray/20">
+              {summaries.map((summary) => (
+                <tr key={`${summary.day}-${summary.model_id}`}>
+                  <td className="px-4 py-2 font-mono">{summary.day}</td>
+                  <td className="px-2 py-2">{summary.model_id}</td>
+                  <td className="px-2 py-2 text-right">
+                    {summary.dictation_count}
+                  </td>
+                  <td className="px-2 py-2 text-right">
+                    {formatMs(summary.avg_load_ms)}
+                  </td>
+                  <td className="px-2 py-2 text-right">
+                    {formatMs(summary.avg_inference_ms)}
+                  </td>
+                  <td className="px-2 py-2 text-right">
+                    {formatMs(summary.avg_post_process_ms)}
+                  </td>
+                  <td className="px-2 py-2 text-right">
+                    {formatMs(summary.avg_paste_ms)}
+                  </td>
+                  <td className="px-4 py-2 text-right font-mono">
+                    {summary.avg_real_time_factor.toFixed(3)}
+                  </td>
+                </tr>
+              ))}
+            </tbody>
+          </table>
+        </div>
+      )}
+    </SettingsGroup>
+  );
+};
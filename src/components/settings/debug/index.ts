@@ -1,3 +1,4 @@
 export { WordCorrectionThreshold } from "./WordCorrectionThreshold";
 export { LogDirectory } from "./LogDirectory";
 export { LogLevelSelector } from "./LogLevelSelector";
+export { TranscriptionMetrics } from "./TranscriptionMetrics";
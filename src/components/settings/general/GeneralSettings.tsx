@@ -1,11 +1,16 @@
 import React from "react";
 import { useTranslation } from "react-i18next";
 import { MicrophoneSelector } from "../MicrophoneSelector";
+import { FallbackMicrophones } from "../FallbackMicrophones";
+import { CaptureSourceSelector } from "../CaptureSourceSelector";
 import { LanguageSelector } from "../LanguageSelector";
 import { AleFlowShortcut } from "../AleFlowShortcut";
 import { SettingsGroup } from "../../ui/SettingsGroup";
 import { OutputDeviceSelector } from "../OutputDeviceSelector";
 import { PushToTalk } from "../PushToTalk";
+import { HandsFreeMode } from "../HandsFreeMode";
+import { WakeWord } from "../WakeWord";
+import { Diarization } from "../Diarization";
 import { AudioFeedback } from "../AudioFeedback";
 import { useSettings } from "../../../hooks/useSettings";
 import { VolumeSlider } from "../VolumeSlider";
@@ -17,11 +22,20 @@ export const GeneralSettings: React.FC = () => {
     <div className="max-w-3xl w-full mx-auto space-y-6">
       <SettingsGroup title={t("settings.general.title")}>
         <AleFlowShortcut shortcutId="transcribe" grouped={true} />
+        <AleFlowShortcut shortcutId="meeting" grouped={true} />
+        <AleFlowShortcut shortcutId="undo_last_dictation" grouped={true} />
+        <AleFlowShortcut shortcutId="repaste_last" grouped={true} />
+        <AleFlowShortcut shortcutId="history_picker" grouped={true} />
+        <Diarization descriptionMode="tooltip" grouped={true} />
         <LanguageSelector descriptionMode="tooltip" grouped={true} />
         <PushToTalk descriptionMode="tooltip" grouped={true} />
+        <HandsFreeMode descriptionMode="tooltip" grouped={true} />
+        <WakeWord descriptionMode="tooltip" grouped={true} />
       </SettingsGroup>
       <SettingsGroup title={t("settings.sound.title")}>
+        <CaptureSourceSelector descriptionMode="tooltip" grouped={true} />
         <MicrophoneSelector descriptionMode="tooltip" grouped={true} />
+        <FallbackMicrophones descriptionMode="tooltip" grouped={true} />
         <AudioFeedback descriptionMode="tooltip" grouped={true} />
         <OutputDeviceSelector
           descriptionMode="tooltip"
@@ -2,11 +2,16 @@ import React, { useState, useEffect, useCallback } from "react";
 import { useTranslation } from "react-i18next";
 import { AudioPlayer } from "../../ui/AudioPlayer";
 import { Button } from "../../ui/Button";
-import { Copy, Star, Check, Trash2, FolderOpen } from "lucide-react";
+import { Copy, Star, Check, Trash2, FolderOpen, Download } from "lucide-react";
 import { convertFileSrc } from "@tauri-apps/api/core";
 import { listen } from "@tauri-apps/api/event";
-import { commands, type HistoryEntry } from "@/bindings";
+import { toast } from "sonner";
+import { commands, type HistoryEntry, type TranscriptFormat } from "@/bindings";
 import { formatDateTime } from "@/utils/dateFormat";
+import { MeetingRecorder } from "./MeetingRecorder";
+import { SpeakerNames } from "./SpeakerNames";
+
+const EXPORT_FORMATS: TranscriptFormat[] = ["srt", "vtt", "markdown"];
 
 interface OpenRecordingsButtonProps {
   onClick: () => void;
@@ -122,6 +127,7 @@ export const HistorySettings: React.FC = () => {
   if (loading) {
     return (
       <div className="max-w-3xl w-full mx-auto space-y-6">
+        <MeetingRecorder />
         <div className="space-y-2">
           <div className="px-4 flex items-center justify-between">
             <div>
@@ -147,6 +153,7 @@ export const HistorySettings: React.FC = () => {
   if (historyEntries.length === 0) {
     return (
       <div className="max-w-3xl w-full mx-auto space-y-6">
+        <MeetingRecorder />
         <div className="space-y-2">
           <div className="px-4 flex items-center justify-between">
             <div>
@@ -171,6 +178,7 @@ export const HistorySettings: React.FC = () => {
 
   return (
     <div className="max-w-3xl w-full mx-auto space-y-6">
+      <MeetingRecorder />
       <div className="space-y-2">
         <div className="px-4 flex items-center justify-between">
           <div>
@@ -244,12 +252,26 @@ const HistoryEntryComponent: React.FC<HistoryEntryProps> = ({
     }
   };
 
+  const handleExport = async (format: TranscriptFormat) => {
+    const result = await commands.exportHistoryEntry(entry.id, format);
+    if (result.status === "error") {
+      toast.error(t("settings.history.export.error", { error: result.error }));
+    }
+  };
+
   const formattedDate = formatDateTime(String(entry.timestamp), i18n.language);
 
   return (
     <div className="px-4 py-2 pb-5 flex flex-col gap-3">
       <div className="flex justify-between items-center">
-        <p className="text-sm font-medium">{formattedDate}</p>
+        <p className="text-sm font-medium">
+          {formattedDate}
+          {entry.discarded && (
+            <span className="ml-2 text-xs font-normal text-mid-gray">
+              {t("settings.history.discarded")}
+            </span>
+          )}
+        </p>
         <div className="flex items-center gap-1">
           <button
             onClick={handleCopyText}
@@ -290,10 +312,30 @@ const HistoryEntryComponent: React.FC<HistoryEntryProps> = ({
           </button>
         </div>
       </div>
-      <p className="italic text-text/90 text-sm pb-2">
+      <p
+        className={`italic text-text/90 text-sm pb-2 whitespace-pre-line ${entry.discarded ? "line-through opacity-60" : ""}`}
+      >
         {entry.transcription_text}
       </p>
+      <SpeakerNames entryId={entry.id} speakers={entry.speakers} />
       {audioUrl && <AudioPlayer src={audioUrl} className="w-full" />}
+      <div className="flex items-center gap-2">
+        <span className="text-xs text-mid-gray">
+          {t("settings.history.export.title")}
+        </span>
+        {EXPORT_FORMATS.map((format) => (
+          <Button
+            key={format}
+            onClick={() => handleExport(format)}
+            variant="secondary"
+            size="sm"
+            className="flex items-center gap-1"
+          >
+            <Download className="w-3 h-3" />
+            <span>{t(`settings.history.export.${format}`)}</span>
+          </Button>
+        ))}
+      </div>
     </div>
   );
 };
@@ -0,0 +1,77 @@
+import React, { useEffect, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { listen } from "@tauri-apps/api/event";
+import { toast } from "sonner";
+import { commands, type MeetingStatus } from "@/bindings";
+import { Button } from "../../ui/Button";
+
+export const MeetingRecorder: React.FC = () => {
+  const { t } = useTranslation();
+  const [status, setStatus] = useState<MeetingStatus | null>(null);
+  const [busy, setBusy] = useState(false);
+
+  useEffect(() => {
+    commands.getMeetingStatus().then(setStatus);
+
+    const statusUnlisten = listen<MeetingStatus>("meeting-status", (event) =>
+      setStatus(event.payload),
+    );
+    const errorUnlisten = listen<string>("meeting-error", (event) => {
+      toast.error(
+        t("settings.history.meeting.error", { error: event.payload }),
+      );
+    });
+
+    return () => {
+      statusUnlisten.then((fn) => fn());
+      errorUnlisten.then((fn) => fn());
+    };
+  }, [t]);
+
+  if (!status) {
+    return null;
+  }
+
+  const handleToggle = async () => {
+    setBusy(true);
+    const result = status.recording
+      ? await commands.stopMeeting()
+      : await commands.startMeeting();
+    if (result.status === "error") {
+      toast.error(t("settings.history.meeting.error", { error: result.error }));
+    }
+    setBusy(false);
+  };
+
+  let description = t("settings.history.meeting.description");
+  if (status.recording) {
+    description = t("settings.history.meeting.recording", {
+      count: status.recorded_chunks,
+    });
+  } else if (status.pending_chunks > 0) {
+    description = t("settings.history.meeting.transcribing", {
+      count: status.pending_chunks,
+    });
+  }
+
+  return (
+    <div className="bg-background border border-mid-gray/20 rounded-lg px-4 py-3 flex items-center justify-between gap-4">
+      <div className="flex flex-col gap-1">
+        <p className="text-sm font-medium">
+          {t("settings.history.meeting.title")}
+        </p>
+        <p className="text-xs text-mid-gray">{description}</p>
+      </div>
+      <Button
+        onClick={handleToggle}
+        disabled={busy}
+        variant={status.recording ? "danger" : "primary"}
+        size="sm"
+      >
+        {status.recording
+          ? t("settings.history.meeting.stop")
+          : t("settings.history.meeting.start")}
+      </Button>
+    </div>
+  );
+};
@@ -0,0 +1,62 @@
+import React from "react";
+import { useTranslation } from "react-i18next";
+import { toast } from "sonner";
+import { commands, type SpeakerLabel } from "@/bindings";
+import { Input } from "../../ui/Input";
+
+interface SpeakerNamesProps {
+  entryId: number;
+  speakers: SpeakerLabel[];
+}
+
+export const SpeakerNames: React.FC<SpeakerNamesProps> = ({
+  entryId,
+  speakers,
+}) => {
+  const { t } = useTranslation();
+
+  const rename = async (speaker: SpeakerLabel, name: string) => {
+    if (name.trim() === (speaker.name ?? "")) {
+      return;
+    }
+    const result = await commands.renameHistorySpeaker(
+      entryId,
+      speaker.speaker,
+      name,
+    );
+    if (result.status === "error") {
+      toast.error(
+        t("settings.history.speakers.error", { error: result.error }),
+      );
+    }
+  };
+
+  if (speakers.length === 0) {
+    return null;
+  }
+
+  return (
+    <div className="flex flex-wrap items-center gap-2">
+      <span className="text-xs text-mid-gray">
+        {t("settings.history.speakers.title")}
+      </span>
+      {speakers.map((speaker) => (
+        <Input
+          key={`${speaker.speaker}-${speaker.name ?? ""}`}
+          variant="compact"
+          className="w-32"
+          defaultValue={speaker.name ?? ""}
+          placeholder={t("settings.history.speakers.default", {
+            number: speaker.speaker + 1,
+          })}
+          onBlur={(event) => rename(speaker, event.currentTarget.value)}
+          onKeyDown={(event) => {
+            if (event.key === "Enter") {
+              event.currentTarget.blur();
+            }
+          }}
+        />
+      ))}
+    </div>
+  );
+};
@@ -12,6 +12,12 @@ export { ClamshellMicrophoneSelector } from "./ClamshellMicrophoneSelector";
 export { OutputDeviceSelector } from "./OutputDeviceSelector";
 export { AlwaysOnMicrophone } from "./AlwaysOnMicrophone";
 export { PushToTalk } from "./PushToTalk";
+export { HandsFreeMode } from "./HandsFreeMode";
+export { WakeWord } from "./WakeWord";
+export { Diarization } from "./Diarization";
+export { VadSettings } from "./VadSettings";
+export { AudioProcessing } from "./AudioProcessing";
+export { PreRoll } from "./PreRoll";
 export { AudioFeedback } from "./AudioFeedback";
 export { ShowOverlay } from "./ShowOverlay";
 export { AleFlowShortcut } from "./AleFlowShortcut";
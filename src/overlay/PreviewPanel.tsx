@@ -0,0 +1,126 @@
+import React, { useEffect, useRef, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { commands, type LLMPrompt } from "@/bindings";
+
+export interface PreviewPayload {
+  text: string;
+  prompt_id: string | null;
+  countdown_secs: number;
+}
+
+interface PreviewPanelProps {
+  preview: PreviewPayload;
+  prompts: LLMPrompt[];
+}
+
+// Pastes on its own when the countdown runs out, unless the text is edited or
+// another prompt is picked first
+const PreviewPanel: React.FC<PreviewPanelProps> = ({ preview, prompts }) => {
+  const { t } = useTranslation();
+  const [text, setText] = useState(preview.text);
+  const [promptId, setPromptId] = useState(preview.prompt_id ?? "");
+  const [remaining, setRemaining] = useState(preview.countdown_secs);
+  const [paused, setPaused] = useState(preview.countdown_secs === 0);
+  const [switching, setSwitching] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+  const textRef = useRef<HTMLTextAreaElement>(null);
+
+  const accept = () => {
+    commands.acceptPreview(text);
+  };
+
+  useEffect(() => {
+    textRef.current?.focus();
+  }, []);
+
+  useEffect(() => {
+    if (paused) {
+      return;
+    }
+    if (remaining <= 0) {
+      accept();
+      return;
+    }
+    const timer = setTimeout(() => setRemaining((secs) => secs - 1), 1000);
+    return () => clearTimeout(timer);
+  }, [paused, remaining]);
+
+  const switchPrompt = async (value: string) => {
+    setPaused(true);
+    setPromptId(value);
+    setSwitching(true);
+    const result = await commands.switchPreviewPrompt(value || null);
+    if (result.status === "ok") {
+      setText(result.data);
+      setError(null);
+    } else {
+      setError(result.error);
+    }
+    setSwitching(false);
+  };
+
+  const handleKeyDown = (e: React.KeyboardEvent) => {
+    if (e.key === "Enter" && !e.shiftKey) {
+      e.preventDefault();
+      if (!switching) {
+        accept();
+      }
+    } else if (e.key === "Escape") {
+      e.preventDefault();
+      commands.cancelOperation();
+    }
+  };
+
+  return (
+    <div className="preview-panel" onKeyDown={handleKeyDown}>
+      <textarea
+        ref={textRef}
+        className="preview-text"
+        value={text}
+        onChange={(e) => {
+          setPaused(true);
+          setText(e.target.value);
+        }}
+        disabled={switching}
+      />
+      <div className="preview-footer">
+        <select
+          className="preview-prompt"
+          value={promptId}
+          onChange={(e) => switchPrompt(e.target.value)}
+          disabled={switching}
+        >
+          <option value="">{t("overlay.preview.original")}</option>
+          {prompts.map((prompt) => (
+            <option key={prompt.id} value={prompt.id}>
+              {prompt.name}
+            </option>
+          ))}
+        </select>
+        <span className="preview-status">
+          {switching
+            ? t("overlay.preview.rewriting")
+            : (error ??
+              (paused
+                ? t("overlay.preview.pressEnter")
+                : t("overlay.preview.pastingIn", { seconds: remaining })))}
+        </span>
+        <button
+          className="preview-button"
+          onClick={() => commands.cancelOperation()}
+        >
+          {t("overlay.preview.cancel")}
+        </button>
+        <button
+          className="preview-button primary"
+          onClick={accept}
+          disabled={switching}
+        >
+          {t("overlay.preview.paste")}
+        </button>
+      </div>
+    </div>
+  );
+};
+
+export default PreviewPanel;
@@ -7,10 +7,11 @@ import {
   CancelIcon,
 } from "../components/icons";
 import "./RecordingOverlay.css";
-import { commands } from "@/bindings";
+import { commands, type LLMPrompt } from "@/bindings";
 import { syncLanguageFromSettings } from "@/i18n";
+import PreviewPanel, { type PreviewPayload } from "./PreviewPanel";
 
-type OverlayState = "recording" | "transcribing";
+type OverlayState = "recording" | "transcribing" | "insert" | "preview";
 
 const RecordingOverlay: React.FC = () => {
   const { t } = useTranslation();
@@ -18,6 +19,10 @@ const RecordingOverlay: React.FC = () => {
   const [state, setState] = useState<OverlayState>("recording");
   const [levels, setLevels] = useState<number[]>(Array(16).fill(0));
   const smoothedLevelsRef = useRef<number[]>(Array(16).fill(0));
+  const [preview, setPreview] = useState<PreviewPayload | null>(null);
+  const [previewCount, setPreviewCount] = useState(0);
+  const [prompts, setPrompts] = useState<LLMPrompt[]>([]);
+  const [draggable, setDraggable] = useState(false);
 
   useEffect(() => {
     const setupEventListeners = async () => {
@@ -26,10 +31,31 @@ const RecordingOverlay: React.FC = () => {
         // Sync language from settings each time overlay is shown
         await syncLanguageFromSettings();
         const overlayState = event.payload as OverlayState;
+        // With a custom position the overlay is placed by dragging it
+        const result = await commands.getAppSettings();
+        if (result.status === "ok") {
+          setDraggable(result.data.overlay_position === "custom");
+        }
         setState(overlayState);
         setIsVisible(true);
       });
 
+      // Listen for a finished dictation to review before pasting
+      const unlistenPreview = await listen<PreviewPayload>(
+        "show-preview",
+        async (event) => {
+          await syncLanguageFromSettings();
+          const result = await commands.getAppSettings();
+          if (result.status === "ok") {
+            setPrompts(result.data.post_process_prompts ?? []);
+          }
+          setPreview(event.payload);
+          setPreviewCount((count) => count + 1);
+          setState("preview");
+          setIsVisible(true);
+        },
+      );
+
       // Listen for hide-overlay event from Rust
       const unlistenHide = await listen("hide-overlay", () => {
         setIsVisible(false);
@@ -52,6 +78,7 @@ const RecordingOverlay: React.FC = () => {
       // Cleanup function
       return () => {
         unlistenShow();
+        unlistenPreview();
         unlistenHide();
         unlistenLevel();
       };
@@ -68,11 +95,28 @@ const RecordingOverlay: React.FC = () => {
     }
   };
 
+  if (state === "preview" && preview) {
+    return (
+      <div
+        className={`recording-overlay preview ${isVisible ? "fade-in" : ""}`}
+      >
+        <PreviewPanel key={previewCount} preview={preview} prompts={prompts} />
+      </div>
+    );
+  }
+
+  const dragRegion = draggable || undefined;
+
   return (
-    <div className={`recording-overlay ${isVisible ? "fade-in" : ""}`}>
-      <div className="overlay-left">{getIcon()}</div>
+    <div
+      className={`recording-overlay ${isVisible ? "fade-in" : ""}`}
+      data-tauri-drag-region={dragRegion}
+    >
+      <div className="overlay-left" data-tauri-drag-region={dragRegion}>
+        {getIcon()}
+      </div>
 
-      <div className="overlay-middle">
+      <div className="overlay-middle" data-tauri-drag-region={dragRegion}>
         {state === "recording" && (
           <div className="bars-container">
             {levels.map((v, i) => (
@@ -91,10 +135,20 @@ const RecordingOverlay: React.FC = () => {
         {state === "transcribing" && (
           <div className="transcribing-text">{t("overlay.transcribing")}</div>
         )}
+        {state === "insert" && (
+          <div
+            className="insert-text"
+            onClick={() => {
+              commands.insertPendingText();
+            }}
+          >
+            {t("overlay.clickToInsert")}
+          </div>
+        )}
       </div>
 
       <div className="overlay-right">
-        {state === "recording" && (
+        {(state === "recording" || state === "insert") && (
           <div
             className="cancel-button"
             onClick={() => {
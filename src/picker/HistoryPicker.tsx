@@ -0,0 +1,134 @@
+import { listen } from "@tauri-apps/api/event";
+import { getCurrentWindow } from "@tauri-apps/api/window";
+import React, { useEffect, useMemo, useRef, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { commands, type HistoryEntry } from "@/bindings";
+import { syncLanguageFromSettings } from "@/i18n";
+import { formatDateTime } from "@/utils/dateFormat";
+
+const MAX_RESULTS = 50;
+
+const textOf = (entry: HistoryEntry) =>
+  entry.post_processed_text ?? entry.transcription_text;
+
+const HistoryPicker: React.FC = () => {
+  const { t, i18n } = useTranslation();
+  const [entries, setEntries] = useState<HistoryEntry[]>([]);
+  const [query, setQuery] = useState("");
+  const [selected, setSelected] = useState(0);
+  const inputRef = useRef<HTMLInputElement>(null);
+  const listRef = useRef<HTMLUListElement>(null);
+
+  useEffect(() => {
+    const setup = async () => {
+      const unlistenShow = await listen("show-history-picker", async () => {
+        await syncLanguageFromSettings();
+        setQuery("");
+        setSelected(0);
+        const result = await commands.getHistoryEntries();
+        setEntries(result.status === "ok" ? result.data : []);
+        inputRef.current?.focus();
+      });
+
+      // Clicking anywhere else dismisses the picker
+      const unlistenFocus = await getCurrentWindow().onFocusChanged(
+        ({ payload: focused }) => {
+          if (!focused) {
+            commands.hideHistoryPicker();
+          }
+        },
+      );
+
+      return () => {
+        unlistenShow();
+        unlistenFocus();
+      };
+    };
+
+    const cleanup = setup();
+    return () => {
+      cleanup.then((fn) => fn());
+    };
+  }, []);
+
+  const matches = useMemo(() => {
+    const needle = query.trim().toLowerCase();
+    return entries
+      .filter(
+        (entry) => !needle || textOf(entry).toLowerCase().includes(needle),
+      )
+      .slice(0, MAX_RESULTS);
+  }, [entries, query]);
+
+  useEffect(() => {
+    listRef.current?.children[selected]?.scrollIntoView({ block: "nearest" });
+  }, [selected]);
+
+  const paste = (entry: HistoryEntry | undefined) => {
+    if (entry) {
+      commands.pasteHistoryEntry(entry.id);
+    }
+  };
+
+  const handleKeyDown = (e: React.KeyboardEvent) => {
+    switch (e.key) {
+      case "ArrowDown":
+        e.preventDefault();
+        setSelected((i) => Math.min(i + 1, matches.length - 1));
+        break;
+      case "ArrowUp":
+        e.preventDefault();
+        setSelected((i) => Math.max(i - 1, 0));
+        break;
+      case "Enter":
+        e.preventDefault();
+        paste(matches[selected]);
+        break;
+      case "Escape":
+        e.preventDefault();
+        commands.hideHistoryPicker();
+        break;
+    }
+  };
+
+  return (
+    <div
+      className="flex flex-col h-full bg-background border border-mid-gray/20 rounded-lg overflow-hidden"
+      onKeyDown={handleKeyDown}
+    >
+      <input
+        ref={inputRef}
+        type="text"
+        value={query}
+        onChange={(e) => {
+          setQuery(e.target.value);
+          setSelected(0);
+        }}
+        placeholder={t("picker.searchPlaceholder")}
+        className="px-4 py-3 text-sm bg-transparent border-b border-mid-gray/20 outline-none"
+        autoFocus
+      />
+      {matches.length === 0 ? (
+        <p className="p-4 text-sm text-mid-gray">{t("picker.empty")}</p>
+      ) : (
+        <ul ref={listRef} className="flex-1 overflow-y-auto">
+          {matches.map((entry, index) => (
+            <li
+              key={entry.id}
+              onMouseEnter={() => setSelected(index)}
+              onClick={() => paste(entry)}
+              className={`px-4 py-2 cursor-pointer ${index === selected ? "bg-logo-primary/20" : ""}`}
+            >
+              <p className="text-sm line-clamp-2">{textOf(entry)}</p>
+              <p className="text-xs text-mid-gray">
+                {formatDateTime(String(entry.timestamp), i18n.language)}
+              </p>
+            </li>
+          ))}
+        </ul>
+      )}
+    </div>
+  );
+};
+
+export default HistoryPicker;
@@ -0,0 +1,11 @@
+import React from "react";
+import ReactDOM from "react-dom/client";
+import HistoryPicker from "./HistoryPicker";
+import "@/i18n";
+import "../App.css";
+
+ReactDOM.createRoot(document.getElementById("root") as HTMLElement).render(
+  <React.StrictMode>
+    <HistoryPicker />
+  </React.StrictMode>,
+);
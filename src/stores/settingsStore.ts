@@ -1,6 +1,13 @@
 import { create } from "zustand";
 import { subscribeWithSelector } from "zustand/middleware";
-import type { AppSettings as Settings, AudioDevice } from "@/bindings";
+import type {
+  AppSettings as Settings,
+  AudioDevice,
+  CaptureSource,
+  MicrophoneCaptureSettings,
+  OutputSink,
+  VadEngine,
+} from "@/bindings";
 import { commands } from "@/bindings";
 
 interface SettingsStore {
@@ -47,6 +54,10 @@ interface SettingsStore {
   updatePostProcessModel: (providerId: string, model: string) => Promise<void>;
   fetchPostProcessModels: (providerId: string) => Promise<string[]>;
   setPostProcessModelOptions: (providerId: string, models: string[]) => void;
+  updateMicrophoneCapture: (
+    deviceName: string,
+    capture: MicrophoneCaptureSettings,
+  ) => Promise<void>;
 
   // Internal state setters
   setSettings: (settings: Settings | null) => void;
@@ -72,6 +83,33 @@ const settingUpdaters: {
 } = {
   always_on_microphone: (value) =>
     commands.updateMicrophoneMode(value as boolean),
+  hands_free_enabled: (value) =>
+    commands.changeHandsFreeSetting(value as boolean),
+  hands_free_silence_ms: (value) =>
+    commands.changeHandsFreeSilenceSetting(value as number),
+  hands_free_max_duration_secs: (value) =>
+    commands.changeHandsFreeMaxDurationSetting(value as number),
+  wake_word_enabled: (value) =>
+    commands.changeWakeWordSetting(value as boolean),
+  vad_engine: (value) => commands.changeVadEngineSetting(value as VadEngine),
+  vad_threshold: (value) =>
+    commands.changeVadThresholdSetting(value as number),
+  vad_prefill_frames: (value) =>
+    commands.changeVadPrefillSetting(value as number),
+  vad_hangover_frames: (value) =>
+    commands.changeVadHangoverSetting(value as number),
+  vad_onset_frames: (value) => commands.changeVadOnsetSetting(value as number),
+  vad_trim_enabled: (value) => commands.changeVadTrimSetting(value as boolean),
+  dsp_high_pass_enabled: (value) =>
+    commands.changeDspHighPassSetting(value as boolean),
+  dsp_denoise_enabled: (value) =>
+    commands.changeDspDenoiseSetting(value as boolean),
+  dsp_agc_enabled: (value) => commands.changeDspAgcSetting(value as boolean),
+  dsp_clipping_detection_enabled: (value) =>
+    commands.changeDspClippingDetectionSetting(value as boolean),
+  dsp_ab_capture_enabled: (value) =>
+    commands.changeDspAbCaptureSetting(value as boolean),
+  pre_roll_ms: (value) => commands.changePreRollSetting(value as number),
   audio_feedback: (value) =>
     commands.changeAudioFeedbackSetting(value as boolean),
   audio_feedback_volume: (value) =>
@@ -93,6 +131,12 @@ const settingUpdaters: {
     commands.setClamshellMicrophone(
       (value as string) === "Default" ? "default" : (value as string),
     ),
+  fallback_microphones: (value) =>
+    commands.changeFallbackMicrophonesSetting(value as string[]),
+  capture_source: (value) =>
+    commands.changeCaptureSourceSetting(value as CaptureSource),
+  diarization_enabled: (value) =>
+    commands.changeDiarizationSetting(value as boolean),
   selected_output_device: (value) =>
     commands.setSelectedOutputDevice(
       (value as string) === "Default" || value === null
@@ -107,13 +151,27 @@ const settingUpdaters: {
     commands.changeSelectedLanguageSetting(value as string),
   overlay_position: (value) =>
     commands.changeOverlayPositionSetting(value as string),
+  overlay_monitor: (value) =>
+    commands.changeOverlayMonitorSetting(value as string | null),
   debug_mode: (value) => commands.changeDebugModeSetting(value as boolean),
   custom_words: (value) => commands.updateCustomWords(value as string[]),
   word_correction_threshold: (value) =>
     commands.changeWordCorrectionThresholdSetting(value as number),
   paste_method: (value) => commands.changePasteMethodSetting(value as string),
+  typing_delay_ms: (value) =>
+    commands.changeTypingDelaySetting(value as number),
+  typing_chunk_size: (value) =>
+    commands.changeTypingChunkSizeSetting(value as number),
   clipboard_handling: (value) =>
     commands.changeClipboardHandlingSetting(value as string),
+  focus_change_behavior: (value) =>
+    commands.changeFocusChangeBehaviorSetting(value as string),
+  output_sinks: (value) =>
+    commands.changeOutputSinksSetting(value as OutputSink[]),
+  paste_preview_enabled: (value) =>
+    commands.changePastePreviewEnabledSetting(value as boolean),
+  paste_preview_countdown_secs: (value) =>
+    commands.changePastePreviewCountdownSetting(value as number),
   history_limit: (value) => commands.updateHistoryLimit(value as number),
   post_process_enabled: (value) =>
     commands.changePostProcessEnabledSetting(value as boolean),
@@ -431,6 +489,28 @@ export const useSettingsStore = create<SettingsStore>()(
       return get().updatePostProcessSetting("model", providerId, model);
     },
 
+    updateMicrophoneCapture: async (deviceName, capture) => {
+      const { setUpdating, refreshSettings } = get();
+      const updateKey = `microphone_capture:${deviceName}`;
+
+      setUpdating(updateKey, true);
+
+      try {
+        const result = await commands.changeMicrophoneCaptureSetting(
+          deviceName,
+          capture,
+        );
+        if (result.status === "error") {
+          console.error("Failed to update microphone capture:", result.error);
+        }
+        await refreshSettings();
+      } catch (error) {
+        console.error("Failed to update microphone capture:", error);
+      } finally {
+        setUpdating(updateKey, false);
+      }
+    },
+
     fetchPostProcessModels: async (providerId) => {
       const updateKey = `post_process_models_fetch:${providerId}`;
       const { setUpdating, setPostProcessModelOptions } = get();